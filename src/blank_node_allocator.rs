@@ -0,0 +1,185 @@
+use crate::Node;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Assigns consecutive integer labels to blank nodes.
+///
+/// Blank nodes are only identified by their pointer and don't have labels of their own, but
+/// label-based formats like N-Triples need one for every blank node. An allocator bridges both
+/// models: Every blank node it [allocates](#method.allocate) or that is
+/// [registered](#method.register) gets the next free label, starting at 0, and keeps this label for
+/// the lifetime of the allocator. Serializers like
+/// [`write_ntriples_with_labels`](ntriples/fn.write_ntriples_with_labels.html) write the labels as
+/// `_:b0`, `_:b1`, and so on, and register the blank nodes they encounter on the way. Reusing an
+/// allocator therefore keeps the labels stable across several documents.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{BlankNodeAllocator, Node};
+///
+/// let mut allocator = BlankNodeAllocator::new();
+/// let first = allocator.allocate();
+/// let second = allocator.allocate();
+/// assert_eq!(Some(0), allocator.label(&first));
+/// assert_eq!(Some(1), allocator.label(&second));
+///
+/// // Blank nodes that were created elsewhere are registered to get a label, too.
+/// let external = Node::blank();
+/// assert_eq!(None, allocator.label(&external));
+/// assert_eq!(Some(2), allocator.register(&external));
+/// assert_eq!(Some(2), allocator.register(&external));
+///
+/// // IRIs and literals don't get labels.
+/// assert_eq!(None, allocator.register(&Node::from("urn:alice")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BlankNodeAllocator {
+    labels: HashMap<Node, usize>,
+}
+
+impl BlankNodeAllocator {
+    /// Create a new allocator without any labeled blank nodes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new blank node and label it.
+    pub fn allocate(&mut self) -> Node {
+        let node = Node::blank();
+        self.label_of_blank(&node);
+        node
+    }
+
+    /// Return the label of a blank node, labeling it first if it didn't have one yet.
+    ///
+    /// Returns `None` if the node isn't blank.
+    pub fn register(&mut self, node: &Node) -> Option<usize> {
+        if node.is_blank() {
+            Some(self.label_of_blank(node))
+        } else {
+            None
+        }
+    }
+
+    /// Return the label of a blank node, if it has one.
+    pub fn label(&self, node: &Node) -> Option<usize> {
+        self.labels.get(node).copied()
+    }
+
+    /// Compare two nodes, ordering labeled blank nodes by their labels.
+    ///
+    /// This is the order of [`Node`](struct.Node.html)'s `Ord` implementation, except that blank
+    /// nodes with a label are ordered by their labels, after all other nodes and before blank nodes
+    /// without a label.
+    pub fn compare(&self, a: &Node, b: &Node) -> Ordering {
+        match (self.label(a), self.label(b)) {
+            (Some(label_a), Some(label_b)) => label_a.cmp(&label_b),
+            (Some(_), None) if b.is_blank() => Ordering::Less,
+            (None, Some(_)) if a.is_blank() => Ordering::Greater,
+            _ => a.cmp(b),
+        }
+    }
+
+    /// Return the number of labeled blank nodes.
+    ///
+    /// This is also the label the next blank node will get.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Return `true` if no blank node has been labeled yet.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Return the label of a node that is known to be blank, labeling it if necessary.
+    pub(crate) fn label_of_blank(&mut self, node: &Node) -> usize {
+        let next_label = self.labels.len();
+        *self.labels.entry(node.clone()).or_insert(next_label)
+    }
+}
+
+/// Maps the blank node labels of a document to blank nodes.
+///
+/// Every label is mapped to the same node throughout the document, regardless of the position it
+/// appears in. This is the reverse of a `BlankNodeAllocator`, which is used to write documents.
+#[cfg(any(feature = "nom", feature = "jsonld"))]
+#[derive(Default)]
+pub(crate) struct BlankNodes {
+    nodes: HashMap<String, Node>,
+}
+
+#[cfg(any(feature = "nom", feature = "jsonld"))]
+impl BlankNodes {
+    pub fn get(&mut self, label: &str) -> Node {
+        self.nodes
+            .entry(label.to_owned())
+            .or_insert_with(Node::blank)
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    #[cfg(feature = "ntriples")]
+    fn labels_survive_serialization() {
+        let mut allocator = BlankNodeAllocator::new();
+        let external = Node::blank();
+        allocator.register(&external);
+        let allocated = allocator.allocate();
+
+        let mut graph = HashGraph::new();
+        let predicate = Node::from("urn:arrdf:tests:predicate:a");
+        let unlabeled = Node::blank();
+        graph.clone_insert(&allocated, &predicate, &external);
+        graph.clone_insert(&external, &predicate, &unlabeled);
+
+        let mut output: Vec<u8> = Vec::new();
+        ntriples::write_ntriples_with_labels(&graph, &mut allocator, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let mut lines: Vec<&str> = output.lines().collect();
+        lines.sort_unstable();
+        assert_eq!(
+            vec![
+                "_:b0 <urn:arrdf:tests:predicate:a> _:b2 .",
+                "_:b1 <urn:arrdf:tests:predicate:a> _:b0 .",
+            ],
+            lines
+        );
+
+        // The writer has labeled the remaining blank node.
+        assert_eq!(3, allocator.len());
+        assert_eq!(Some(2), allocator.label(&unlabeled));
+    }
+
+    #[test]
+    fn triples_sorted_by_labels() {
+        let mut allocator = BlankNodeAllocator::new();
+        let second = Node::blank();
+        let first = allocator.allocate();
+        allocator.register(&second);
+        let unlabeled = Node::blank();
+        let iri = Node::from("urn:arrdf:tests:node:a");
+        let predicate = Node::from("urn:arrdf:tests:predicate:a");
+
+        let mut graph = HashGraph::new();
+        for subject in &[&unlabeled, &second, &iri, &first] {
+            graph.clone_insert(subject, &predicate, &iri);
+        }
+        let subjects: Vec<&Node> = graph
+            .triples_sorted_by_labels(&allocator)
+            .into_iter()
+            .map(|(s, _, _)| s)
+            .collect();
+        assert_eq!(vec![&iri, &first, &second, &unlabeled], subjects);
+
+        assert_eq!(Ordering::Less, allocator.compare(&iri, &first));
+        assert_eq!(Ordering::Less, allocator.compare(&second, &unlabeled));
+        assert_eq!(Ordering::Equal, allocator.compare(&first, &first));
+    }
+}
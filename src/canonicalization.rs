@@ -0,0 +1,423 @@
+//! Canonical N-Quads of datasets, enabled by the `canonicalization` feature.
+//!
+//! This is an implementation of the [RDF Dataset Canonicalization](https://www.w3.org/TR/rdf-canon/)
+//! algorithm, also known as URDNA2015: Every blank node is labeled by hashing the quads it appears
+//! in, and blank nodes with equal hashes are told apart by hashing their surroundings until a
+//! label can be assigned purely from the structure of the dataset.
+use crate::node::escape;
+use crate::{Dataset, Node, NodeKind};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// A quad of a dataset, with the graph name as the last element.
+type Quad<'a> = (&'a Node, &'a Node, &'a Node, Option<&'a Node>);
+
+/// Issues sequential identifiers like `c14n0`, `c14n1` to blank nodes.
+#[derive(Clone)]
+struct IdentifierIssuer<'a> {
+    prefix: &'static str,
+    identifiers: HashMap<&'a Node, usize>,
+    /// The blank nodes in the order their identifiers were issued.
+    issued: Vec<&'a Node>,
+}
+
+impl<'a> IdentifierIssuer<'a> {
+    fn new(prefix: &'static str) -> Self {
+        Self {
+            prefix,
+            identifiers: HashMap::new(),
+            issued: Vec::new(),
+        }
+    }
+
+    fn get(&self, node: &Node) -> Option<String> {
+        self.identifiers
+            .get(node)
+            .map(|index| format!("{}{}", self.prefix, index))
+    }
+
+    fn issue(&mut self, node: &'a Node) -> String {
+        if !self.identifiers.contains_key(node) {
+            self.identifiers.insert(node, self.issued.len());
+            self.issued.push(node);
+        }
+        self.get(node).unwrap()
+    }
+}
+
+struct Canonicalizer<'a> {
+    quads: Vec<Quad<'a>>,
+    blank_quads: HashMap<&'a Node, Vec<Quad<'a>>>,
+    first_degree_hashes: HashMap<&'a Node, String>,
+    canonical: IdentifierIssuer<'a>,
+}
+
+impl<'a> Canonicalizer<'a> {
+    fn new(dataset: &'a Dataset) -> Self {
+        let quads: Vec<Quad> = dataset.iter().map(|(g, s, p, o)| (s, p, o, g)).collect();
+        let mut blank_quads: HashMap<&Node, Vec<Quad>> = HashMap::new();
+        for quad in quads.iter() {
+            let (s, p, o, g) = *quad;
+            let mut blanks: Vec<&Node> = vec![s, p, o]
+                .into_iter()
+                .chain(g)
+                .filter(|node| node.is_blank())
+                .collect();
+            blanks.sort_by_key(|node| node.internal().as_ptr());
+            blanks.dedup();
+            for blank in blanks {
+                blank_quads.entry(blank).or_default().push(*quad);
+            }
+        }
+
+        let mut canonicalizer = Self {
+            quads,
+            blank_quads,
+            first_degree_hashes: HashMap::new(),
+            canonical: IdentifierIssuer::new("c14n"),
+        };
+        let blanks: Vec<&Node> = canonicalizer.blank_quads.keys().copied().collect();
+        for blank in blanks {
+            let hash = canonicalizer.hash_first_degree_quads(blank);
+            canonicalizer.first_degree_hashes.insert(blank, hash);
+        }
+        canonicalizer
+    }
+
+    fn hash_first_degree_quads(&self, node: &Node) -> String {
+        let mut lines: Vec<String> = self.blank_quads[node]
+            .iter()
+            .map(|quad| {
+                nquad(quad, |blank| {
+                    if blank == node {
+                        "a".to_owned()
+                    } else {
+                        "z".to_owned()
+                    }
+                })
+            })
+            .collect();
+        lines.sort();
+        sha256(&lines.concat())
+    }
+
+    fn hash_related_blank_node(
+        &self,
+        related: &Node,
+        quad: &Quad,
+        issuer: &IdentifierIssuer,
+        position: char,
+    ) -> String {
+        let identifier = match self.canonical.get(related).or_else(|| issuer.get(related)) {
+            Some(identifier) => format!("_:{}", identifier),
+            None => self.first_degree_hashes[related].clone(),
+        };
+        let mut input = position.to_string();
+        if position != 'g' {
+            input.push_str(&format!("<{}>", quad.1.as_str()));
+        }
+        input.push_str(&identifier);
+        sha256(&input)
+    }
+
+    fn hash_n_degree_quads(
+        &self,
+        node: &'a Node,
+        mut issuer: IdentifierIssuer<'a>,
+    ) -> (String, IdentifierIssuer<'a>) {
+        let mut related_blanks: BTreeMap<String, Vec<&'a Node>> = BTreeMap::new();
+        for quad in self.blank_quads[node].iter() {
+            let (s, _, o, g) = *quad;
+            let components = vec![(s, 's'), (o, 'o')]
+                .into_iter()
+                .chain(g.map(|g| (g, 'g')));
+            for (component, position) in components {
+                if component.is_blank() && component != node {
+                    let hash = self.hash_related_blank_node(component, quad, &issuer, position);
+                    related_blanks.entry(hash).or_default().push(component);
+                }
+            }
+        }
+
+        let mut data = String::new();
+        for (related_hash, blanks) in related_blanks {
+            data.push_str(&related_hash);
+            let mut chosen_path = String::new();
+            let mut chosen_issuer = None;
+
+            'permutations: for permutation in permutations(&blanks) {
+                let mut issuer_copy = issuer.clone();
+                let mut path = String::new();
+                let mut recursion_list = Vec::new();
+                let worse = |path: &String, chosen_path: &String| {
+                    !chosen_path.is_empty() && path.len() >= chosen_path.len() && path > chosen_path
+                };
+
+                for related in permutation {
+                    match self.canonical.get(related) {
+                        Some(identifier) => path.push_str(&format!("_:{}", identifier)),
+                        None => {
+                            if issuer_copy.get(related).is_none() {
+                                recursion_list.push(related);
+                            }
+                            path.push_str(&format!("_:{}", issuer_copy.issue(related)));
+                        }
+                    }
+                    if worse(&path, &chosen_path) {
+                        continue 'permutations;
+                    }
+                }
+
+                for related in recursion_list {
+                    let (hash, result_issuer) =
+                        self.hash_n_degree_quads(related, issuer_copy.clone());
+                    path.push_str(&format!("_:{}<{}>", issuer_copy.issue(related), hash));
+                    issuer_copy = result_issuer;
+                    if worse(&path, &chosen_path) {
+                        continue 'permutations;
+                    }
+                }
+
+                if chosen_path.is_empty() || path < chosen_path {
+                    chosen_path = path;
+                    chosen_issuer = Some(issuer_copy);
+                }
+            }
+
+            data.push_str(&chosen_path);
+            if let Some(chosen_issuer) = chosen_issuer {
+                issuer = chosen_issuer;
+            }
+        }
+
+        (sha256(&data), issuer)
+    }
+
+    fn canonicalize(mut self) -> String {
+        let mut blanks_by_hash: BTreeMap<&str, Vec<&'a Node>> = BTreeMap::new();
+        for (blank, hash) in self.first_degree_hashes.iter() {
+            blanks_by_hash.entry(hash).or_default().push(blank);
+        }
+
+        // Blank nodes with a unique hash are labeled in the order of their hashes.
+        let mut shared_hashes: Vec<Vec<&Node>> = Vec::new();
+        for blanks in blanks_by_hash.into_values() {
+            if blanks.len() == 1 {
+                self.canonical.issue(blanks[0]);
+            } else {
+                shared_hashes.push(blanks);
+            }
+        }
+
+        // All others are labeled by hashing their surroundings.
+        for blanks in shared_hashes {
+            let mut results = Vec::new();
+            for blank in blanks {
+                if self.canonical.get(blank).is_some() {
+                    continue;
+                }
+                let mut issuer = IdentifierIssuer::new("b");
+                issuer.issue(blank);
+                results.push(self.hash_n_degree_quads(blank, issuer));
+            }
+            results.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, issuer) in results {
+                for blank in issuer.issued {
+                    self.canonical.issue(blank);
+                }
+            }
+        }
+
+        let canonical = &self.canonical;
+        let mut lines: Vec<String> = self
+            .quads
+            .iter()
+            .map(|quad| nquad(quad, |blank| canonical.get(blank).unwrap()))
+            .collect();
+        lines.sort();
+        lines.concat()
+    }
+}
+
+/// Serialize a quad as a line of N-Quads, labeling blank nodes with `label`.
+fn nquad<F: Fn(&Node) -> String>(quad: &Quad, label: F) -> String {
+    let (s, p, o, g) = *quad;
+    let mut line = String::new();
+    for node in vec![s, p, o].into_iter().chain(g) {
+        match node.kind() {
+            NodeKind::Iri => line.push_str(&format!("<{}> ", node.as_str())),
+            NodeKind::Blank => line.push_str(&format!("_:{} ", label(node))),
+            NodeKind::Literal => {
+                line.push_str(&format!("\"{}\"", escape(node.lexical_form())));
+                match (node.datatype(), node.language()) {
+                    (Some(datatype), _) => line.push_str(&format!("^^<{}> ", datatype)),
+                    (None, Some(language)) => line.push_str(&format!("@{} ", language)),
+                    (None, None) => line.push(' '),
+                }
+            }
+        }
+    }
+    line.push_str(".\n");
+    line
+}
+
+fn sha256(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Return all permutations of the nodes.
+fn permutations<'a>(nodes: &[&'a Node]) -> Vec<Vec<&'a Node>> {
+    if nodes.len() <= 1 {
+        return vec![nodes.to_vec()];
+    }
+    let mut result = Vec::new();
+    for (index, first) in nodes.iter().enumerate() {
+        let mut rest = nodes.to_vec();
+        rest.remove(index);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, *first);
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+impl Dataset {
+    /// Serialize the dataset as canonical N-Quads.
+    ///
+    /// Two isomorphic datasets produce exactly the same document, which makes it suitable for
+    /// hashing and signing. Blank nodes are labeled `_:c14n0`, `_:c14n1` and so on, based only on
+    /// the structure of the dataset, and the quads are sorted. This implements the
+    /// [RDF Dataset Canonicalization](https://www.w3.org/TR/rdf-canon/) algorithm (URDNA2015).
+    ///
+    /// Datasets with many blank nodes that can't be told apart by their direct surroundings may
+    /// take exponential time, since the algorithm tries all permutations of such blank nodes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Dataset, Node};
+    ///
+    /// let (knows, name) = (Node::from("http://example.org/knows"), Node::from("http://example.org/name"));
+    /// let graph = Node::from("http://example.org/graph");
+    ///
+    /// let (alice, bob) = (Node::blank(), Node::blank());
+    /// let mut a = Dataset::new();
+    /// a.insert_quad(None, alice, knows.clone(), bob.clone());
+    /// a.insert_quad(Some(graph.clone()), bob, name.clone(), Node::from("Bob"));
+    ///
+    /// let (x, y) = (Node::blank(), Node::blank());
+    /// let mut b = Dataset::new();
+    /// b.insert_quad(Some(graph), x.clone(), name, Node::from("Bob"));
+    /// b.insert_quad(None, y, knows, x);
+    ///
+    /// assert_eq!(a.canonicalize(), b.canonicalize());
+    /// ```
+    pub fn canonicalize(&self) -> String {
+        Canonicalizer::new(self).canonicalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dataset, Node};
+    use std::collections::HashMap;
+
+    /// Build a dataset from quads written like in N-Quads, with an optional fourth term as the
+    /// graph name. Terms starting with `_:` are blank node labels.
+    fn dataset<'a>(quads: &[&[&'a str]]) -> Dataset {
+        let mut blanks: HashMap<&str, Node> = HashMap::new();
+        let mut node = |term: &'a str| {
+            if term.starts_with("_:") {
+                blanks.entry(term).or_insert_with(Node::blank).clone()
+            } else {
+                Node::from(term)
+            }
+        };
+        let mut dataset = Dataset::new();
+        for quad in quads {
+            let (subject, predicate, object) = (node(quad[0]), node(quad[1]), node(quad[2]));
+            let graph = quad.get(3).map(|name| node(name));
+            dataset.insert_quad(graph, subject, predicate, object);
+        }
+        dataset
+    }
+
+    #[test]
+    fn unique_hashes() {
+        let dataset = dataset(&[
+            &["http://example.com/#p", "http://example.com/#q", "_:e0"],
+            &["http://example.com/#p", "http://example.com/#r", "_:e1"],
+            &["_:e0", "http://example.com/#s", "http://example.com/#u"],
+            &["_:e1", "http://example.com/#t", "http://example.com/#u"],
+        ]);
+        assert_eq!(
+            "<http://example.com/#p> <http://example.com/#q> _:c14n0 .\n\
+             <http://example.com/#p> <http://example.com/#r> _:c14n1 .\n\
+             _:c14n0 <http://example.com/#s> <http://example.com/#u> .\n\
+             _:c14n1 <http://example.com/#t> <http://example.com/#u> .\n",
+            dataset.canonicalize()
+        );
+    }
+
+    #[test]
+    fn shared_hashes() {
+        let dataset = dataset(&[
+            &["http://example.com/#p", "http://example.com/#q", "_:e0"],
+            &["http://example.com/#p", "http://example.com/#q", "_:e1"],
+            &["_:e0", "http://example.com/#p", "_:e2"],
+            &["_:e1", "http://example.com/#p", "_:e3"],
+            &["_:e2", "http://example.com/#r", "_:e3"],
+        ]);
+        assert_eq!(
+            "<http://example.com/#p> <http://example.com/#q> _:c14n2 .\n\
+             <http://example.com/#p> <http://example.com/#q> _:c14n3 .\n\
+             _:c14n0 <http://example.com/#r> _:c14n1 .\n\
+             _:c14n2 <http://example.com/#p> _:c14n1 .\n\
+             _:c14n3 <http://example.com/#p> _:c14n0 .\n",
+            dataset.canonicalize()
+        );
+    }
+
+    #[test]
+    fn isomorphic_datasets() {
+        let integer = "\"1\"^^<http://www.w3.org/2001/XMLSchema#integer>";
+        // A cycle of blank nodes that can only be told apart by the named graph of one quad.
+        let a = dataset(&[
+            &["_:a", "urn:p", "_:b"],
+            &["_:b", "urn:p", "_:c"],
+            &["_:c", "urn:p", "_:a", "urn:g"],
+            &["_:c", "urn:name", "\"c\"@en"],
+            &["urn:s", "urn:p", integer, "_:a"],
+        ]);
+        let b = dataset(&[
+            &["urn:s", "urn:p", integer, "_:z"],
+            &["_:y", "urn:name", "\"c\"@en"],
+            &["_:y", "urn:p", "_:z", "urn:g"],
+            &["_:x", "urn:p", "_:y"],
+            &["_:z", "urn:p", "_:x"],
+        ]);
+        let canonical = a.canonicalize();
+        assert_eq!(canonical, b.canonicalize());
+        assert_eq!(5, canonical.lines().count());
+        #[cfg(feature = "nquads")]
+        assert!(crate::iso::is_isomorphic(
+            a.default_graph(),
+            crate::nquads::parse_nquads(&canonical)
+                .unwrap()
+                .default_graph()
+        ));
+
+        let c = dataset(&[
+            &["_:a", "urn:p", "_:b"],
+            &["_:b", "urn:p", "_:c", "urn:g"],
+            &["_:c", "urn:p", "_:a"],
+            &["_:c", "urn:name", "\"c\"@en"],
+            &["urn:s", "urn:p", integer, "_:a"],
+        ]);
+        assert_ne!(canonical, c.canonicalize());
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{Graph, Node};
+use std::collections::HashMap;
+
+/// A graph that counts how often every triple was inserted.
+///
+/// Graphs are sets of triples, so inserting a triple twice has no effect on a
+/// [`HashGraph`](struct.HashGraph.html). A `CountingGraph` instead counts the insertions of every
+/// triple, and [`remove`](trait.Graph.html#tymethod.remove) only decrements the count. The triple
+/// is only removed from the graph once its count reaches zero. This is useful to merge several
+/// source graphs and later remove one of them again without losing the triples that other sources
+/// assert too.
+///
+/// All other methods of the [`Graph`](trait.Graph.html) trait ignore the counts: Every triple is
+/// only yielded once, and [`retain`](trait.Graph.html#method.retain) and
+/// [`clear`](trait.Graph.html#method.clear) remove triples regardless of their counts.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{graph, CountingGraph, Graph, Node};
+///
+/// let source_a = graph! {
+///     "urn:alice" -> "urn:knows" -> "urn:bob";
+///     "urn:alice" -> "urn:knows" -> "urn:carol";
+/// };
+/// let source_b = graph! {
+///     "urn:alice" -> "urn:knows" -> "urn:bob";
+/// };
+///
+/// let mut graph = CountingGraph::new();
+/// graph.merge(&source_a);
+/// graph.merge(&source_b);
+/// assert_eq!(2, graph.len());
+///
+/// let (alice, knows, bob) = (Node::from("urn:alice"), Node::from("urn:knows"), Node::from("urn:bob"));
+/// assert_eq!(2, graph.count(&alice, &knows, &bob));
+///
+/// // Removing the triples of the first source keeps the triple that the second source asserts.
+/// graph.remove_all(source_a.iter());
+/// assert_eq!(1, graph.len());
+/// assert_eq!(1, graph.count(&alice, &knows, &bob));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CountingGraph {
+    nodes: HashMap<Node, HashMap<Node, HashMap<Node, usize>>>,
+}
+
+impl CountingGraph {
+    /// Create a new, empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return how often the triple was inserted, or zero if the graph doesn't contain it.
+    pub fn count(&self, subject: &Node, predicate: &Node, object: &Node) -> usize {
+        self.nodes
+            .get(subject)
+            .and_then(|relationships| relationships.get(predicate))
+            .and_then(|objects| objects.get(object))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Return the sum of the counts of all triples.
+    pub fn total_count(&self) -> usize {
+        self.nodes
+            .values()
+            .flat_map(|relationships| relationships.values())
+            .flat_map(|objects| objects.values())
+            .sum()
+    }
+}
+
+impl Graph for CountingGraph {
+    fn len(&self) -> usize {
+        self.nodes
+            .values()
+            .flat_map(|relationships| relationships.values())
+            .map(|objects| objects.len())
+            .sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn contains(&self, subject: &Node, predicate: &Node, object: &Node) -> bool {
+        self.count(subject, predicate, object) > 0
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = (&'a Node, &'a Node, &'a Node)>> {
+        Box::new(self.nodes.iter().flat_map(|(subject, relationships)| {
+            relationships.iter().flat_map(move |(predicate, objects)| {
+                objects
+                    .keys()
+                    .map(move |object| (subject, predicate, object))
+            })
+        }))
+    }
+
+    fn insert(&mut self, subject: Node, predicate: Node, object: Node) {
+        *self
+            .nodes
+            .entry(subject)
+            .or_default()
+            .entry(predicate)
+            .or_default()
+            .entry(object)
+            .or_insert(0) += 1;
+    }
+
+    fn remove(&mut self, subject: &Node, predicate: &Node, object: &Node) {
+        let relationships = match self.nodes.get_mut(subject) {
+            Some(relationships) => relationships,
+            None => return,
+        };
+        let objects = match relationships.get_mut(predicate) {
+            Some(objects) => objects,
+            None => return,
+        };
+        if let Some(count) = objects.get_mut(object) {
+            *count -= 1;
+            if *count == 0 {
+                objects.remove(object);
+            }
+        }
+
+        // Drop empty maps, so that `is_empty` only has to check the subjects.
+        if objects.is_empty() {
+            relationships.remove(predicate);
+        }
+        if relationships.is_empty() {
+            self.nodes.remove(subject);
+        }
+    }
+
+    fn retain<F: FnMut(&Node, &Node, &Node) -> bool>(&mut self, mut f: F) {
+        self.nodes.retain(|subject, relationships| {
+            relationships.retain(|predicate, objects| {
+                objects.retain(|object, _| f(subject, predicate, object));
+                !objects.is_empty()
+            });
+            !relationships.is_empty()
+        });
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    fn relationships<'a>(
+        &'a self,
+        subject: &'a Node,
+    ) -> Box<dyn 'a + Iterator<Item = (&'a Node, &'a Node, &'a Node)>> {
+        match self.nodes.get(subject) {
+            Some(relationships) => {
+                Box::new(relationships.iter().flat_map(move |(predicate, objects)| {
+                    objects
+                        .keys()
+                        .map(move |object| (subject, predicate, object))
+                }))
+            }
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn objects<'a>(
+        &'a self,
+        subject: &'a Node,
+        predicate: &'a Node,
+    ) -> Box<dyn 'a + Iterator<Item = (&'a Node, &'a Node, &'a Node)>> {
+        match self
+            .nodes
+            .get(subject)
+            .and_then(|relationships| relationships.get(predicate))
+        {
+            Some(objects) => Box::new(
+                objects
+                    .keys()
+                    .map(move |object| (subject, predicate, object)),
+            ),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn validate() {
+        Validator::new(CountingGraph::new()).validate();
+    }
+
+    #[test]
+    fn counts() {
+        let validator = Validator::new(CountingGraph::new());
+        let mut graph = validator.graph;
+        let node_a = &validator.node_a;
+        let node_b = &validator.node_b;
+        let predicate_a = &validator.predicate_a;
+
+        graph.clone_insert(node_a, predicate_a, node_b);
+        graph.clone_insert(node_a, predicate_a, node_b);
+        assert_eq!(3, graph.len());
+        assert_eq!(3, graph.count(node_a, predicate_a, node_b));
+        assert_eq!(5, graph.total_count());
+        assert_eq!(0, graph.count(node_b, predicate_a, node_a));
+
+        graph.remove(node_a, predicate_a, node_b);
+        graph.remove(node_a, predicate_a, node_b);
+        assert!(graph.contains(node_a, predicate_a, node_b));
+        graph.remove(node_a, predicate_a, node_b);
+        assert!(!graph.contains(node_a, predicate_a, node_b));
+        assert_eq!(2, graph.len());
+        assert!(!graph.nodes.contains_key(node_a));
+
+        // Removing a triple that isn't contained does nothing.
+        graph.remove(node_a, predicate_a, node_b);
+        assert_eq!(2, graph.len());
+
+        // Retain removes triples regardless of their counts.
+        graph.clone_insert(node_b, predicate_a, node_a);
+        graph.clone_insert(node_b, predicate_a, node_a);
+        graph.retain(|_, p, _| p != predicate_a);
+        assert_eq!(2, graph.len());
+        assert_eq!(2, graph.total_count());
+
+        graph.clear();
+        assert!(graph.is_empty());
+    }
+}
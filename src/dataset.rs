@@ -0,0 +1,486 @@
+use crate::{util, Graph, HashGraph, Node};
+use std::collections::{HashMap, HashSet};
+
+/// A collection of graphs, as defined by [RDF 1.1](https://www.w3.org/TR/rdf11-concepts/#section-dataset).
+///
+/// A dataset consists of exactly one default graph, which has no name, and any number of named
+/// graphs, which are identified by a node.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Dataset {
+    default_graph: HashGraph,
+    named_graphs: HashMap<Node, HashGraph>,
+}
+
+impl Dataset {
+    /// Create a new dataset with an empty default graph and no named graphs.
+    pub fn new() -> Self {
+        Self {
+            default_graph: HashGraph::new(),
+            named_graphs: HashMap::new(),
+        }
+    }
+
+    /// Return a reference to the default graph.
+    pub fn default_graph(&self) -> &HashGraph {
+        &self.default_graph
+    }
+
+    /// Return a mutable reference to the default graph.
+    pub fn default_graph_mut(&mut self) -> &mut HashGraph {
+        &mut self.default_graph
+    }
+
+    /// Return a reference to the graph with the given name, if there is one.
+    pub fn graph(&self, name: &Node) -> Option<&HashGraph> {
+        self.named_graphs.get(name)
+    }
+
+    /// Return a mutable reference to the graph with the given name.
+    ///
+    /// If the dataset doesn't contain a graph with this name yet, an empty graph is created.
+    pub fn named_graph_mut(&mut self, name: Node) -> &mut HashGraph {
+        self.named_graphs.entry(name).or_default()
+    }
+
+    /// Return an iterator over the names and graphs of all named graphs.
+    pub fn named_graphs(&self) -> impl Iterator<Item = (&Node, &HashGraph)> {
+        self.named_graphs.iter()
+    }
+
+    /// Return an iterator over the names of all named graphs.
+    pub fn graph_names(&self) -> impl Iterator<Item = &Node> {
+        self.named_graphs.keys()
+    }
+
+    /// Insert a quad into the dataset.
+    ///
+    /// If `graph` is `None`, the triple is inserted into the default graph. Otherwise, it's inserted
+    /// into the named graph, which is created if necessary.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Dataset, Graph, Node};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    /// let name = Node::from("https://example.org/graph");
+    ///
+    /// let mut dataset = Dataset::new();
+    /// dataset.insert_quad(None, node_a.clone(), node_b.clone(), node_c.clone());
+    /// dataset.insert_quad(Some(name.clone()), node_c.clone(), node_b.clone(), node_a.clone());
+    ///
+    /// assert!(dataset.default_graph().contains(&node_a, &node_b, &node_c));
+    /// assert!(dataset.graph(&name).unwrap().contains(&node_c, &node_b, &node_a));
+    /// assert_eq!(2, dataset.len());
+    /// ```
+    pub fn insert_quad(
+        &mut self,
+        graph: Option<Node>,
+        subject: Node,
+        predicate: Node,
+        object: Node,
+    ) {
+        match graph {
+            Some(name) => self
+                .named_graph_mut(name)
+                .insert(subject, predicate, object),
+            None => self.default_graph.insert(subject, predicate, object),
+        }
+    }
+
+    /// Remove a quad from the dataset.
+    ///
+    /// If `graph` is `None`, the triple is removed from the default graph. Otherwise, it's removed
+    /// from the named graph. Named graphs remain in the dataset even if they become empty.
+    pub fn remove_quad(
+        &mut self,
+        graph: Option<&Node>,
+        subject: &Node,
+        predicate: &Node,
+        object: &Node,
+    ) {
+        match graph {
+            Some(name) => {
+                if let Some(graph) = self.named_graphs.get_mut(name) {
+                    graph.remove(subject, predicate, object);
+                }
+            }
+            None => self.default_graph.remove(subject, predicate, object),
+        }
+    }
+
+    /// Return `true` if the given graph contains the triple.
+    pub fn contains_quad(
+        &self,
+        graph: Option<&Node>,
+        subject: &Node,
+        predicate: &Node,
+        object: &Node,
+    ) -> bool {
+        match graph {
+            Some(name) => self
+                .graph(name)
+                .map(|graph| graph.contains(subject, predicate, object))
+                .unwrap_or(false),
+            None => self.default_graph.contains(subject, predicate, object),
+        }
+    }
+
+    /// Return the number of quads in the dataset.
+    pub fn len(&self) -> usize {
+        self.default_graph.len()
+            + self
+                .named_graphs
+                .values()
+                .map(|graph| graph.len())
+                .sum::<usize>()
+    }
+
+    /// Return `true` if the dataset contains no quads.
+    pub fn is_empty(&self) -> bool {
+        self.default_graph.is_empty() && self.named_graphs.values().all(|graph| graph.is_empty())
+    }
+
+    /// Return an iterator over all quads in the dataset.
+    ///
+    /// The first element of a quad is the name of the graph that contains the triple, or `None` for the default graph.
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&Node>, &Node, &Node, &Node)> {
+        let default_quads = self.default_graph.iter().map(|(s, p, o)| (None, s, p, o));
+        let named_quads = self
+            .named_graphs
+            .iter()
+            .flat_map(|(name, graph)| graph.iter().map(move |(s, p, o)| (Some(name), s, p, o)));
+        default_quads.chain(named_quads)
+    }
+
+    /// Return an iterator over the triples of the default graph and all named graphs.
+    ///
+    /// The graphs are merged, which means that the name of the graph a triple is contained in is lost
+    /// and that every triple is only produced once, even if it is contained in several graphs.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Dataset, Graph, Node};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    ///
+    /// let mut dataset = Dataset::new();
+    /// dataset.default_graph_mut().clone_insert(&node_a, &node_b, &node_c);
+    /// dataset.named_graph_mut(node_a.clone()).clone_insert(&node_a, &node_b, &node_c);
+    /// dataset.named_graph_mut(node_b.clone()).clone_insert(&node_c, &node_b, &node_a);
+    ///
+    /// assert_eq!(2, dataset.iter_all_triples().count());
+    /// ```
+    pub fn iter_all_triples(&self) -> impl Iterator<Item = (&Node, &Node, &Node)> {
+        util::dedup_triples(
+            self.default_graph
+                .iter()
+                .chain(self.named_graphs.values().flat_map(|graph| graph.iter())),
+        )
+    }
+
+    /// Return all blank nodes that appear in the triples of more than one graph.
+    ///
+    /// The scope of a blank node is the graph it's contained in, so sharing a blank node between
+    /// graphs is usually a mistake. Since blank nodes are distinguished by identity, a blank node
+    /// is only reported if the very same node is used in several graphs. Using a blank node as the
+    /// name of a graph doesn't count as an appearance.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Dataset, Node};
+    ///
+    /// let knows = Node::from("urn:knows");
+    /// let alice = Node::from("urn:alice");
+    /// let someone = Node::blank();
+    ///
+    /// let mut dataset = Dataset::new();
+    /// dataset.insert_quad(None, alice.clone(), knows.clone(), someone.clone());
+    /// assert!(dataset.blank_node_scope_violations().is_empty());
+    ///
+    /// dataset.insert_quad(Some(Node::from("urn:graph")), someone.clone(), knows, alice);
+    /// assert_eq!(vec![someone], dataset.blank_node_scope_violations());
+    /// ```
+    pub fn blank_node_scope_violations(&self) -> Vec<Node> {
+        let mut graph_counts: HashMap<&Node, usize> = HashMap::new();
+        for graph in std::iter::once(&self.default_graph).chain(self.named_graphs.values()) {
+            let blanks: HashSet<&Node> = graph
+                .iter()
+                .flat_map(|(s, p, o)| vec![s, p, o])
+                .filter(|node| node.is_blank())
+                .collect();
+            for blank in blanks {
+                *graph_counts.entry(blank).or_insert(0) += 1;
+            }
+        }
+        graph_counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(blank, _)| blank.clone())
+            .collect()
+    }
+}
+
+/// Quads are given as `(graph, subject, predicate, object)`, and quads without a graph name are
+/// inserted into the default graph.
+impl Extend<(Option<Node>, Node, Node, Node)> for Dataset {
+    fn extend<T: IntoIterator<Item = (Option<Node>, Node, Node, Node)>>(&mut self, iter: T) {
+        for (graph, subject, predicate, object) in iter {
+            self.insert_quad(graph, subject, predicate, object);
+        }
+    }
+}
+
+/// Collect quads into a dataset, like [`insert_quad`](struct.Dataset.html#method.insert_quad).
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{Dataset, Node};
+///
+/// let (alice, knows, bob) = (Node::from("urn:alice"), Node::from("urn:knows"), Node::from("urn:bob"));
+/// let name = Node::from("urn:graph");
+///
+/// let dataset: Dataset = vec![
+///     (None, alice.clone(), knows.clone(), bob.clone()),
+///     (Some(name.clone()), bob.clone(), knows.clone(), alice.clone()),
+/// ]
+/// .into_iter()
+/// .collect();
+/// assert!(dataset.contains_quad(None, &alice, &knows, &bob));
+/// assert!(dataset.contains_quad(Some(&name), &bob, &knows, &alice));
+///
+/// let copy: Dataset = dataset.clone().into_iter().collect();
+/// assert_eq!(dataset, copy);
+/// ```
+impl std::iter::FromIterator<(Option<Node>, Node, Node, Node)> for Dataset {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (Option<Node>, Node, Node, Node)>,
+    {
+        let mut dataset = Dataset::new();
+        dataset.extend(iter);
+        dataset
+    }
+}
+
+/// Iterate over owned quads, with `None` as the graph name of the default graph.
+///
+/// Empty named graphs don't produce any quads, so they are lost when the quads are collected into
+/// another dataset.
+impl std::iter::IntoIterator for Dataset {
+    type Item = (Option<Node>, Node, Node, Node);
+    type IntoIter = Box<dyn Iterator<Item = (Option<Node>, Node, Node, Node)>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let default_quads = self
+            .default_graph
+            .into_iter()
+            .map(|(s, p, o)| (None, s, p, o));
+        let named_quads = self.named_graphs.into_iter().flat_map(|(name, graph)| {
+            graph
+                .into_iter()
+                .map(move |(s, p, o)| (Some(name.clone()), s, p, o))
+        });
+        Box::new(default_quads.chain(named_quads))
+    }
+}
+
+/// Iterate over borrowed quads, just like [`Dataset::iter`](struct.Dataset.html#method.iter).
+impl<'a> std::iter::IntoIterator for &'a Dataset {
+    type Item = (Option<&'a Node>, &'a Node, &'a Node, &'a Node);
+    type IntoIter = Box<dyn 'a + Iterator<Item = (Option<&'a Node>, &'a Node, &'a Node, &'a Node)>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn iter_all_triples() {
+        let validator = Validator::new(HashGraph::new());
+        let name = Node::from("urn:arrdf:tests:graph:a");
+
+        let mut dataset = Dataset::new();
+        *dataset.default_graph_mut() = validator.graph.clone();
+        dataset.named_graph_mut(name.clone()).clone_insert(
+            &validator.node_a,
+            &validator.predicate_a,
+            &validator.node_a,
+        );
+        dataset.named_graph_mut(name.clone()).clone_insert(
+            &validator.node_a,
+            &validator.predicate_a,
+            &validator.node_b,
+        );
+
+        assert!(!dataset.default_graph().contains(
+            &validator.node_a,
+            &validator.predicate_a,
+            &validator.node_a
+        ));
+        assert_eq!(2, dataset.graph(&name).unwrap().len());
+
+        let all_triples: Vec<(&Node, &Node, &Node)> = dataset.iter_all_triples().collect();
+        assert_eq!(4, all_triples.len());
+        assert!(all_triples.contains(&(
+            &validator.node_a,
+            &validator.predicate_a,
+            &validator.node_a
+        )));
+        assert!(all_triples.contains(&(
+            &validator.node_a,
+            &validator.predicate_a,
+            &validator.node_b
+        )));
+        assert!(all_triples.contains(&(
+            &validator.node_b,
+            &validator.predicate_b,
+            &validator.node_c
+        )));
+        assert!(all_triples.contains(&(
+            &validator.node_c,
+            &validator.predicate_c,
+            &validator.node_a
+        )));
+    }
+
+    #[test]
+    fn quads() {
+        let validator = Validator::new(HashGraph::new());
+        let node_a = &validator.node_a;
+        let node_b = &validator.node_b;
+        let node_c = &validator.node_c;
+        let predicate_a = &validator.predicate_a;
+        let name_a = Node::from("urn:arrdf:tests:graph:a");
+        let name_b = Node::blank();
+
+        let mut dataset = Dataset::new();
+        assert!(dataset.is_empty());
+        dataset.insert_quad(None, node_a.clone(), predicate_a.clone(), node_b.clone());
+        dataset.insert_quad(
+            Some(name_a.clone()),
+            node_a.clone(),
+            predicate_a.clone(),
+            node_b.clone(),
+        );
+        dataset.insert_quad(
+            Some(name_b.clone()),
+            node_c.clone(),
+            predicate_a.clone(),
+            node_a.clone(),
+        );
+        assert_eq!(3, dataset.len());
+
+        let names: Vec<&Node> = dataset.graph_names().collect();
+        assert_eq!(2, names.len());
+        assert!(names.contains(&&name_a));
+        assert!(names.contains(&&name_b));
+
+        let quads: Vec<(Option<&Node>, &Node, &Node, &Node)> = dataset.iter().collect();
+        assert_eq!(3, quads.len());
+        assert!(quads.contains(&(None, node_a, predicate_a, node_b)));
+        assert!(quads.contains(&(Some(&name_a), node_a, predicate_a, node_b)));
+        assert!(quads.contains(&(Some(&name_b), node_c, predicate_a, node_a)));
+
+        dataset.remove_quad(Some(&name_a), node_a, predicate_a, node_b);
+        assert!(!dataset.contains_quad(Some(&name_a), node_a, predicate_a, node_b));
+        assert!(dataset.contains_quad(None, node_a, predicate_a, node_b));
+        assert!(dataset.graph(&name_a).unwrap().is_empty());
+
+        dataset.remove_quad(None, node_a, predicate_a, node_b);
+        dataset.remove_quad(Some(&name_b), node_c, predicate_a, node_a);
+        assert!(dataset.is_empty());
+        assert!(!dataset.contains_quad(Some(node_a), node_c, predicate_a, node_a));
+    }
+
+    #[test]
+    fn quad_iterators() {
+        let validator = Validator::new(HashGraph::new());
+        let name = Node::from("urn:arrdf:tests:graph:a");
+        let blank_name = Node::blank();
+
+        let mut dataset = Dataset::new();
+        *dataset.default_graph_mut() = validator.graph.clone();
+        dataset.insert_quad(
+            Some(name.clone()),
+            validator.node_a.clone(),
+            validator.predicate_a.clone(),
+            validator.node_c.clone(),
+        );
+        dataset.insert_quad(
+            Some(blank_name.clone()),
+            validator.node_c.clone(),
+            validator.predicate_b.clone(),
+            blank_name.clone(),
+        );
+
+        let mut count = 0;
+        for (graph, s, p, o) in &dataset {
+            assert!(dataset.contains_quad(graph, s, p, o));
+            count += 1;
+        }
+        assert_eq!(5, count);
+
+        let d2: Dataset = dataset.clone().into_iter().collect();
+        assert_eq!(dataset, d2);
+        assert!(d2.contains_quad(
+            Some(&blank_name),
+            &validator.node_c,
+            &validator.predicate_b,
+            &blank_name
+        ));
+        assert_eq!(3, d2.default_graph().len());
+    }
+
+    #[test]
+    fn blank_node_scope_violations() {
+        let validator = Validator::new(HashGraph::new());
+        let name = Node::from("urn:arrdf:tests:graph:a");
+        let blank_name = Node::blank();
+        let other_blank = Node::blank();
+
+        let mut dataset = Dataset::new();
+        *dataset.default_graph_mut() = validator.graph.clone();
+        dataset.insert_quad(
+            Some(name.clone()),
+            other_blank.clone(),
+            validator.predicate_a.clone(),
+            validator.node_a.clone(),
+        );
+        dataset.insert_quad(
+            Some(blank_name.clone()),
+            validator.node_a.clone(),
+            validator.predicate_a.clone(),
+            other_blank.clone(),
+        );
+        assert_eq!(
+            vec![other_blank.clone()],
+            dataset.blank_node_scope_violations()
+        );
+
+        // The blank node of the validator is in the default graph and now also in a named graph.
+        dataset.insert_quad(
+            Some(name),
+            validator.node_c.clone(),
+            validator.predicate_b.clone(),
+            validator.node_a.clone(),
+        );
+        let violations: std::collections::HashSet<Node> =
+            dataset.blank_node_scope_violations().into_iter().collect();
+        assert_eq!(2, violations.len());
+        assert!(violations.contains(&validator.node_c));
+        assert!(violations.contains(&other_blank));
+        assert!(!violations.contains(&blank_name));
+    }
+}
@@ -0,0 +1,167 @@
+//! Differences between two versions of a graph.
+//!
+//! A [`GraphDiff`](struct.GraphDiff.html) records which triples have to be added to and removed from an
+//! old graph to turn it into a new one. Blank nodes are compared by identity, just like everywhere
+//! else, so two independently parsed documents usually differ in all triples with blank nodes.
+use crate::{set, Graph, HashGraph, Node};
+use std::collections::HashSet;
+use std::fmt;
+
+/// The triples that were added and removed between two graphs.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct GraphDiff {
+    /// Triples that are contained in the new graph, but not in the old one.
+    pub added: HashGraph,
+    /// Triples that are contained in the old graph, but not in the new one.
+    pub removed: HashGraph,
+}
+
+impl GraphDiff {
+    /// Compute the difference from `old` to `new`.
+    pub fn between<G: Graph, H: Graph>(old: &G, new: &H) -> Self {
+        Self {
+            added: set::difference(new, old).collect(),
+            removed: set::difference(old, new).collect(),
+        }
+    }
+
+    /// Return `true` if both graphs contain the same triples.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Return the subjects of all added and removed triples.
+    pub fn affected_subjects(&self) -> HashSet<&Node> {
+        self.added
+            .iter()
+            .chain(self.removed.iter())
+            .map(|(subject, _, _)| subject)
+            .collect()
+    }
+}
+
+impl fmt::Display for GraphDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let added = self.added.len();
+        let removed = self.removed.len();
+        let subjects = self.affected_subjects().len();
+        write!(
+            f,
+            "{} {} added, {} removed, {} {} affected",
+            added,
+            if added == 1 { "triple" } else { "triples" },
+            removed,
+            subjects,
+            if subjects == 1 { "subject" } else { "subjects" },
+        )
+    }
+}
+
+/// Return a short, human-readable report of the changes from `a` to `b`.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::graph;
+///
+/// let a = graph! {
+///     "urn:alice" -> "urn:knows" -> "urn:bob";
+///     "urn:bob" -> "urn:knows" -> "urn:carol";
+/// };
+/// let b = graph! {
+///     "urn:alice" -> "urn:knows" -> "urn:bob";
+///     "urn:alice" -> "urn:knows" -> "urn:carol";
+/// };
+///
+/// assert_eq!(
+///     "1 triple added, 1 removed, 2 subjects affected",
+///     arrdf::diff::summary(&a, &b)
+/// );
+/// ```
+pub fn summary(a: &HashGraph, b: &HashGraph) -> String {
+    GraphDiff::between(a, b).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::*;
+    use crate::*;
+
+    #[test]
+    fn summary() {
+        let validator = Validator::new(HashGraph::new());
+        let old = validator.graph.clone();
+        let mut new = validator.graph.clone();
+        new.remove(&validator.node_a, &validator.predicate_a, &validator.node_b);
+        for i in 0..12 {
+            new.insert(
+                validator.node_b.clone(),
+                validator.predicate_b.clone(),
+                Node::from(format!("{}", i).as_str()),
+            );
+        }
+
+        let diff = GraphDiff::between(&old, &new);
+        assert_eq!(12, diff.added.len());
+        assert_eq!(1, diff.removed.len());
+        assert!(diff.removed.contains(
+            &validator.node_a,
+            &validator.predicate_a,
+            &validator.node_b
+        ));
+        assert_eq!(2, diff.affected_subjects().len());
+        assert_eq!(
+            "12 triples added, 1 removed, 2 subjects affected",
+            diff::summary(&old, &new)
+        );
+
+        assert!(GraphDiff::between(&old, &old).is_empty());
+        assert_eq!(
+            "0 triples added, 0 removed, 0 subjects affected",
+            diff::summary(&old, &old)
+        );
+    }
+
+    #[test]
+    fn apply_diff() {
+        // A small xorshift generator, so that the graphs are random but reproducible.
+        let mut state: u32 = 0x2545_f491;
+        let mut next = move |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as usize % bound
+        };
+        let nodes = [
+            Node::from("urn:arrdf:tests:node:a"),
+            Node::from("urn:arrdf:tests:node:b"),
+            Node::from("urn:arrdf:tests:node:c"),
+            Node::from("Literal"),
+            Node::blank(),
+            Node::blank(),
+        ];
+        let mut random_graph = || -> HashGraph {
+            let len = next(20);
+            (0..len)
+                .map(|_| {
+                    (
+                        nodes[next(nodes.len())].clone(),
+                        nodes[next(3)].clone(),
+                        nodes[next(nodes.len())].clone(),
+                    )
+                })
+                .collect()
+        };
+
+        for _ in 0..200 {
+            let mut graph = random_graph();
+            let other = random_graph();
+            let patch = graph.diff(&other);
+            assert!(crate::set::is_subset(&patch.added, &other));
+            assert!(crate::set::is_subset(&patch.removed, &graph));
+            graph.apply(&patch);
+            assert_eq!(other, graph);
+            assert!(graph.diff(&other).is_empty());
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+
+/// The error returned by the parsers if a document isn't valid.
+///
+/// ## Examples
+///
+/// ```
+/// # #[cfg(feature = "turtle")]
+/// # {
+/// let error = arrdf::turtle::parse("<urn:alice> <urn:knows> <urn:bob>").unwrap_err();
+/// assert_eq!(1, error.line);
+/// assert_eq!(34, error.column);
+/// assert_eq!("Line 1, column 34: expected '.' to end the statement", error.to_string());
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// The number of the offending line, starting at 1.
+    pub line: usize,
+    /// The number of the offending character within the line, starting at 1.
+    pub column: usize,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl ParseError {
+    /// Create an error for the position in `input` where `rest` starts.
+    #[cfg(any(feature = "nom", feature = "jsonld"))]
+    pub(crate) fn at(input: &str, rest: &str, message: String) -> Self {
+        let consumed = &input[..input.len() - rest.len()];
+        let line_start = consumed.rfind('\n').map(|index| index + 1).unwrap_or(0);
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -1,4 +1,4 @@
-use crate::Node;
+use crate::{BlankNodeAllocator, Node};
 
 /// A generalized RDF triple store.
 ///
@@ -124,6 +124,55 @@ pub trait Graph {
             .any(|(s, p, o)| s == subject && p == predicate && o == object)
     }
 
+    /// Return `true` if the graph contains a triple with the given subject and predicate.
+    ///
+    /// This answers whether the subject has any value for the predicate, which is clearer than
+    /// `graph.objects(subject, predicate).next().is_some()`. The default implementation does
+    /// exactly that, but an implementation may use an optimized query that doesn't iterate
+    /// over the objects.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    /// let graph: HashGraph = vec![(&node_a, &node_b, &node_c)].into_iter().collect();
+    ///
+    /// assert!(graph.contains_subject_predicate(&node_a, &node_b));
+    /// assert!(!graph.contains_subject_predicate(&node_a, &node_c));
+    /// ```
+    fn contains_subject_predicate(&self, subject: &Node, predicate: &Node) -> bool {
+        self.objects(subject, predicate).next().is_some()
+    }
+
+    /// Return `true` if no subject, predicate or object of the graph is a blank node.
+    ///
+    /// Ground graphs are equal if and only if they're [isomorphic](iso/fn.is_isomorphic.html), so
+    /// they can be compared with a cheap triple-by-triple comparison instead of an isomorphism
+    /// check. The default implementation scans the triples until it finds a blank node, but an
+    /// implementation may use a more efficient query.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let mut graph: HashGraph = vec![(&node_a, &node_b, &node_a)].into_iter().collect();
+    /// assert!(graph.is_ground());
+    ///
+    /// graph.insert(node_a, node_b, Node::blank());
+    /// assert!(!graph.is_ground());
+    /// ```
+    fn is_ground(&self) -> bool {
+        self.iter()
+            .all(|(s, p, o)| !s.is_blank() && !p.is_blank() && !o.is_blank())
+    }
+
     /// Return `true if the graph is valid, non-generalized RDF graph.
     ///
     /// In this crate, all graphs are "generalized graphs" per default. This means that both subject,
@@ -135,6 +184,36 @@ pub trait Graph {
         self.iter().all(|(s, p, _)| !s.is_literal() && p.is_iri())
     }
 
+    /// Insert a triple and return `true` if it wasn't contained in the graph before.
+    ///
+    /// The default implementation calls [`contains`](#method.contains) and only inserts the
+    /// triple if it's missing, which takes two lookups. Implementations may insert the triple with
+    /// a single lookup instead. Implementations that ignore some triples in
+    /// [`insert`](#tymethod.insert) should override this method to return `false` for them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    /// let mut graph = HashGraph::new();
+    ///
+    /// assert!(graph.insert_if_absent(node_a.clone(), node_b.clone(), node_c.clone()));
+    /// assert!(!graph.insert_if_absent(node_a, node_b, node_c));
+    /// assert_eq!(1, graph.len());
+    /// ```
+    fn insert_if_absent(&mut self, subject: Node, predicate: Node, object: Node) -> bool {
+        if self.contains(&subject, &predicate, &object) {
+            false
+        } else {
+            self.insert(subject, predicate, object);
+            true
+        }
+    }
+
     /// Clone the referenced nodes and insert them as a triple.
     ///
     /// ## Examples
@@ -157,6 +236,51 @@ pub trait Graph {
         self.insert(subject.clone(), predicate.clone(), object.clone());
     }
 
+    /// Insert a triple that is given as a tuple.
+    ///
+    /// This is useful for triples that come from iterators or parsers, which are usually tuples.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let triple = (Node::from("Node A"), Node::from("Node B"), Node::from("Node C"));
+    /// let mut graph = HashGraph::new();
+    /// graph.insert_triple(triple.clone());
+    ///
+    /// // With the default implementation, this is equivalent to:
+    /// let (subject, predicate, object) = triple;
+    /// graph.insert(subject, predicate, object);
+    /// ```
+    #[cfg(not(tarpaulin_include))]
+    fn insert_triple(&mut self, (subject, predicate, object): (Node, Node, Node)) {
+        self.insert(subject, predicate, object);
+    }
+
+    /// Remove a triple that is given as a tuple of references.
+    ///
+    /// This is the counterpart of [`insert_triple`](#method.insert_triple) and accepts the
+    /// triples yielded by [`iter`](#tymethod.iter).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    /// let mut graph: HashGraph = vec![(&node_a, &node_b, &node_c)].into_iter().collect();
+    ///
+    /// graph.remove_triple((&node_a, &node_b, &node_c));
+    /// assert!(graph.is_empty());
+    /// ```
+    #[cfg(not(tarpaulin_include))]
+    fn remove_triple(&mut self, (subject, predicate, object): (&Node, &Node, &Node)) {
+        self.remove(subject, predicate, object);
+    }
+
     /// Extend the graph with the contents of the iterator.
     ///
     /// This method provides the same functionality as the [`Extend`](https://doc.rust-lang.org/stable/std/iter/trait.Extend.html) trait,
@@ -236,6 +360,61 @@ pub trait Graph {
         )
     }
 
+    /// Insert clones of all triples of another graph into this graph.
+    ///
+    /// The other graph may be of any type, which makes this method useful to fold e.g. the view of
+    /// a transaction into a `HashGraph`. It's equivalent to `self.clone_extend(other.iter())`.
+    ///
+    /// ## Examples
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    ///
+    /// let mut graph_a: HashGraph = vec![(&node_a, &node_b, &node_c)].into_iter().collect();
+    /// let graph_b: HashGraph = vec![(&node_c, &node_b, &node_a)].into_iter().collect();
+    ///
+    /// graph_a.merge(&graph_b);
+    /// assert_eq!(2, graph_a.len());
+    /// assert!(graph_a.contains(&node_c, &node_b, &node_a));
+    /// ```
+    fn merge<G: Graph>(&mut self, other: &G) {
+        for (s, p, o) in other.iter() {
+            self.clone_insert(s, p, o);
+        }
+    }
+
+    /// Move all triples of another graph into this graph.
+    ///
+    /// In contrast to [`merge`](#method.merge), the other graph is consumed, so its nodes are moved
+    /// instead of cloned. This is only available for graphs that can be turned into an iterator over
+    /// owned triples, like a `HashGraph`. Borrowed views of a graph, like transactions, have to be
+    /// merged instead.
+    ///
+    /// ## Examples
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    ///
+    /// let mut graph_a: HashGraph = vec![(&node_a, &node_b, &node_c)].into_iter().collect();
+    /// let graph_b: HashGraph = vec![(&node_c, &node_b, &node_a)].into_iter().collect();
+    ///
+    /// graph_a.insert_all_from(graph_b);
+    /// assert_eq!(2, graph_a.len());
+    /// assert!(graph_a.contains(&node_c, &node_b, &node_a));
+    /// ```
+    fn insert_all_from<G>(&mut self, other: G)
+    where
+        G: Graph + IntoIterator<Item = (Node, Node, Node)>,
+    {
+        self.extend(other)
+    }
+
     /// Remove all triples produced by the iterator.
     ///
     /// ## Examples
@@ -294,6 +473,77 @@ pub trait Graph {
         self.retain(|_, _, _| false);
     }
 
+    /// Return all triples, ordered by subject, then by predicate, then by object.
+    ///
+    /// The nodes are compared with [`Node`](struct.Node.html)'s `Ord` implementation, which orders
+    /// blank nodes after all other nodes and among each other in an order that is consistent
+    /// within a run of the program, but may differ between runs. Use
+    /// [`triples_sorted_by_labels`](#method.triples_sorted_by_labels) to order blank nodes by
+    /// their labels instead. All triples are collected in a `Vec` to sort them, so this allocates
+    /// memory proportional to the size of the graph.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph};
+    ///
+    /// let graph = graph! {
+    ///     "urn:bob" -> "urn:knows" -> "urn:alice";
+    ///     "urn:alice" -> "urn:name" -> "Alice";
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    /// };
+    /// let pairs: Vec<(&str, &str)> = graph
+    ///     .triples_sorted()
+    ///     .into_iter()
+    ///     .map(|(s, p, _)| (s.as_str(), p.as_str()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     vec![("urn:alice", "urn:knows"), ("urn:alice", "urn:name"), ("urn:bob", "urn:knows")],
+    ///     pairs
+    /// );
+    /// ```
+    fn triples_sorted(&self) -> Vec<(&Node, &Node, &Node)> {
+        let mut triples: Vec<(&Node, &Node, &Node)> = self.iter().collect();
+        triples.sort_unstable();
+        triples
+    }
+
+    /// Return all triples ordered like [`triples_sorted`](#method.triples_sorted), but with blank nodes ordered by their labels.
+    ///
+    /// Blank nodes that have a label in `labels` are ordered by it, which makes the order
+    /// reproducible between runs if the labels are. See
+    /// [`BlankNodeAllocator::compare`](struct.BlankNodeAllocator.html#method.compare) for the
+    /// order of all nodes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{BlankNodeAllocator, Graph, HashGraph, Node};
+    ///
+    /// let mut labels = BlankNodeAllocator::new();
+    /// let first = labels.allocate();
+    /// let second = labels.allocate();
+    ///
+    /// let mut graph = HashGraph::new();
+    /// let name = Node::from("urn:name");
+    /// graph.clone_insert(&second, &name, &Node::from("Second"));
+    /// graph.clone_insert(&first, &name, &Node::from("First"));
+    ///
+    /// let sorted = graph.triples_sorted_by_labels(&labels);
+    /// assert_eq!(&first, sorted[0].0);
+    /// assert_eq!(&second, sorted[1].0);
+    /// ```
+    fn triples_sorted_by_labels(&self, labels: &BlankNodeAllocator) -> Vec<(&Node, &Node, &Node)> {
+        let mut triples: Vec<(&Node, &Node, &Node)> = self.iter().collect();
+        triples.sort_unstable_by(|(s_a, p_a, o_a), (s_b, p_b, o_b)| {
+            labels
+                .compare(s_a, s_b)
+                .then_with(|| labels.compare(p_a, p_b))
+                .then_with(|| labels.compare(o_a, o_b))
+        });
+        triples
+    }
+
     /// Get all relationships of a given subject.
     ///
     /// This is equivalent to iterating over all triples and filtering all triples without the
@@ -0,0 +1,640 @@
+use crate::vocab::{rdf, rdfs, void, xsd};
+use crate::{Graph, HashGraph, Node, NodeKind};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::BuildHasher;
+
+/// A reason why a triple isn't valid RDF, as reported by [`HashGraph::validate_report`](struct.HashGraph.html#method.validate_report).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ValidationIssue {
+    /// The subject is a literal, but subjects must be IRIs or blank nodes.
+    LiteralSubject,
+    /// The predicate is a literal or a blank node, but predicates must be IRIs.
+    NonIriPredicate,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationIssue::LiteralSubject => f.write_str("the subject is a literal"),
+            ValidationIssue::NonIriPredicate => f.write_str("the predicate is not an IRI"),
+        }
+    }
+}
+
+/// Metrics that characterize the shape of a graph, as computed by [`HashGraph::metrics`](struct.HashGraph.html#method.metrics).
+///
+/// The nodes of a graph are all nodes that appear as subjects or objects. Predicates are only
+/// counted if they appear in one of these positions too.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GraphMetrics {
+    /// The number of triples in the graph.
+    pub triples: usize,
+    /// The number of distinct nodes in the graph.
+    pub nodes: usize,
+    /// The average number of triples per node with the node as the subject.
+    pub average_out_degree: f64,
+    /// The maximal number of triples with the same subject.
+    pub max_out_degree: usize,
+    /// The fraction of nodes that are blank nodes.
+    pub blank_fraction: f64,
+}
+
+impl HashGraph {
+    /// Return the predicates that are used by [`partition_schema_data`](#method.partition_schema_data).
+    ///
+    /// These are `rdfs:subClassOf`, `rdfs:subPropertyOf`, `rdfs:domain` and `rdfs:range`.
+    pub fn default_schema_predicates() -> HashSet<Node> {
+        vec![
+            rdfs::sub_class_of(),
+            rdfs::sub_property_of(),
+            rdfs::domain(),
+            rdfs::range(),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Collapse the graph to the relationships between the types of its nodes.
+    ///
+    /// Every triple `s p o` is replaced by triples `S p O` for all types `S` of `s` and `O` of `o`,
+    /// where the types of a node are the objects of its `rdf:type` triples. Literals are replaced by
+    /// their datatype, language-tagged literals by `rdf:langString`, and plain literals by
+    /// `rdfs:Literal`. Triples with an untyped subject or object are skipped, just like the
+    /// `rdf:type` triples themselves.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node, vocab::rdf};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> (rdf::type_()) -> "urn:Person";
+    ///     "urn:bob" -> (rdf::type_()) -> "urn:Person";
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    /// };
+    ///
+    /// let type_graph = graph.type_graph();
+    /// assert_eq!(1, type_graph.len());
+    /// let person = Node::from("urn:Person");
+    /// assert!(type_graph.contains(&person, &Node::from("urn:knows"), &person));
+    /// ```
+    pub fn type_graph(&self) -> HashGraph<S> {
+        let type_ = rdf::type_();
+        let literal = rdfs::literal();
+        let lang_string = rdf::lang_string();
+        let types_of = |node: &Node| -> Vec<Node> {
+            if node.kind() == NodeKind::Literal {
+                vec![match (node.datatype(), node.language()) {
+                    (Some(datatype), _) => Node::from(datatype),
+                    (None, Some(_)) => lang_string.clone(),
+                    (None, None) => literal.clone(),
+                }]
+            } else {
+                self.types_of(node).cloned().collect()
+            }
+        };
+
+        let mut type_graph = HashGraph::default();
+        for (subject, predicate, object) in self.iter() {
+            if predicate == &type_ {
+                continue;
+            }
+            let object_types = types_of(object);
+            for subject_type in self.types_of(subject) {
+                for object_type in object_types.iter() {
+                    type_graph.clone_insert(subject_type, predicate, object_type);
+                }
+            }
+        }
+        type_graph
+    }
+
+    /// Describe the graph with the [VoID vocabulary](https://www.w3.org/TR/void/).
+    ///
+    /// The returned metadata graph declares `dataset` as a `void:Dataset` and contains its number of
+    /// triples (`void:triples`), distinct subjects (`void:distinctSubjects`), distinct predicates
+    /// (`void:properties`) and distinct classes (`void:classes`) as `xsd:integer` literals.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph, vocab::{void, xsd}};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    /// let graph: HashGraph = vec![(&node_a, &node_b, &node_c)].into_iter().collect();
+    ///
+    /// let dataset = Node::from("https://example.org/dataset");
+    /// let description = graph.void_description(&dataset);
+    /// assert!(description.contains(
+    ///     &dataset,
+    ///     &void::triples(),
+    ///     &Node::typed_literal("1", &xsd::integer())
+    /// ));
+    /// ```
+    pub fn void_description(&self, dataset: &Node) -> HashGraph<S> {
+        let rdf_type = rdf::type_();
+        let mut subjects = 0;
+        let mut properties: HashSet<&Node> = HashSet::new();
+        let mut classes: HashSet<&Node> = HashSet::new();
+        for relationships in self.nodes.values() {
+            let mut relationships = relationships
+                .iter()
+                .filter(|(_, objects)| !objects.is_empty())
+                .peekable();
+            if relationships.peek().is_some() {
+                subjects += 1;
+            }
+            for (predicate, objects) in relationships {
+                properties.insert(predicate);
+                if *predicate == rdf_type {
+                    classes.extend(objects.iter());
+                }
+            }
+        }
+
+        let count = |n: usize| Node::typed_literal(&n.to_string(), &xsd::integer());
+        let mut description = HashGraph::default();
+        description.insert(dataset.clone(), rdf_type.clone(), void::dataset());
+        description.insert(dataset.clone(), void::triples(), count(self.len()));
+        description.insert(dataset.clone(), void::distinct_subjects(), count(subjects));
+        description.insert(dataset.clone(), void::properties(), count(properties.len()));
+        description.insert(dataset.clone(), void::classes(), count(classes.len()));
+        description
+    }
+
+    /// Split the graph into a schema graph and a data graph.
+    ///
+    /// The schema graph contains all triples with one of the [default schema predicates](#method.default_schema_predicates)
+    /// and the data graph contains all other triples. Since the schema of a graph rarely changes, it
+    /// may be stored or cached separately.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph, vocab::rdfs};
+    ///
+    /// let lion = Node::from("https://example.org/Lion");
+    /// let cat = Node::from("https://example.org/Cat");
+    /// let name = Node::from("https://example.org/name");
+    /// let graph: HashGraph = vec![
+    ///     (&lion, &rdfs::sub_class_of(), &cat),
+    ///     (&lion, &name, &Node::from("Lion")),
+    /// ].into_iter().collect();
+    ///
+    /// let (schema, data) = graph.partition_schema_data();
+    /// assert!(schema.contains(&lion, &rdfs::sub_class_of(), &cat));
+    /// assert!(data.contains(&lion, &name, &Node::from("Lion")));
+    /// ```
+    pub fn partition_schema_data(&self) -> (HashGraph<S>, HashGraph<S>) {
+        self.partition_schema_data_with(&HashGraph::default_schema_predicates())
+    }
+
+    /// Split the graph into a schema graph with the given predicates and a data graph.
+    ///
+    /// This is the same as [`partition_schema_data`](#method.partition_schema_data), but with a
+    /// custom set of schema predicates.
+    pub fn partition_schema_data_with(
+        &self,
+        schema_predicates: &HashSet<Node>,
+    ) -> (HashGraph<S>, HashGraph<S>) {
+        let mut schema = HashGraph::default();
+        let mut data = HashGraph::default();
+        for (s, p, o) in self.iter() {
+            if schema_predicates.contains(p) {
+                schema.clone_insert(s, p, o);
+            } else {
+                data.clone_insert(s, p, o);
+            }
+        }
+        (schema, data)
+    }
+
+    /// List every triple that isn't valid RDF, together with the reason.
+    ///
+    /// It reports why triples are invalid without modifying the graph, e.g. to log data-quality
+    /// issues of imported data. A triple with both a literal subject and a non-IRI predicate is
+    /// reported once for every issue.
+    ///
+    /// ## Differences to `is_valid_graph` and `sanitize`
+    ///
+    /// The report follows the RDF definition and classifies nodes by their
+    /// [kind](struct.Node.html#method.kind), so triples with a blank subject are valid.
+    /// [`is_valid_graph`](trait.Graph.html#method.is_valid_graph) and
+    /// [`sanitize`](trait.Graph.html#method.sanitize) treat every node that isn't an IRI as a
+    /// literal instead, so they also reject blank subjects. A graph with blank subjects may
+    /// therefore be invalid according to `is_valid_graph` and still have an empty report.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Graph, HashGraph, Node, ValidationIssue};
+    ///
+    /// let mut graph = HashGraph::new();
+    /// graph.insert(Node::from("urn:alice"), Node::from("urn:name"), Node::from("Alice"));
+    /// graph.insert(Node::from("Alice"), Node::from("urn:is"), Node::from("urn:alice"));
+    ///
+    /// let report = graph.validate_report();
+    /// assert_eq!(1, report.len());
+    /// assert_eq!(Node::from("Alice"), (report[0].0).0);
+    /// assert_eq!(ValidationIssue::LiteralSubject, report[0].1);
+    /// ```
+    pub fn validate_report(&self) -> Vec<((Node, Node, Node), ValidationIssue)> {
+        let mut report = Vec::new();
+        for (s, p, o) in self.iter() {
+            let triple = || (s.clone(), p.clone(), o.clone());
+            if s.kind() == NodeKind::Literal {
+                report.push((triple(), ValidationIssue::LiteralSubject));
+            }
+            if p.kind() != NodeKind::Iri {
+                report.push((triple(), ValidationIssue::NonIriPredicate));
+            }
+        }
+        report
+    }
+
+    /// Return all predicates that have both IRIs and literals as objects.
+    ///
+    /// A predicate is usually either used to link resources or to attach values to them. Therefore,
+    /// a predicate with both kinds of objects often indicates a modeling error. Blank objects are
+    /// ignored since they may stand for both.
+    pub fn mixed_object_kind_predicates(&self) -> Vec<Node> {
+        let mut kinds: HashMap<&Node, (bool, bool)> = HashMap::new();
+        for (_, predicate, object) in self.iter() {
+            let (has_iri, has_literal) = kinds.entry(predicate).or_default();
+            match object.kind() {
+                NodeKind::Iri => *has_iri = true,
+                NodeKind::Literal => *has_literal = true,
+                NodeKind::Blank => (),
+            }
+        }
+        kinds
+            .into_iter()
+            .filter(|(_, kinds)| *kinds == (true, true))
+            .map(|(predicate, _)| predicate.clone())
+            .collect()
+    }
+
+    /// Count the triples of every predicate.
+    ///
+    /// The counts are computed in a single pass over the graph. Predicates of any kind are counted,
+    /// including blank nodes and literals in generalized graphs.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:carol";
+    ///     "urn:bob" -> "urn:knows" -> "urn:carol";
+    ///     "urn:bob" -> "urn:name" -> "Bob";
+    /// };
+    ///
+    /// let counts = graph.count_by_predicate();
+    /// assert_eq!(3, counts[&Node::from("urn:knows")]);
+    /// assert_eq!(1, counts[&Node::from("urn:name")]);
+    /// ```
+    pub fn count_by_predicate(&self) -> HashMap<Node, usize> {
+        let mut counts: HashMap<Node, usize> = HashMap::new();
+        for relationships in self.nodes.values() {
+            for (predicate, objects) in relationships {
+                if !objects.is_empty() {
+                    *counts.entry(predicate.clone()).or_insert(0) += objects.len();
+                }
+            }
+        }
+        counts
+    }
+
+    /// Count the triples of every subject.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:name" -> "Alice";
+    ///     "urn:bob" -> "urn:name" -> "Bob";
+    /// };
+    ///
+    /// let counts = graph.count_by_subject();
+    /// assert_eq!(2, counts[&Node::from("urn:alice")]);
+    /// assert_eq!(1, counts[&Node::from("urn:bob")]);
+    /// ```
+    pub fn count_by_subject(&self) -> HashMap<Node, usize> {
+        self.nodes
+            .iter()
+            .map(|(subject, relationships)| {
+                let count = relationships.values().map(|objects| objects.len()).sum();
+                (subject.clone(), count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+
+    /// Return the number of triples with the node as their subject.
+    ///
+    /// Triples are indexed by their subject, so this only counts the objects of the node's
+    /// predicates, which takes time proportional to the number of its predicates.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:carol";
+    ///     "urn:bob" -> "urn:knows" -> "urn:carol";
+    /// };
+    ///
+    /// let carol = Node::from("urn:carol");
+    /// assert_eq!(2, graph.out_degree(&Node::from("urn:alice")));
+    /// assert_eq!(0, graph.out_degree(&carol));
+    /// assert_eq!(2, graph.in_degree(&carol));
+    /// ```
+    pub fn out_degree(&self, node: &Node) -> usize {
+        self.nodes
+            .get(node)
+            .map(|relationships| relationships.values().map(|objects| objects.len()).sum())
+            .unwrap_or(0)
+    }
+
+    /// Return the number of triples with the node as their object.
+    ///
+    /// There is no index of objects, so in contrast to [`out_degree`](#method.out_degree), this
+    /// looks up the node in the objects of every subject and predicate. It therefore takes time
+    /// proportional to the number of subject-predicate pairs in the graph. If you need the
+    /// in-degrees of many nodes, count the objects in a single pass over all triples instead.
+    pub fn in_degree(&self, node: &Node) -> usize {
+        self.nodes
+            .values()
+            .flat_map(|relationships| relationships.values())
+            .filter(|objects| objects.contains(node))
+            .count()
+    }
+
+    /// Compute metrics that characterize the graph.
+    ///
+    /// The averages and fractions of an empty graph are zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, HashGraph};
+    ///
+    /// let graph = graph! {
+    ///     alice -> knows -> bob;
+    ///     alice -> knows -> carol;
+    /// };
+    ///
+    /// let metrics = graph.metrics();
+    /// assert_eq!(2, metrics.triples);
+    /// assert_eq!(3, metrics.nodes);
+    /// assert_eq!(2, metrics.max_out_degree);
+    /// ```
+    pub fn metrics(&self) -> GraphMetrics {
+        let mut nodes: HashSet<&Node> = HashSet::new();
+        let mut triples = 0;
+        let mut max_out_degree = 0;
+        for (subject, relationships) in self.nodes.iter() {
+            let out_degree: usize = relationships.values().map(HashSet::len).sum();
+            if out_degree == 0 {
+                continue;
+            }
+            triples += out_degree;
+            max_out_degree = max_out_degree.max(out_degree);
+            nodes.insert(subject);
+            nodes.extend(relationships.values().flatten());
+        }
+
+        let (average_out_degree, blank_fraction) = if nodes.is_empty() {
+            (0.0, 0.0)
+        } else {
+            let n_blanks = nodes.iter().filter(|node| node.is_blank()).count();
+            (
+                triples as f64 / nodes.len() as f64,
+                n_blanks as f64 / nodes.len() as f64,
+            )
+        };
+
+        GraphMetrics {
+            triples,
+            nodes: nodes.len(),
+            average_out_degree,
+            max_out_degree,
+            blank_fraction,
+        }
+    }
+}
+
+#[test]
+fn count_by_predicate() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let blank_predicate = Node::blank();
+    graph.clone_insert(&validator.node_a, &validator.predicate_a, &validator.node_c);
+    graph.clone_insert(&validator.node_b, &validator.predicate_a, &validator.node_a);
+    graph.clone_insert(&validator.node_a, &blank_predicate, &validator.node_b);
+    graph.remove(&validator.node_b, &validator.predicate_b, &validator.node_c);
+
+    let counts = graph.count_by_predicate();
+    assert_eq!(3, counts.len());
+    assert_eq!(3, counts[&validator.predicate_a]);
+    assert_eq!(1, counts[&validator.predicate_c]);
+    assert_eq!(1, counts[&blank_predicate]);
+    assert!(!counts.contains_key(&validator.predicate_b));
+    assert_eq!(graph.len(), counts.values().sum::<usize>());
+
+    let counts = graph.count_by_subject();
+    assert_eq!(3, counts.len());
+    assert_eq!(3, counts[&validator.node_a]);
+    assert_eq!(1, counts[&validator.node_b]);
+    assert_eq!(1, counts[&validator.node_c]);
+}
+
+#[test]
+fn validate_report() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    assert!(graph.validate_report().is_empty());
+    // In contrast to `is_valid_graph`, the blank subject of node C isn't an issue.
+    assert!(!graph.is_valid_graph());
+
+    let literal = Node::from("Literal");
+    let blank_predicate = Node::blank();
+    graph.clone_insert(&literal, &validator.predicate_a, &validator.node_a);
+    graph.clone_insert(&validator.node_a, &literal, &validator.node_b);
+    graph.clone_insert(&validator.node_b, &blank_predicate, &literal);
+    graph.clone_insert(&literal, &literal, &literal);
+
+    let report: HashSet<((Node, Node, Node), ValidationIssue)> =
+        graph.validate_report().into_iter().collect();
+    let triple = |s: &Node, p: &Node, o: &Node| (s.clone(), p.clone(), o.clone());
+    assert_eq!(5, report.len());
+    assert!(report.contains(&(
+        triple(&literal, &validator.predicate_a, &validator.node_a),
+        ValidationIssue::LiteralSubject
+    )));
+    assert!(report.contains(&(
+        triple(&validator.node_a, &literal, &validator.node_b),
+        ValidationIssue::NonIriPredicate
+    )));
+    assert!(report.contains(&(
+        triple(&validator.node_b, &blank_predicate, &literal),
+        ValidationIssue::NonIriPredicate
+    )));
+    assert!(report.contains(&(
+        triple(&literal, &literal, &literal),
+        ValidationIssue::LiteralSubject
+    )));
+    assert!(report.contains(&(
+        triple(&literal, &literal, &literal),
+        ValidationIssue::NonIriPredicate
+    )));
+}
+
+#[test]
+fn degrees() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    graph.clone_insert(&validator.node_a, &validator.predicate_b, &validator.node_c);
+    graph.clone_insert(&validator.node_b, &validator.predicate_a, &validator.node_c);
+
+    assert_eq!(2, graph.out_degree(&validator.node_a));
+    assert_eq!(2, graph.out_degree(&validator.node_b));
+    assert_eq!(1, graph.out_degree(&validator.node_c));
+    assert_eq!(1, graph.in_degree(&validator.node_a));
+    assert_eq!(1, graph.in_degree(&validator.node_b));
+    assert_eq!(3, graph.in_degree(&validator.node_c));
+
+    // Predicates are neither counted as subjects nor as objects.
+    assert_eq!(0, graph.out_degree(&validator.predicate_a));
+    assert_eq!(0, graph.in_degree(&validator.predicate_a));
+}
+
+#[test]
+fn void_description() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let class = Node::from("urn:arrdf:tests:class:a");
+    graph.clone_insert(&validator.node_a, &rdf::type_(), &class);
+    graph.clone_insert(&validator.node_b, &rdf::type_(), &class);
+
+    let dataset = Node::from("urn:arrdf:tests:dataset");
+    let description = graph.void_description(&dataset);
+    let count = |n: usize| Node::typed_literal(&n.to_string(), &xsd::integer());
+
+    assert_eq!(5, description.len());
+    assert!(description.contains(&dataset, &rdf::type_(), &void::dataset()));
+    assert!(description.contains(&dataset, &void::triples(), &count(graph.len())));
+    assert!(description.contains(&dataset, &void::distinct_subjects(), &count(3)));
+    assert!(description.contains(&dataset, &void::properties(), &count(4)));
+    assert!(description.contains(&dataset, &void::classes(), &count(1)));
+}
+
+#[test]
+fn partition_schema_data() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph.clone();
+    let class_a = Node::from("urn:arrdf:tests:class:a");
+    let class_b = Node::from("urn:arrdf:tests:class:b");
+    graph.clone_insert(&class_a, &rdfs::sub_class_of(), &class_b);
+
+    let (schema, data) = graph.partition_schema_data();
+    assert_eq!(1, schema.len());
+    assert!(schema.contains(&class_a, &rdfs::sub_class_of(), &class_b));
+    assert_eq!(validator.graph, data);
+
+    let schema_predicates = vec![validator.predicate_a.clone()].into_iter().collect();
+    let (schema, data) = graph.partition_schema_data_with(&schema_predicates);
+    assert_eq!(1, schema.len());
+    assert!(schema.contains(&validator.node_a, &validator.predicate_a, &validator.node_b));
+    assert_eq!(3, data.len());
+}
+
+#[test]
+fn mixed_object_kind_predicates() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    assert!(graph.mixed_object_kind_predicates().is_empty());
+
+    graph.clone_insert(
+        &validator.node_a,
+        &validator.predicate_a,
+        &Node::from("Hello"),
+    );
+    graph.clone_insert(&validator.node_b, &validator.predicate_b, &Node::blank());
+    assert_eq!(
+        vec![validator.predicate_a.clone()],
+        graph.mixed_object_kind_predicates()
+    );
+}
+
+#[test]
+fn metrics() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let metrics = graph.metrics();
+    assert_eq!(3, metrics.triples);
+    assert_eq!(3, metrics.nodes);
+    assert_eq!(1.0, metrics.average_out_degree);
+    assert_eq!(1, metrics.max_out_degree);
+    assert_eq!(1.0 / 3.0, metrics.blank_fraction);
+
+    graph.clone_insert(&validator.node_a, &validator.predicate_b, &Node::blank());
+    let metrics = graph.metrics();
+    assert_eq!(4, metrics.triples);
+    assert_eq!(4, metrics.nodes);
+    assert_eq!(1.0, metrics.average_out_degree);
+    assert_eq!(2, metrics.max_out_degree);
+    assert_eq!(0.5, metrics.blank_fraction);
+
+    let metrics = HashGraph::new().metrics();
+    assert_eq!(0, metrics.nodes);
+    assert_eq!(0.0, metrics.average_out_degree);
+    assert_eq!(0.0, metrics.blank_fraction);
+}
+
+#[test]
+fn type_graph() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let class_a = Node::from("urn:arrdf:tests:class:a");
+    let class_b = Node::from("urn:arrdf:tests:class:b");
+    graph.clone_insert(&validator.node_a, &rdf::type_(), &class_a);
+    graph.clone_insert(&validator.node_b, &rdf::type_(), &class_b);
+    graph.clone_insert(&validator.node_b, &rdf::type_(), &class_a);
+    graph.clone_insert(
+        &validator.node_a,
+        &validator.predicate_b,
+        &Node::from("Hello"),
+    );
+    graph.clone_insert(
+        &validator.node_a,
+        &validator.predicate_c,
+        &Node::typed_literal("42", &xsd::integer()),
+    );
+    graph.clone_insert(
+        &validator.node_a,
+        &validator.predicate_c,
+        &Node::lang_literal("chat", "fr"),
+    );
+
+    // Node C has no type, so only the triple from A to B and the literals remain.
+    let type_graph = graph.type_graph();
+    assert_eq!(5, type_graph.len());
+    assert!(type_graph.contains(&class_a, &validator.predicate_a, &class_a));
+    assert!(type_graph.contains(&class_a, &validator.predicate_a, &class_b));
+    assert!(type_graph.contains(&class_a, &validator.predicate_b, &rdfs::literal()));
+    assert!(type_graph.contains(&class_a, &validator.predicate_c, &xsd::integer()));
+    assert!(type_graph.contains(&class_a, &validator.predicate_c, &rdf::lang_string()));
+}
@@ -0,0 +1,184 @@
+#[cfg(any(test, feature = "petgraph"))]
+use crate::Graph;
+use crate::{HashGraph, Node};
+#[cfg(feature = "petgraph")]
+use petgraph::graph::NodeIndex;
+#[cfg(feature = "petgraph")]
+use petgraph::visit::EdgeRef;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "petgraph")]
+use std::collections::HashMap;
+#[cfg(all(test, feature = "rayon"))]
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Return a parallel iterator over all triples of the graph.
+    ///
+    /// The work is split over the subjects of the graph, so graphs with only a handful of subjects
+    /// don't benefit much from it. This method is only available with the `rayon` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    /// use rayon::prelude::*;
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:bob" -> "urn:knows" -> "urn:carol";
+    ///     "urn:bob" -> "urn:name" -> "Bob";
+    /// };
+    ///
+    /// let knows = Node::from("urn:knows");
+    /// assert_eq!(2, graph.par_triples().filter(|(_, p, _)| *p == &knows).count());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_triples(&self) -> impl ParallelIterator<Item = (&Node, &Node, &Node)>
+    where
+        S: Sync,
+    {
+        self.nodes
+            .par_iter()
+            .flat_map_iter(|(subject, relationships)| {
+                relationships.iter().flat_map(move |(predicate, objects)| {
+                    objects
+                        .iter()
+                        .map(move |object| (subject, predicate, object))
+                })
+            })
+    }
+
+    /// Convert the graph into a [`petgraph`](https://docs.rs/petgraph) graph.
+    ///
+    /// Every node that appears as a subject or an object becomes a vertex, and every triple becomes
+    /// an edge from the subject's to the object's vertex, weighted with the predicate. Literals are
+    /// vertices just like IRIs and blank nodes; since they usually only appear as objects, they
+    /// become sink vertices without outgoing edges. Predicates only become vertices if they also
+    /// appear as a subject or an object.
+    ///
+    /// Next to the petgraph graph, a map from every node to its vertex is returned. This method is
+    /// only available with the `petgraph` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    /// use petgraph::algo::has_path_connecting;
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:bob" -> "urn:knows" -> "urn:carol";
+    ///     "urn:carol" -> "urn:name" -> "Carol";
+    /// };
+    ///
+    /// let (petgraph, indices) = graph.to_petgraph();
+    /// assert_eq!(4, petgraph.node_count());
+    /// assert_eq!(3, petgraph.edge_count());
+    ///
+    /// let alice = indices[&Node::from("urn:alice")];
+    /// let carol = indices[&Node::from("Carol")];
+    /// assert!(has_path_connecting(&petgraph, alice, carol, None));
+    /// ```
+    #[cfg(feature = "petgraph")]
+    pub fn to_petgraph(&self) -> (petgraph::Graph<Node, Node>, HashMap<Node, NodeIndex>) {
+        let mut petgraph = petgraph::Graph::new();
+        let mut indices: HashMap<Node, NodeIndex> = HashMap::new();
+        for (subject, predicate, object) in self.iter() {
+            let mut index_of = |node: &Node| {
+                *indices
+                    .entry(node.clone())
+                    .or_insert_with(|| petgraph.add_node(node.clone()))
+            };
+            let subject = index_of(subject);
+            let object = index_of(object);
+            petgraph.add_edge(subject, object, predicate.clone());
+        }
+        (petgraph, indices)
+    }
+}
+
+impl HashGraph {
+    /// Create a graph from a [`petgraph`](https://docs.rs/petgraph) graph.
+    ///
+    /// This is the reverse of [`to_petgraph`](#method.to_petgraph): Every edge becomes a triple with
+    /// the edge's source vertex as the subject, its weight as the predicate and its target vertex
+    /// as the object. Vertices without any edges are dropped, since a graph only consists of
+    /// triples. This method is only available with the `petgraph` feature.
+    #[cfg(feature = "petgraph")]
+    pub fn from_petgraph(petgraph: &petgraph::Graph<Node, Node>) -> Self {
+        petgraph
+            .edge_references()
+            .map(|edge| {
+                (
+                    &petgraph[edge.source()],
+                    edge.weight(),
+                    &petgraph[edge.target()],
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_triples() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph.clone();
+    for i in 0..100 {
+        graph.insert(
+            Node::from(format!("urn:arrdf:tests:node:{}", i).as_str()),
+            validator.predicate_a.clone(),
+            Node::from(format!("{}", i).as_str()),
+        );
+    }
+
+    let parallel: HashSet<(&Node, &Node, &Node)> = graph.par_triples().collect();
+    let sequential: HashSet<(&Node, &Node, &Node)> = graph.iter().collect();
+    assert_eq!(103, parallel.len());
+    assert_eq!(sequential, parallel);
+    assert_eq!(
+        101,
+        graph
+            .par_triples()
+            .filter(|(_, p, _)| *p == &validator.predicate_a)
+            .count()
+    );
+}
+
+#[cfg(feature = "petgraph")]
+#[test]
+fn petgraph_conversion() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph.clone();
+    let literal = Node::from("Hello World");
+    graph.clone_insert(&validator.node_a, &validator.predicate_b, &literal);
+
+    let (petgraph, indices) = graph.to_petgraph();
+    assert_eq!(4, petgraph.node_count());
+    assert_eq!(4, petgraph.edge_count());
+    assert_eq!(4, indices.len());
+    for (node, index) in indices.iter() {
+        assert_eq!(node, &petgraph[*index]);
+    }
+    assert!(!indices.contains_key(&validator.predicate_a));
+
+    // The literal is a sink, and the blank node keeps its identity.
+    let literal_index = indices[&literal];
+    assert_eq!(
+        0,
+        petgraph
+            .neighbors_directed(literal_index, petgraph::Direction::Outgoing)
+            .count()
+    );
+    assert_eq!(
+        1,
+        petgraph::algo::kosaraju_scc(&petgraph)
+            .iter()
+            .filter(|component| component.len() == 3)
+            .count()
+    );
+
+    assert_eq!(graph, HashGraph::from_petgraph(&petgraph));
+}
@@ -0,0 +1,1401 @@
+use crate::vocab::rdf;
+use crate::{iso, Graph, Node};
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::BuildHasher;
+
+mod analysis;
+#[cfg(any(feature = "petgraph", feature = "rayon"))]
+mod interop;
+mod normalize;
+#[cfg(feature = "ntriples")]
+mod roundtrip;
+mod set_ops;
+mod transform;
+mod traversal;
+
+pub use analysis::{GraphMetrics, ValidationIssue};
+#[cfg(feature = "ntriples")]
+pub use roundtrip::RoundtripError;
+
+/// A canonical implementation of the `Graph` trait.
+///
+/// The `HashGraph` implements the `Graph` trait with a hierarchy of `HashMap`s and a `HashSet` to
+/// efficiently support path traversals and containment queries. If you simply want to use a `Graph`,
+/// use this one.
+///
+/// Triples are indexed by subject, then by predicate, then by object. All methods of the `Graph`
+/// trait use this index, and lookups of missing subjects or predicates, like
+/// [`relationships`](trait.Graph.html#method.relationships) and
+/// [`objects`](trait.Graph.html#method.objects), return empty iterators. On top of the trait,
+/// the `HashGraph` provides in-place set operations like [`union_with`](#method.union_with),
+/// traversals like [`bfs`](#method.bfs) and [`transitive_closure`](#method.transitive_closure),
+/// and transformations like [`materialize_rdfs`](#method.materialize_rdfs) and
+/// [`skolemize`](#method.skolemize).
+///
+/// The maps and sets of the index use the hasher `S`, which defaults to the `RandomState` of the
+/// standard library. Another hasher can be plugged in with a type alias, for example
+/// `type FxHashGraph = HashGraph<BuildHasherDefault<FxHasher>>;`, and graphs of that type are
+/// created with `Default::default()` or collected from an iterator of triples. All methods and the
+/// `Graph` trait are available for every hasher, except for the associated functions that don't take
+/// a graph: Just like `HashMap::new`, the constructors [`new`](#method.new),
+/// [`with_triple_capacity`](#method.with_triple_capacity) and
+/// [`with_max_literal_len`](#method.with_max_literal_len) as well as
+/// [`from_petgraph`](#method.from_petgraph) and
+/// [`default_schema_predicates`](#method.default_schema_predicates) are only implemented for the
+/// default hasher, so that calls like `HashGraph::new()` don't need a type annotation. Graphs with
+/// another hasher therefore start out without a literal length limit.
+///
+/// Check out the [crate-level introduction](index.html) for some examples.
+#[derive(Clone, Debug, Default)]
+pub struct HashGraph<S = RandomState> {
+    nodes: HashMap<Node, HashMap<Node, HashSet<Node, S>, S>, S>,
+    max_literal_len: Option<usize>,
+}
+
+/// A builder for bulk-loading a `HashGraph`.
+///
+/// The builder only collects triples in a vector and builds the nested hash maps of the graph in
+/// one pass when [`build`](#method.build) is called. This avoids repeated hash map growth while loading
+/// a large number of triples. Duplicate triples are removed by `build`.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{Node, Graph, GraphBuilder};
+///
+/// let node_a = Node::from("Node A");
+/// let node_b = Node::from("Node B");
+/// let node_c = Node::from("Node C");
+///
+/// let mut builder = GraphBuilder::new();
+/// builder.push(node_a.clone(), node_b.clone(), node_c.clone());
+/// builder.push(node_a.clone(), node_b.clone(), node_c.clone());
+/// assert_eq!(2, builder.len());
+///
+/// let graph = builder.build();
+/// assert_eq!(1, graph.len());
+/// assert!(graph.contains(&node_a, &node_b, &node_c));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GraphBuilder {
+    triples: Vec<(Node, Node, Node)>,
+}
+
+impl GraphBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self {
+            triples: Vec::new(),
+        }
+    }
+
+    /// Create a new, empty builder with space for the given number of triples.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            triples: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Add a triple to the builder.
+    pub fn push(&mut self, subject: Node, predicate: Node, object: Node) {
+        self.triples.push((subject, predicate, object));
+    }
+
+    /// Return the number of collected triples, including duplicates.
+    pub fn len(&self) -> usize {
+        self.triples.len()
+    }
+
+    /// Return `true` if no triples were collected.
+    pub fn is_empty(&self) -> bool {
+        self.triples.is_empty()
+    }
+
+    /// Build the graph from the collected triples.
+    pub fn build(self) -> HashGraph {
+        let mut nodes: HashMap<Node, HashMap<Node, HashSet<Node>>> =
+            HashMap::with_capacity(self.triples.len());
+        for (subject, predicate, object) in self.triples {
+            nodes
+                .entry(subject)
+                .or_default()
+                .entry(predicate)
+                .or_default()
+                .insert(object);
+        }
+        nodes.shrink_to_fit();
+        HashGraph {
+            nodes,
+            max_literal_len: None,
+        }
+    }
+}
+
+impl Extend<(Node, Node, Node)> for GraphBuilder {
+    fn extend<T: IntoIterator<Item = (Node, Node, Node)>>(&mut self, iter: T) {
+        self.triples.extend(iter);
+    }
+}
+
+/// The objects of a subject and predicate in a `HashGraph`, returned by [`HashGraph::entry`](struct.HashGraph.html#method.entry).
+///
+/// The entry borrows the set of objects mutably, and with it the whole graph.
+pub struct ObjectsEntry<'a, S = RandomState> {
+    objects: &'a mut HashSet<Node, S>,
+    max_literal_len: Option<usize>,
+}
+
+impl<'a, S: BuildHasher> ObjectsEntry<'a, S> {
+    /// Insert an object and return `true` if it's new.
+    ///
+    /// Like [`Graph::insert`](trait.Graph.html#tymethod.insert), literals that exceed the
+    /// [maximal literal length](struct.HashGraph.html#method.with_max_literal_len) of the graph
+    /// are ignored. Use [`try_insert`](#method.try_insert) to detect them.
+    pub fn insert(&mut self, object: Node) -> bool {
+        self.try_insert(object).unwrap_or(false)
+    }
+
+    /// Insert an object and return whether it's new, or an error if it's a literal that is too long.
+    pub fn try_insert(&mut self, object: Node) -> Result<bool, LiteralLengthError> {
+        check_literal_len(self.max_literal_len, &object)?;
+        Ok(self.objects.insert(object))
+    }
+
+    /// Return `true` if the entry contains the given object.
+    pub fn contains(&self, object: &Node) -> bool {
+        self.objects.contains(object)
+    }
+
+    /// Return the number of objects.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Return `true` if there are no objects.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Iterate over the objects.
+    pub fn iter(&self) -> impl Iterator<Item = &Node> {
+        self.objects.iter()
+    }
+}
+
+/// The error returned by [`HashGraph::try_insert`](struct.HashGraph.html#method.try_insert) if a literal object is too long.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LiteralLengthError {
+    /// The length of the rejected literal, in bytes.
+    pub len: usize,
+    /// The maximal length of literals in the graph, in bytes.
+    pub limit: usize,
+}
+
+impl fmt::Display for LiteralLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Literal of length {} exceeds the limit of {}",
+            self.len, self.limit
+        )
+    }
+}
+
+impl std::error::Error for LiteralLengthError {}
+
+impl HashGraph {
+    /// Create a new, empty graph.
+    pub fn new() -> Self {
+        HashGraph {
+            nodes: HashMap::new(),
+            max_literal_len: None,
+        }
+    }
+
+    /// Create a new, empty graph with space for at least the given number of triples.
+    ///
+    /// Since the subjects of the triples aren't known yet, space is reserved as if every triple
+    /// had a different subject. See [`reserve`](#method.reserve) for details.
+    pub fn with_triple_capacity(triples: usize) -> Self {
+        let mut graph = HashGraph::new();
+        graph.reserve(triples);
+        graph
+    }
+
+    /// Create a new, empty graph that only accepts literal objects up to the given length.
+    ///
+    /// The length of a literal is the length of its [lexical form](struct.Node.html#method.lexical_form) in bytes.
+    /// Triples with longer literal objects are ignored by [`insert`](trait.Graph.html#tymethod.insert) and
+    /// rejected with an error by [`try_insert`](#method.try_insert). This guards the graph against
+    /// oversized literals from untrusted input.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph};
+    ///
+    /// let mut graph = HashGraph::with_max_literal_len(5);
+    /// let subject = Node::from("urn:subject");
+    /// let predicate = Node::from("urn:predicate");
+    ///
+    /// graph.insert(subject.clone(), predicate.clone(), Node::from("Hello"));
+    /// graph.insert(subject.clone(), predicate.clone(), Node::from("Hello World"));
+    /// assert_eq!(1, graph.len());
+    ///
+    /// assert!(graph.try_insert(subject, predicate, Node::from("Hello World")).is_err());
+    /// ```
+    pub fn with_max_literal_len(limit: usize) -> Self {
+        HashGraph {
+            nodes: HashMap::new(),
+            max_literal_len: Some(limit),
+        }
+    }
+}
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Reserve space for at least `additional` more triples.
+    ///
+    /// The graph stores the relationships of every subject in a separate map, so only the map of
+    /// subjects can be grown in advance. The number of new subjects is estimated from the average
+    /// number of triples per subject in the graph, or taken to be `additional` if the graph is
+    /// empty. This avoids repeatedly growing the map of subjects when many triples are inserted.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Graph, HashGraph, Node};
+    ///
+    /// let mut graph = HashGraph::new();
+    /// graph.reserve(1000);
+    /// for i in 0..1000 {
+    ///     let subject = Node::from(format!("urn:subject:{}", i).as_str());
+    ///     graph.insert(subject, Node::from("urn:value"), Node::from("42"));
+    /// }
+    /// assert_eq!(1000, graph.len());
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        let subjects = self.nodes.len();
+        let triples = if subjects == 0 { 0 } else { self.len() };
+        let estimate = match triples {
+            0 => additional,
+            _ => {
+                let scaled = additional.saturating_mul(subjects);
+                scaled.saturating_add(triples - 1) / triples
+            }
+        };
+        self.nodes.reserve(estimate);
+    }
+
+    /// Drop empty entries and shrink the capacity of all maps as much as possible.
+    ///
+    /// Removing triples may leave empty object sets, predicate maps and subject entries behind.
+    /// This method removes them and then shrinks every remaining map, which reclaims memory after
+    /// bulk deletions.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Graph, HashGraph, Node};
+    ///
+    /// let mut graph = HashGraph::with_triple_capacity(1000);
+    /// graph.insert(Node::from("urn:alice"), Node::from("urn:knows"), Node::from("urn:bob"));
+    /// graph.shrink_to_fit();
+    /// assert_eq!(1, graph.len());
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.retain(|_, relationships| {
+            relationships.retain(|_, objects| {
+                objects.shrink_to_fit();
+                !objects.is_empty()
+            });
+            relationships.shrink_to_fit();
+            !relationships.is_empty()
+        });
+        self.nodes.shrink_to_fit();
+    }
+
+    /// Return `true` if the graph doesn't contain any triples.
+    ///
+    /// This is the same as [`Graph::is_empty`](trait.Graph.html#method.is_empty), but doesn't
+    /// require the trait to be in scope.
+    pub fn is_empty(&self) -> bool {
+        self.nodes
+            .values()
+            .all(|rels| rels.values().all(|objects| objects.is_empty()))
+    }
+
+    /// Remove all triples from the graph.
+    ///
+    /// This is the same as [`Graph::clear`](trait.Graph.html#method.clear), but doesn't require
+    /// the trait to be in scope. The [maximal literal length](#method.with_max_literal_len) of the
+    /// graph is kept.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, HashGraph};
+    ///
+    /// let mut graph: HashGraph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    /// };
+    /// assert!(!graph.is_empty());
+    /// graph.clear();
+    /// assert!(graph.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Return the maximal length of literal objects, if there is one.
+    pub fn max_literal_len(&self) -> Option<usize> {
+        self.max_literal_len
+    }
+
+    /// Set the maximal length of literal objects, without checking the existing triples.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn set_max_literal_len(&mut self, limit: Option<usize>) {
+        self.max_literal_len = limit;
+    }
+
+    /// Insert a triple into the graph or return an error if the object is a literal that is too long.
+    ///
+    /// Graphs without a [maximal literal length](#method.with_max_literal_len) accept every triple.
+    pub fn try_insert(
+        &mut self,
+        subject: Node,
+        predicate: Node,
+        object: Node,
+    ) -> Result<(), LiteralLengthError> {
+        check_literal_len(self.max_literal_len, &object)?;
+        self.nodes
+            .entry(subject)
+            .or_default()
+            .entry(predicate)
+            .or_default()
+            .insert(object);
+        Ok(())
+    }
+
+    /// Look up the objects of a subject and predicate once to insert several objects.
+    ///
+    /// Every insertion into the graph hashes the subject and the predicate to find the set of
+    /// objects. The returned entry holds on to this set instead, so that the subject and the
+    /// predicate are only hashed once, like [`HashMap::entry`](https://doc.rust-lang.org/std/collections/struct.HashMap.html#method.entry).
+    /// The entry mutably borrows the graph, so the graph can't be read or modified in any other
+    /// way until the entry is dropped. If no object is inserted, an empty set of objects is left
+    /// behind. It doesn't affect the triples or the equality of the graph, and
+    /// [`shrink_to_fit`](#method.shrink_to_fit) removes it.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Graph, HashGraph, Node};
+    ///
+    /// let mut graph = HashGraph::new();
+    /// let alice = Node::from("urn:alice");
+    /// let knows = Node::from("urn:knows");
+    ///
+    /// let mut entry = graph.entry(alice.clone(), knows.clone());
+    /// for name in &["urn:bob", "urn:carol", "urn:bob"] {
+    ///     entry.insert(Node::from(*name));
+    /// }
+    /// assert_eq!(2, entry.len());
+    ///
+    /// assert_eq!(2, graph.len());
+    /// assert!(graph.contains(&alice, &knows, &Node::from("urn:carol")));
+    /// ```
+    pub fn entry(&mut self, subject: Node, predicate: Node) -> ObjectsEntry<'_, S> {
+        let objects = self
+            .nodes
+            .entry(subject)
+            .or_default()
+            .entry(predicate)
+            .or_default();
+        ObjectsEntry {
+            objects,
+            max_literal_len: self.max_literal_len,
+        }
+    }
+}
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Return a new graph with clones of all triples that satisfy `f`.
+    ///
+    /// This is the non-mutating counterpart of [`retain`](trait.Graph.html#tymethod.retain): The
+    /// graph itself is left untouched.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:name" -> "Alice";
+    /// };
+    ///
+    /// let names = graph.filter_into(|_, _, o| o.is_literal());
+    /// assert_eq!(1, names.len());
+    /// assert_eq!(2, graph.len());
+    /// ```
+    pub fn filter_into<F: FnMut(&Node, &Node, &Node) -> bool>(&self, mut f: F) -> HashGraph<S> {
+        self.iter().filter(|(s, p, o)| f(s, p, o)).collect()
+    }
+
+    /// Retain only the triples whose subject satisfies `f`.
+    ///
+    /// In contrast to [`retain`](trait.Graph.html#tymethod.retain), `f` is only called once per
+    /// subject and all triples of a rejected subject are dropped at once.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let mut graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:name" -> "Alice";
+    ///     "urn:bob" -> "urn:name" -> "Bob";
+    /// };
+    ///
+    /// graph.retain_subjects(|subject| subject.as_str() != "urn:alice");
+    /// assert_eq!(1, graph.len());
+    /// ```
+    pub fn retain_subjects<F: FnMut(&Node) -> bool>(&mut self, mut f: F) {
+        self.nodes.retain(|subject, _| f(subject));
+    }
+
+    /// Retain only the triples whose predicate satisfies `f`.
+    ///
+    /// `f` is called once per subject and predicate, and all objects of a rejected predicate are
+    /// dropped at once. Subjects that are left without any predicates are removed too.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let mut graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:name" -> "Alice";
+    ///     "urn:bob" -> "urn:name" -> "Bob";
+    /// };
+    ///
+    /// let name = Node::from("urn:name");
+    /// graph.retain_predicates(|predicate| predicate == &name);
+    /// assert_eq!(2, graph.len());
+    /// ```
+    pub fn retain_predicates<F: FnMut(&Node) -> bool>(&mut self, mut f: F) {
+        self.nodes.retain(|_, relationships| {
+            relationships.retain(|predicate, _| f(predicate));
+            !relationships.is_empty()
+        });
+    }
+
+    /// Return every distinct node that appears as the subject, predicate or object of a triple.
+    ///
+    /// Every node is yielded exactly once, in an arbitrary order. The nodes are deduplicated in a
+    /// single pass over all triples, which keeps a set of the nodes that have been yielded so far.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:bob" -> "urn:knows" -> "urn:alice";
+    /// };
+    ///
+    /// let mut nodes: Vec<&Node> = graph.nodes().collect();
+    /// nodes.sort();
+    /// assert_eq!(
+    ///     vec![&Node::from("urn:alice"), &Node::from("urn:bob"), &Node::from("urn:knows")],
+    ///     nodes
+    /// );
+    /// ```
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        let mut seen: HashSet<&Node> = HashSet::new();
+        self.iter()
+            .flat_map(|(s, p, o)| IntoIterator::into_iter([s, p, o]))
+            .filter(move |node| seen.insert(node))
+    }
+
+    /// Return an iterator over the types of the subject.
+    ///
+    /// These are the objects of all triples with the given subject and `rdf:type` as the predicate.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Node, Graph, HashGraph, vocab::rdf};
+    ///
+    /// let rust_lv2 = Node::from("https://github.com/RustAudio/rust-lv2");
+    /// let software = Node::from("http://schema.org/SoftwareSourceCode");
+    /// let graph: HashGraph = vec![(&rust_lv2, &rdf::type_(), &software)].into_iter().collect();
+    ///
+    /// let types: Vec<&Node> = graph.types_of(&rust_lv2).collect();
+    /// assert_eq!(vec![&software], types);
+    /// ```
+    pub fn types_of<'a>(&'a self, subject: &'a Node) -> impl 'a + Iterator<Item = &'a Node> {
+        self.nodes
+            .get(subject)
+            .and_then(|relationships| relationships.get(&rdf::type_()))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Return an object of the triples with the given subject and predicate, if there is one.
+    ///
+    /// This is only meaningful for properties that are known to have at most one value per
+    /// subject: If there are several objects, an arbitrary one is returned, and it may differ
+    /// between calls after the graph was modified. Use [`get_objects`](#method.get_objects) for
+    /// multi-valued properties.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! { "urn:alice" -> "urn:name" -> "Alice"; };
+    /// let alice = Node::from("urn:alice");
+    ///
+    /// assert_eq!(Some(&Node::from("Alice")), graph.get_object(&alice, &Node::from("urn:name")));
+    /// assert_eq!(None, graph.get_object(&alice, &Node::from("urn:age")));
+    /// ```
+    pub fn get_object(&self, subject: &Node, predicate: &Node) -> Option<&Node> {
+        self.nodes
+            .get(subject)
+            .and_then(|relationships| relationships.get(predicate))
+            .and_then(|objects| objects.iter().next())
+    }
+
+    /// Return an iterator over the objects of all triples with the given subject and predicate.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:carol";
+    /// };
+    ///
+    /// let (alice, knows) = (Node::from("urn:alice"), Node::from("urn:knows"));
+    /// assert_eq!(2, graph.get_objects(&alice, &knows).count());
+    /// ```
+    pub fn get_objects<'a>(
+        &'a self,
+        subject: &Node,
+        predicate: &Node,
+    ) -> impl 'a + Iterator<Item = &'a Node> {
+        self.nodes
+            .get(subject)
+            .and_then(|relationships| relationships.get(predicate))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Return `true` if the graphs are equal up to the identity of their blank nodes.
+    ///
+    /// In contrast to `==`, which compares blank nodes by identity, this checks whether the blank
+    /// nodes of one graph can be relabeled to turn it into the other one. All other triples have to
+    /// match exactly. See the [`iso`](iso/index.html) module for details.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let blank = Node::blank();
+    /// let a = graph! { "urn:alice" -> "urn:knows" -> (blank); };
+    /// let blank = Node::blank();
+    /// let b = graph! { "urn:alice" -> "urn:knows" -> (blank); };
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.is_isomorphic(&b));
+    /// ```
+    pub fn is_isomorphic(&self, other: &HashGraph<S>) -> bool {
+        iso::is_isomorphic(self, other)
+    }
+
+    /// Modify the relationships of a subject in place.
+    ///
+    /// The closure receives mutable access to the map from predicates to objects of the subject,
+    /// which is created if the subject isn't in the graph yet. Predicates without objects and the
+    /// subject itself are removed afterwards if they were left empty. This is an escape hatch for
+    /// complex edits, so the closure bypasses the literal length limit of the graph.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{Graph, HashGraph, Node};
+    ///
+    /// let node_a = Node::from("Node A");
+    /// let node_b = Node::from("Node B");
+    /// let node_c = Node::from("Node C");
+    ///
+    /// let mut graph = HashGraph::new();
+    /// graph.modify_subject(node_a.clone(), |relationships| {
+    ///     relationships.entry(node_b.clone()).or_default().insert(node_c.clone());
+    /// });
+    /// assert!(graph.contains(&node_a, &node_b, &node_c));
+    /// ```
+    pub fn modify_subject<F>(&mut self, subject: Node, f: F)
+    where
+        F: FnOnce(&mut HashMap<Node, HashSet<Node, S>, S>),
+    {
+        let relationships = self.nodes.entry(subject.clone()).or_default();
+        f(relationships);
+        relationships.retain(|_, objects| !objects.is_empty());
+        if relationships.is_empty() {
+            self.nodes.remove(&subject);
+        }
+    }
+}
+
+fn check_literal_len(limit: Option<usize>, object: &Node) -> Result<(), LiteralLengthError> {
+    match limit {
+        Some(limit) => {
+            let len = object.lexical_form().len();
+            if len > limit && object.is_literal() {
+                Err(LiteralLengthError { len, limit })
+            } else {
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+impl<S: BuildHasher> PartialEq for HashGraph<S> {
+    fn eq(&self, other: &Self) -> bool {
+        // Object sets may be left empty by `retain` and `entry`. They don't belong to any triple,
+        // so they are skipped on both sides.
+        let contains_all = |graph: &Self, other: &Self| {
+            graph.nodes.iter().all(|(subject, relationships)| {
+                relationships.iter().all(|(predicate, objects)| {
+                    objects.is_empty()
+                        || other
+                            .nodes
+                            .get(subject)
+                            .and_then(|relationships| relationships.get(predicate))
+                            == Some(objects)
+                })
+            })
+        };
+        contains_all(self, other) && contains_all(other, self)
+    }
+}
+
+impl<S: BuildHasher> Eq for HashGraph<S> {}
+
+impl<S: BuildHasher + Default> Graph for HashGraph<S> {
+    fn len(&self) -> usize {
+        self.nodes
+            .values()
+            .map(|relationships| {
+                relationships
+                    .values()
+                    .map(|objects| objects.len())
+                    .sum::<usize>()
+            })
+            .sum::<usize>()
+    }
+
+    fn is_empty(&self) -> bool {
+        HashGraph::<S>::is_empty(self)
+    }
+
+    fn contains(&self, subject: &Node, predicate: &Node, object: &Node) -> bool {
+        self.nodes
+            .get(subject)
+            .and_then(|r| r.get(predicate))
+            .map(|o| o.contains(object))
+            .unwrap_or(false)
+    }
+
+    fn is_ground(&self) -> bool {
+        // Every subject and predicate only has to be checked once, not once per triple. Entries
+        // without objects are skipped, since they don't belong to any triple.
+        self.nodes.iter().all(|(subject, relationships)| {
+            relationships.iter().all(|(predicate, objects)| {
+                objects.is_empty()
+                    || (!subject.is_blank()
+                        && !predicate.is_blank()
+                        && !objects.iter().any(Node::is_blank))
+            })
+        })
+    }
+
+    fn contains_subject_predicate(&self, subject: &Node, predicate: &Node) -> bool {
+        // Object sets may be left empty by `retain`, so they have to be checked too.
+        self.nodes
+            .get(subject)
+            .and_then(|r| r.get(predicate))
+            .map(|o| !o.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = (&'a Node, &'a Node, &'a Node)>> {
+        let relationships = self
+            .nodes
+            .iter()
+            .map(|(subject, relationships)| {
+                relationships
+                    .iter()
+                    .map(move |(predicate, objects)| {
+                        objects
+                            .iter()
+                            .map(move |object| (subject, predicate, object))
+                    })
+                    .flatten()
+            })
+            .flatten();
+        Box::new(relationships)
+    }
+
+    fn insert(&mut self, subject: Node, predicate: Node, object: Node) {
+        // Literals that are too long are ignored, use `try_insert` to detect them.
+        self.try_insert(subject, predicate, object).ok();
+    }
+
+    fn insert_if_absent(&mut self, subject: Node, predicate: Node, object: Node) -> bool {
+        // Check the literal first, so that a rejected one doesn't leave an empty entry behind.
+        check_literal_len(self.max_literal_len, &object).is_ok()
+            && self.entry(subject, predicate).insert(object)
+    }
+
+    fn remove(&mut self, subject: &Node, predicate: &Node, object: &Node) {
+        let relationships = match self.nodes.get_mut(subject) {
+            Some(relationships) => relationships,
+            None => return,
+        };
+        if let Some(objects) = relationships.get_mut(predicate) {
+            objects.remove(object);
+            // Drop empty entries, so that the subject is only kept while it has triples.
+            if objects.is_empty() {
+                relationships.remove(predicate);
+            }
+        }
+        if relationships.is_empty() {
+            self.nodes.remove(subject);
+        }
+    }
+
+    fn retain<F: FnMut(&Node, &Node, &Node) -> bool>(&mut self, mut f: F) {
+        for (subject, relationships) in self.nodes.iter_mut() {
+            for (predicate, objects) in relationships.iter_mut() {
+                objects.retain(|object| f(subject, predicate, object));
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        HashGraph::<S>::clear(self)
+    }
+
+    fn relationships<'a>(
+        &'a self,
+        subject: &'a Node,
+    ) -> Box<dyn 'a + Iterator<Item = (&Node, &Node, &Node)>> {
+        if let Some(relationships) = self.nodes.get(subject) {
+            let iter = relationships
+                .iter()
+                .map(|(predicate, objects)| objects.iter().map(move |object| (predicate, object)))
+                .flatten();
+            let iter = iter.map(move |(predicate, object)| (subject, predicate, object));
+            Box::new(iter)
+        } else {
+            Box::new(std::iter::empty())
+        }
+    }
+
+    fn objects<'a>(
+        &'a self,
+        subject: &'a Node,
+        predicate: &'a Node,
+    ) -> Box<dyn 'a + Iterator<Item = (&Node, &Node, &Node)>> {
+        if let Some(objects) = self
+            .nodes
+            .get(subject)
+            .and_then(|relationships| relationships.get(predicate))
+        {
+            Box::new(
+                objects
+                    .iter()
+                    .map(move |object| (subject, predicate, object)),
+            )
+        } else {
+            Box::new(std::iter::empty())
+        }
+    }
+}
+
+impl<S: BuildHasher + Default> std::iter::FromIterator<(Node, Node, Node)> for HashGraph<S> {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (Node, Node, Node)>,
+    {
+        let iter = iter.into_iter();
+        let mut graph = Self::default();
+        graph.reserve(iter.size_hint().0);
+        graph.extend(iter);
+        graph
+    }
+}
+
+impl<'a, S: BuildHasher + Default> std::iter::FromIterator<(&'a Node, &'a Node, &'a Node)>
+    for HashGraph<S>
+{
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (&'a Node, &'a Node, &'a Node)>,
+    {
+        iter.into_iter()
+            .map(|(s, p, o)| (s.clone(), p.clone(), o.clone()))
+            .collect()
+    }
+}
+
+impl<S: 'static> std::iter::IntoIterator for HashGraph<S> {
+    type Item = (Node, Node, Node);
+    type IntoIter = Box<dyn Iterator<Item = (Node, Node, Node)>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let relationships = self
+            .nodes
+            .into_iter()
+            .map(|(subject, relationships)| {
+                relationships
+                    .into_iter()
+                    .map(|(predicate, objects)| {
+                        objects
+                            .into_iter()
+                            .map(move |object| (predicate.clone(), object))
+                    })
+                    .flatten()
+                    .map(move |(predicate, object)| (subject.clone(), predicate, object))
+            })
+            .flatten();
+        Box::new(relationships)
+    }
+}
+
+/// Iterate over borrowed triples, just like [`Graph::iter`](trait.Graph.html#tymethod.iter).
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{graph, Node};
+///
+/// let graph = graph! {
+///     "urn:alice" -> "urn:knows" -> "urn:bob";
+///     "urn:bob" -> "urn:knows" -> "urn:carol";
+/// };
+///
+/// let mut subjects: Vec<&Node> = Vec::new();
+/// for (subject, _, _) in &graph {
+///     subjects.push(subject);
+/// }
+/// assert_eq!(2, subjects.len());
+/// ```
+impl<'a, S: BuildHasher + Default> std::iter::IntoIterator for &'a HashGraph<S> {
+    type Item = (&'a Node, &'a Node, &'a Node);
+    type IntoIter = Box<dyn 'a + Iterator<Item = (&'a Node, &'a Node, &'a Node)>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+#[cfg(test)]
+fn validate() {
+    let mut validator = crate::Validator::new(HashGraph::new());
+    validator.validate();
+}
+
+#[test]
+fn validate_with_custom_hasher() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+
+    type CustomHashGraph = HashGraph<BuildHasherDefault<DefaultHasher>>;
+    let mut validator = crate::Validator::new(CustomHashGraph::default());
+    validator.validate();
+
+    let graph: CustomHashGraph = validator.graph.iter().collect();
+    assert_eq!(validator.graph, graph);
+}
+
+#[test]
+fn custom_hasher_api() {
+    use crate::vocab::xsd;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+
+    type CustomHashGraph = HashGraph<BuildHasherDefault<DefaultHasher>>;
+    let validator = crate::Validator::new(CustomHashGraph::default());
+    let (node_a, node_b) = (&validator.node_a, &validator.node_b);
+    let mut graph = validator.graph.clone();
+    graph.clone_insert(
+        node_a,
+        &validator.predicate_b,
+        &Node::typed_literal("01.50", &xsd::decimal()),
+    );
+
+    let literals: CustomHashGraph = graph.filter_into(|_, _, o| o.is_literal() && !o.is_blank());
+    assert_eq!(1, literals.len());
+    assert_eq!(graph, &validator.graph | &literals);
+    assert_eq!(literals, &graph - &validator.graph);
+    assert_eq!(1, validator.graph.diff(&graph).added.len());
+    assert!(graph.bfs(node_a, &[]).any(|node| node == node_b));
+    assert!(graph.is_isomorphic(&graph.clone()));
+
+    graph.normalize_numeric_literals();
+    assert!(graph.contains(
+        node_a,
+        &validator.predicate_b,
+        &Node::typed_literal("1.5", &xsd::decimal())
+    ));
+    graph.modify_subject(node_a.clone(), |relationships| {
+        relationships.remove(&validator.predicate_b);
+    });
+    assert_eq!(validator.graph, graph);
+}
+
+#[test]
+fn max_literal_len() {
+    let mut graph = HashGraph::with_max_literal_len(8);
+    let subject = Node::from("urn:arrdf:tests:node:a");
+    let predicate = Node::from("urn:arrdf:tests:predicate:a");
+    let short = Node::from("Hello");
+    let long = Node::from("Hello World");
+    let iri = Node::from("urn:arrdf:tests:node:b");
+    assert_eq!(Some(8), graph.max_literal_len());
+
+    graph
+        .try_insert(subject.clone(), predicate.clone(), short.clone())
+        .unwrap();
+    assert_eq!(
+        Err(LiteralLengthError { len: 11, limit: 8 }),
+        graph.try_insert(subject.clone(), predicate.clone(), long.clone())
+    );
+
+    // IRIs are never rejected and `insert` silently drops literals that are too long.
+    graph.insert(subject.clone(), predicate.clone(), iri.clone());
+    graph.insert(subject.clone(), predicate.clone(), long.clone());
+    assert!(!graph.insert_if_absent(subject.clone(), predicate.clone(), long.clone()));
+    let mut empty = HashGraph::with_max_literal_len(5);
+    assert!(!empty.insert_if_absent(subject.clone(), predicate.clone(), long.clone()));
+    assert!(empty.nodes.is_empty());
+
+    assert_eq!(2, graph.len());
+    assert!(graph.contains(&subject, &predicate, &short));
+    assert!(graph.contains(&subject, &predicate, &iri));
+    assert!(!graph.contains(&subject, &predicate, &long));
+}
+
+#[test]
+fn get_object() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    assert_eq!(
+        Some(&validator.node_b),
+        graph.get_object(&validator.node_a, &validator.predicate_a)
+    );
+    assert_eq!(
+        None,
+        graph.get_object(&validator.node_a, &validator.predicate_b)
+    );
+    assert_eq!(
+        None,
+        graph.get_object(
+            &Node::from("urn:arrdf:tests:node:unknown"),
+            &validator.predicate_a
+        )
+    );
+
+    graph.clone_insert(&validator.node_a, &validator.predicate_a, &validator.node_c);
+    let objects: HashSet<&Node> = graph
+        .get_objects(&validator.node_a, &validator.predicate_a)
+        .collect();
+    assert_eq!(2, objects.len());
+    assert!(objects.contains(&validator.node_b));
+    assert!(objects.contains(&validator.node_c));
+    assert!(objects.contains(
+        graph
+            .get_object(&validator.node_a, &validator.predicate_a)
+            .unwrap()
+    ));
+    assert_eq!(
+        0,
+        graph
+            .get_objects(&validator.node_b, &validator.predicate_a)
+            .count()
+    );
+}
+
+#[test]
+fn insert_all_from() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = HashGraph::new();
+    graph.clone_insert(&validator.node_a, &validator.predicate_a, &validator.node_a);
+    graph.insert_all_from(validator.graph.clone());
+    assert_eq!(4, graph.len());
+    assert!(crate::set::is_subset(&validator.graph, &graph));
+}
+
+#[test]
+fn filter_into() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let original = validator.graph.clone();
+    let filtered = validator
+        .graph
+        .filter_into(|s, _, o| !s.is_blank() && !o.is_blank());
+
+    assert_eq!(1, filtered.len());
+    assert!(filtered.contains(&validator.node_a, &validator.predicate_a, &validator.node_b));
+    assert_eq!(original, validator.graph);
+    assert!(validator.graph.filter_into(|_, _, _| false).is_empty());
+}
+
+#[cfg(test)]
+fn set_operation_operands() -> (crate::Validator<HashGraph>, HashGraph) {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut other = HashGraph::new();
+    other.clone_insert(&validator.node_a, &validator.predicate_a, &validator.node_b);
+    other.clone_insert(&validator.node_b, &validator.predicate_a, &validator.node_a);
+    (validator, other)
+}
+
+#[test]
+fn retain_subjects() {
+    let (mut validator, other) = set_operation_operands();
+    validator.graph.union_with(&other);
+    let node_a = &validator.node_a;
+    let mut calls = 0;
+    validator.graph.retain_subjects(|subject| {
+        calls += 1;
+        subject != node_a
+    });
+
+    let graph = &validator.graph;
+    assert_eq!(3, calls);
+    assert_eq!(3, graph.len());
+    assert!(graph.contains(&validator.node_c, &validator.predicate_c, &validator.node_a));
+    assert!(graph.contains(&validator.node_b, &validator.predicate_b, &validator.node_c));
+    assert!(graph.contains(&validator.node_b, &validator.predicate_a, &validator.node_a));
+    assert_eq!(0, graph.relationships(&validator.node_a).count());
+}
+
+#[test]
+fn retain_predicates() {
+    let (mut validator, other) = set_operation_operands();
+    validator.graph.union_with(&other);
+    let predicate_a = &validator.predicate_a;
+    validator
+        .graph
+        .retain_predicates(|predicate| predicate == predicate_a);
+
+    let graph = &validator.graph;
+    assert_eq!(2, graph.len());
+    assert!(graph.contains(&validator.node_a, &validator.predicate_a, &validator.node_b));
+    assert!(graph.contains(&validator.node_b, &validator.predicate_a, &validator.node_a));
+    assert!(!graph.nodes.contains_key(&validator.node_c));
+}
+
+#[test]
+fn types_of() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let class_a = Node::from("urn:arrdf:tests:class:a");
+    let class_b = Node::from("urn:arrdf:tests:class:b");
+    graph.clone_insert(&validator.node_a, &rdf::type_(), &class_a);
+    graph.clone_insert(&validator.node_a, &rdf::type_(), &class_b);
+
+    let types: HashSet<&Node> = graph.types_of(&validator.node_a).collect();
+    assert_eq!(2, types.len());
+    assert!(types.contains(&class_a));
+    assert!(types.contains(&class_b));
+
+    assert_eq!(0, graph.types_of(&validator.node_b).count());
+    assert_eq!(0, graph.types_of(&class_a).count());
+}
+
+#[test]
+fn graph_builder() {
+    let nodes: Vec<Node> = (0..20)
+        .map(|i| Node::from(format!("urn:arrdf:tests:node:{}", i).as_str()))
+        .collect();
+    let triples: Vec<(Node, Node, Node)> = (0..2000)
+        .map(|i| {
+            (
+                nodes[i % 20].clone(),
+                nodes[i % 7].clone(),
+                nodes[i % 13].clone(),
+            )
+        })
+        .collect();
+
+    let mut graph = HashGraph::new();
+    for (s, p, o) in triples.iter() {
+        graph.clone_insert(s, p, o);
+    }
+
+    let mut builder = GraphBuilder::with_capacity(triples.len());
+    assert!(builder.is_empty());
+    builder.extend(triples);
+    assert_eq!(2000, builder.len());
+
+    let built = builder.build();
+    assert_eq!(graph.len(), built.len());
+    assert_eq!(graph, built);
+}
+
+#[test]
+fn modify_subject() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let (node_a, node_b, node_c) = (&validator.node_a, &validator.node_b, &validator.node_c);
+    let (predicate_a, predicate_b) = (&validator.predicate_a, &validator.predicate_b);
+    graph.clone_insert(node_a, predicate_b, node_c);
+
+    // Swap the objects of the two predicates of node A.
+    graph.modify_subject(node_a.clone(), |relationships| {
+        let objects_a = relationships.remove(predicate_a).unwrap_or_default();
+        let objects_b = relationships.remove(predicate_b).unwrap_or_default();
+        relationships.insert(predicate_a.clone(), objects_b);
+        relationships.insert(predicate_b.clone(), objects_a);
+    });
+    assert_eq!(4, graph.len());
+    assert!(graph.contains(node_a, predicate_a, node_c));
+    assert!(graph.contains(node_a, predicate_b, node_b));
+    assert!(!graph.contains(node_a, predicate_a, node_b));
+    assert!(!graph.contains(node_a, predicate_b, node_c));
+
+    // Subjects that are left empty are pruned.
+    let node_d = Node::from("urn:arrdf:tests:node:d");
+    graph.modify_subject(node_d.clone(), |relationships| {
+        relationships.insert(predicate_a.clone(), HashSet::new());
+    });
+    assert!(!graph.nodes.contains_key(&node_d));
+    graph.modify_subject(node_a.clone(), |relationships| relationships.clear());
+    assert!(!graph.nodes.contains_key(node_a));
+    assert_eq!(2, graph.len());
+}
+
+#[test]
+fn is_isomorphic() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let graph = validator.graph;
+
+    // Replace the blank node C with a freshly allocated one.
+    let (node_c, fresh_c) = (&validator.node_c, Node::blank());
+    let relabel = |node: &Node| {
+        if node == node_c {
+            fresh_c.clone()
+        } else {
+            node.clone()
+        }
+    };
+    let relabeled: HashGraph = graph
+        .iter()
+        .map(|(s, p, o)| (relabel(s), p.clone(), relabel(o)))
+        .collect();
+    assert_ne!(graph, relabeled);
+    assert!(graph.is_isomorphic(&relabeled));
+    assert!(relabeled.is_isomorphic(&graph));
+
+    // Ground triples have to match exactly.
+    let mut changed = relabeled.clone();
+    changed.remove(&validator.node_a, &validator.predicate_a, &validator.node_b);
+    changed.clone_insert(&validator.node_a, &validator.predicate_b, &validator.node_b);
+    assert!(!graph.is_isomorphic(&changed));
+}
+
+#[test]
+fn into_iter_borrowed() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut triples: Vec<(&Node, &Node, &Node)> = Vec::new();
+    for triple in &validator.graph {
+        triples.push(triple);
+    }
+    assert_eq!(3, triples.len());
+    for triple in validator.graph.iter() {
+        assert!(triples.contains(&triple));
+    }
+}
+
+#[test]
+fn reserve() {
+    assert!(HashGraph::with_triple_capacity(100).nodes.capacity() >= 100);
+
+    // Every node of the validator graph is the subject of one triple, so one subject is expected
+    // per new triple.
+    let mut validator = crate::Validator::new(HashGraph::new());
+    validator.graph.reserve(10);
+    assert!(validator.graph.nodes.capacity() >= 13);
+    validator.validate();
+
+    // Two triples per subject.
+    let mut graph = HashGraph::new();
+    graph.clone_insert(&validator.node_a, &validator.predicate_a, &validator.node_b);
+    graph.clone_insert(&validator.node_a, &validator.predicate_b, &validator.node_b);
+    graph.reserve(100);
+    assert!(graph.nodes.capacity() >= 51);
+
+    let triples: Vec<(Node, Node, Node)> = (0..50)
+        .map(|i| {
+            (
+                Node::from(format!("urn:arrdf:tests:subject:{}", i).as_str()),
+                validator.predicate_a.clone(),
+                validator.node_a.clone(),
+            )
+        })
+        .collect();
+    let graph: HashGraph = triples.into_iter().collect();
+    assert_eq!(50, graph.len());
+    assert!(graph.nodes.capacity() >= 50);
+}
+
+#[test]
+fn remove_prunes_empty_entries() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph.clone();
+    graph.clone_insert(&validator.node_a, &validator.predicate_b, &validator.node_c);
+
+    graph.remove(&validator.node_a, &validator.predicate_a, &validator.node_b);
+    let relationships = &graph.nodes[&validator.node_a];
+    assert!(!relationships.contains_key(&validator.predicate_a));
+    assert_eq!(1, relationships.len());
+
+    graph.remove(&validator.node_a, &validator.predicate_b, &validator.node_c);
+    assert!(!graph.nodes.contains_key(&validator.node_a));
+    assert_eq!(0, graph.relationships(&validator.node_a).count());
+    assert!(graph
+        .iter()
+        .all(|(subject, _, _)| subject != &validator.node_a));
+
+    // Removing triples that aren't contained leaves the graph unchanged.
+    graph.remove(&validator.node_a, &validator.predicate_a, &validator.node_b);
+    graph.remove(&validator.node_b, &validator.predicate_a, &validator.node_c);
+    assert_eq!(2, graph.nodes.len());
+    assert_eq!(2, graph.len());
+}
+
+#[test]
+fn shrink_to_fit() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = HashGraph::new();
+    let triples: Vec<(Node, Node, Node)> = (0..1000)
+        .map(|i| {
+            (
+                Node::from(format!("urn:arrdf:tests:subject:{}", i % 100).as_str()),
+                Node::from(format!("urn:arrdf:tests:predicate:{}", i % 7).as_str()),
+                Node::from(format!("urn:arrdf:tests:object:{}", i).as_str()),
+            )
+        })
+        .collect();
+    graph.extend(triples.iter().cloned());
+    graph.union_with(&validator.graph);
+    assert_eq!(1003, graph.len());
+
+    for (s, p, o) in triples.iter() {
+        graph.remove(s, p, o);
+    }
+    graph.shrink_to_fit();
+    assert_eq!(3, graph.nodes.len());
+    assert!(graph.nodes.capacity() < 100);
+    assert!(graph
+        .nodes
+        .values()
+        .all(|relationships| relationships.values().all(|objects| !objects.is_empty())));
+
+    graph.retain(|_, _, _| false);
+    graph.shrink_to_fit();
+    assert!(graph.nodes.is_empty());
+}
+
+#[test]
+fn inherent_clear() {
+    let mut graph = HashGraph::with_max_literal_len(8);
+    assert!(HashGraph::is_empty(&graph));
+    let validator = crate::Validator::new(HashGraph::new());
+    graph.union_with(&validator.graph);
+    assert!(!HashGraph::is_empty(&graph));
+
+    HashGraph::clear(&mut graph);
+    assert!(HashGraph::is_empty(&graph));
+    assert_eq!(0, graph.nodes.len());
+    assert_eq!(Some(8), graph.max_literal_len());
+}
+
+#[test]
+fn nodes() {
+    let mut validator = crate::Validator::new(HashGraph::new());
+    let nodes: HashSet<&Node> = validator.graph.nodes().collect();
+    assert_eq!(6, validator.graph.nodes().count());
+    for node in &[
+        &validator.node_a,
+        &validator.node_b,
+        &validator.node_c,
+        &validator.predicate_a,
+        &validator.predicate_b,
+        &validator.predicate_c,
+    ] {
+        assert!(nodes.contains(node));
+    }
+
+    // A node in several positions is only yielded once.
+    validator.graph.clone_insert(
+        &validator.predicate_a,
+        &validator.predicate_a,
+        &validator.predicate_a,
+    );
+    assert_eq!(6, validator.graph.nodes().count());
+    validator.graph.clear();
+    assert_eq!(0, validator.graph.nodes().count());
+}
+
+#[test]
+fn entry() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = HashGraph::with_max_literal_len(5);
+    graph.merge(&validator.graph);
+
+    let mut entry = graph.entry(validator.node_a.clone(), validator.predicate_a.clone());
+    assert!(entry.contains(&validator.node_b));
+    assert!(!entry.insert(validator.node_b.clone()));
+    assert!(entry.insert(validator.node_c.clone()));
+    assert_eq!(
+        Err(LiteralLengthError { len: 11, limit: 5 }),
+        entry.try_insert(Node::from("Hello World"))
+    );
+    assert!(!entry.insert(Node::from("Hello World")));
+    assert_eq!(2, entry.len());
+    assert_eq!(4, graph.len());
+    assert!(graph.contains(&validator.node_a, &validator.predicate_a, &validator.node_c));
+
+    // An entry without objects doesn't add triples.
+    let entry = graph.entry(validator.node_b.clone(), validator.predicate_a.clone());
+    assert!(entry.is_empty());
+    assert_eq!(4, graph.len());
+    assert!(!graph.contains_subject_predicate(&validator.node_b, &validator.predicate_a));
+
+    // Neither does an entry whose only insertion was rejected, and both keep the graphs equal.
+    let mut other = HashGraph::with_max_literal_len(5);
+    other
+        .entry(validator.node_a.clone(), validator.predicate_b.clone())
+        .insert(Node::from("Hello World"));
+    assert_eq!(HashGraph::new(), other);
+    assert_eq!(other, HashGraph::new());
+    let mut empty = HashGraph::new();
+    empty.entry(validator.node_a.clone(), validator.predicate_a.clone());
+    assert_eq!(HashGraph::new(), empty);
+    assert_ne!(validator.graph, empty);
+}
@@ -0,0 +1,298 @@
+use crate::node::{has_datatype, FLOAT_DATATYPES, INTEGER_DATATYPES};
+use crate::vocab::xsd;
+#[cfg(test)]
+use crate::Graph;
+use crate::{HashGraph, Node, NodeKind};
+#[cfg(test)]
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+
+/// Return the canonical form of an `xsd:decimal` lexical form, or `None` if it's invalid.
+fn canonical_decimal(lexical_form: &str) -> Option<String> {
+    let (negative, unsigned) = match lexical_form.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (
+            false,
+            lexical_form.strip_prefix('+').unwrap_or(lexical_form),
+        ),
+    };
+    let mut parts = unsigned.splitn(2, '.');
+    let integer_part = parts.next().unwrap_or("");
+    let fraction_part = parts.next().unwrap_or("");
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if !is_digits(integer_part)
+        || !is_digits(fraction_part)
+        || integer_part.len() + fraction_part.len() == 0
+    {
+        return None;
+    }
+
+    let integer_part = match integer_part.trim_start_matches('0') {
+        "" => "0",
+        integer_part => integer_part,
+    };
+    let fraction_part = fraction_part.trim_end_matches('0');
+    let mut canonical = String::new();
+    if negative && (integer_part != "0" || !fraction_part.is_empty()) {
+        canonical.push('-');
+    }
+    canonical.push_str(integer_part);
+    if !fraction_part.is_empty() {
+        canonical.push('.');
+        canonical.push_str(fraction_part);
+    }
+    Some(canonical)
+}
+
+/// Check whether a lexical form matches the grammar of `xsd:double` and `xsd:float`.
+///
+/// Rust's float parser is more lenient and also accepts forms like `inf` or `infinity`, so this
+/// has to be checked before parsing.
+fn is_floating_point(lexical_form: &str) -> bool {
+    if let "INF" | "+INF" | "-INF" | "NaN" = lexical_form {
+        return true;
+    }
+    let unsigned = lexical_form
+        .strip_prefix(['+', '-'])
+        .unwrap_or(lexical_form);
+    let mut parts = unsigned.splitn(2, ['e', 'E']);
+    let mantissa = parts.next().unwrap_or("");
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    let mut mantissa_parts = mantissa.splitn(2, '.');
+    let integer_part = mantissa_parts.next().unwrap_or("");
+    let fraction_part = mantissa_parts.next().unwrap_or("");
+    if !is_digits(integer_part)
+        || !is_digits(fraction_part)
+        || integer_part.len() + fraction_part.len() == 0
+    {
+        return false;
+    }
+    match parts.next() {
+        Some(exponent) => {
+            let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            !exponent.is_empty() && is_digits(exponent)
+        }
+        None => true,
+    }
+}
+
+/// Return the canonical form of an `xsd:double` or `xsd:float` value, given its scientific notation.
+fn canonical_floating_point(scientific: String) -> String {
+    match scientific.as_str() {
+        "inf" => "INF".to_owned(),
+        "-inf" => "-INF".to_owned(),
+        "NaN" => scientific,
+        // The canonical mantissa always contains a decimal point.
+        _ if !scientific.contains('.') => scientific.replacen('E', ".0E", 1),
+        _ => scientific,
+    }
+}
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Trim literal objects and collapse their internal whitespace into single spaces.
+    ///
+    /// For typed and language-tagged literals, only the lexical form is normalized. Triples that
+    /// become equal are merged.
+    /// IRIs, blank nodes and subjects are left untouched, and so are literals that would be read as an
+    /// IRI after the normalization.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let mut graph = graph! {
+    ///     "urn:alice" -> "urn:name" -> "  Alice \n Smith ";
+    ///     "urn:alice" -> "urn:name" -> "Alice Smith";
+    /// };
+    /// graph.normalize_literal_whitespace();
+    ///
+    /// assert_eq!(1, graph.len());
+    /// assert!(graph.contains(&Node::from("urn:alice"), &Node::from("urn:name"), &Node::from("Alice Smith")));
+    /// ```
+    pub fn normalize_literal_whitespace(&mut self) {
+        let normalize = |node: Node| -> Node {
+            if node.kind() != NodeKind::Literal {
+                return node;
+            }
+            let lexical_form = node.lexical_form().split_whitespace().collect::<Vec<_>>();
+            let normalized = match (node.datatype(), node.language()) {
+                (Some(datatype), _) => Node::typed_literal(&lexical_form.join(" "), datatype),
+                (None, Some(language)) => Node::lang_literal(&lexical_form.join(" "), language),
+                (None, None) => Node::plain_literal(&lexical_form.join(" ")),
+            };
+            if normalized.kind() == NodeKind::Literal {
+                normalized
+            } else {
+                node
+            }
+        };
+
+        for relationships in self.nodes.values_mut() {
+            for objects in relationships.values_mut() {
+                *objects = objects.drain().map(normalize).collect();
+            }
+        }
+    }
+
+    /// Rewrite numeric literal objects to the canonical lexical form of their datatype.
+    ///
+    /// Literals with the datatypes `xsd:integer`, `xsd:long`, `xsd:int`, `xsd:nonNegativeInteger`,
+    /// `xsd:decimal`, `xsd:double` and `xsd:float` are rewritten to the canonical representations
+    /// of [XML Schema 1.1](https://www.w3.org/TR/xmlschema11-2/), e.g. `"01.50"^^xsd:decimal` becomes
+    /// `"1.5"^^xsd:decimal` and `"150"^^xsd:double` becomes `"1.5E2"^^xsd:double`. Triples that become
+    /// equal are merged. Literals with invalid lexical forms or other datatypes are left untouched.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node, vocab::xsd};
+    ///
+    /// let mut graph = graph! {
+    ///     "urn:pi" -> "urn:value" -> (Node::typed_literal("3.140", &xsd::decimal()));
+    ///     "urn:pi" -> "urn:value" -> (Node::typed_literal("+3.14", &xsd::decimal()));
+    /// };
+    /// graph.normalize_numeric_literals();
+    ///
+    /// assert_eq!(1, graph.len());
+    /// assert!(graph.contains(
+    ///     &Node::from("urn:pi"),
+    ///     &Node::from("urn:value"),
+    ///     &Node::typed_literal("3.14", &xsd::decimal())
+    /// ));
+    /// ```
+    pub fn normalize_numeric_literals(&mut self) {
+        let (decimal, float) = (xsd::decimal(), xsd::float());
+        let normalize = |node: Node| -> Node {
+            let datatype = match node.datatype() {
+                Some(datatype) => datatype,
+                None => return node,
+            };
+            let lexical_form = node.lexical_form();
+            let canonical = if has_datatype(datatype, INTEGER_DATATYPES) {
+                if lexical_form.contains('.') {
+                    None
+                } else {
+                    canonical_decimal(lexical_form)
+                }
+            } else if has_datatype(datatype, FLOAT_DATATYPES) {
+                if datatype == decimal.as_str() {
+                    canonical_decimal(lexical_form)
+                } else if !is_floating_point(lexical_form) {
+                    None
+                } else if datatype == float.as_str() {
+                    lexical_form
+                        .parse::<f32>()
+                        .ok()
+                        .map(|value| canonical_floating_point(format!("{:E}", value)))
+                } else {
+                    // The remaining floating-point datatype is `xsd:double`.
+                    lexical_form
+                        .parse::<f64>()
+                        .ok()
+                        .map(|value| canonical_floating_point(format!("{:E}", value)))
+                }
+            } else {
+                None
+            };
+            match canonical {
+                Some(canonical) => Node::typed_literal(&canonical, datatype),
+                None => node,
+            }
+        };
+
+        for relationships in self.nodes.values_mut() {
+            for objects in relationships.values_mut() {
+                *objects = objects.drain().map(normalize).collect();
+            }
+        }
+    }
+}
+
+#[test]
+fn normalize_literal_whitespace() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let (node_a, predicate_a) = (&validator.node_a, &validator.predicate_a);
+    graph.clone_insert(node_a, predicate_a, &Node::from("  hello  "));
+    graph.clone_insert(node_a, predicate_a, &Node::from("hello"));
+    graph.clone_insert(node_a, predicate_a, &Node::from("hello \t\n world"));
+    graph.clone_insert(
+        node_a,
+        predicate_a,
+        &Node::typed_literal(" 42 ", &xsd::integer()),
+    );
+    graph.clone_insert(node_a, predicate_a, &Node::from(" urn:not-an-iri "));
+    graph.clone_insert(
+        node_a,
+        predicate_a,
+        &Node::lang_literal(" chat  noir ", "fr"),
+    );
+
+    graph.normalize_literal_whitespace();
+    assert_eq!(8, graph.len());
+    assert!(graph.contains(node_a, predicate_a, &Node::lang_literal("chat noir", "fr")));
+    assert!(graph.contains(node_a, predicate_a, &Node::from("hello")));
+    assert!(graph.contains(node_a, predicate_a, &Node::from("hello world")));
+    assert!(graph.contains(
+        node_a,
+        predicate_a,
+        &Node::typed_literal("42", &xsd::integer())
+    ));
+    assert!(graph.contains(node_a, predicate_a, &Node::from(" urn:not-an-iri ")));
+    assert!(graph.contains(node_a, predicate_a, &validator.node_b));
+    assert!(graph.contains(&validator.node_c, &validator.predicate_c, node_a));
+}
+
+#[test]
+fn normalize_numeric_literals() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let (node_a, predicate_a) = (&validator.node_a, &validator.predicate_a);
+    let literals = [
+        ("1.0", xsd::decimal()),
+        ("01.00", xsd::decimal()),
+        ("-0.50", xsd::decimal()),
+        ("-0.0", xsd::decimal()),
+        ("+042", xsd::integer()),
+        ("1.5", xsd::integer()),
+        ("150", xsd::double()),
+        ("1e0", xsd::double()),
+        ("-INF", xsd::float()),
+        ("infinity", xsd::double()),
+        ("nan", xsd::float()),
+        ("1e", xsd::double()),
+        ("abc", xsd::decimal()),
+    ];
+    for (lexical_form, datatype) in literals.iter() {
+        graph.clone_insert(
+            node_a,
+            predicate_a,
+            &Node::typed_literal(lexical_form, datatype),
+        );
+    }
+    graph.clone_insert(node_a, predicate_a, &Node::from("01.00"));
+
+    graph.normalize_numeric_literals();
+    let objects: HashSet<&Node> = graph
+        .objects(node_a, predicate_a)
+        .map(|(_, _, object)| object)
+        .collect();
+    let expected: Vec<Node> = vec![
+        validator.node_b.clone(),
+        Node::typed_literal("1", &xsd::decimal()),
+        Node::typed_literal("-0.5", &xsd::decimal()),
+        Node::typed_literal("0", &xsd::decimal()),
+        Node::typed_literal("42", &xsd::integer()),
+        Node::typed_literal("1.5", &xsd::integer()),
+        Node::typed_literal("1.5E2", &xsd::double()),
+        Node::typed_literal("1.0E0", &xsd::double()),
+        Node::typed_literal("-INF", &xsd::float()),
+        Node::typed_literal("abc", &xsd::decimal()),
+        Node::typed_literal("infinity", &xsd::double()),
+        Node::typed_literal("nan", &xsd::float()),
+        Node::typed_literal("1e", &xsd::double()),
+        Node::from("01.00"),
+    ];
+    assert_eq!(expected.iter().collect::<HashSet<&Node>>(), objects);
+}
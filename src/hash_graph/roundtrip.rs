@@ -0,0 +1,147 @@
+use crate::ntriples::{parse_ntriples, write_ntriples};
+#[cfg(test)]
+use crate::Graph;
+use crate::{iso, set, HashGraph, Node, ParseError};
+use std::fmt;
+use std::hash::BuildHasher;
+use std::io;
+
+/// The error returned by [`HashGraph::assert_serialization_roundtrip`](struct.HashGraph.html#method.assert_serialization_roundtrip).
+///
+/// This type is only available with the `ntriples` feature.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RoundtripError {
+    /// The serialized graph couldn't be parsed.
+    Parse(ParseError),
+    /// The parsed graph isn't isomorphic to the original graph.
+    ///
+    /// Blank nodes of the original and the parsed graph are never equal, so triples with blank nodes
+    /// are always contained in the diff.
+    NotIsomorphic {
+        /// The triples of the original graph that aren't in the parsed graph.
+        missing: Vec<(Node, Node, Node)>,
+        /// The triples of the parsed graph that aren't in the original graph.
+        unexpected: Vec<(Node, Node, Node)>,
+    },
+}
+
+impl fmt::Display for RoundtripError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoundtripError::Parse(error) => {
+                write!(f, "Serialized graph can't be parsed: {}", error)
+            }
+            RoundtripError::NotIsomorphic {
+                missing,
+                unexpected,
+            } => write!(
+                f,
+                "Parsed graph differs from the original: {} triples missing, {} unexpected",
+                missing.len(),
+                unexpected.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RoundtripError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RoundtripError::Parse(error) => Some(error),
+            RoundtripError::NotIsomorphic { .. } => None,
+        }
+    }
+}
+
+fn clone_triples<'a>(
+    triples: impl Iterator<Item = (&'a Node, &'a Node, &'a Node)>,
+) -> Vec<(Node, Node, Node)> {
+    triples
+        .map(|(s, p, o)| (s.clone(), p.clone(), o.clone()))
+        .collect()
+}
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Check that the graph survives a round trip through N-Triples.
+    ///
+    /// The graph is serialized with [`write_ntriples`](ntriples/fn.write_ntriples.html), parsed
+    /// again with [`parse_ntriples`](ntriples/fn.parse_ntriples.html) and the result is compared
+    /// with the original. Since blank nodes get new identities, the graphs only need to be
+    /// isomorphic. This is meant as a helper for property tests. This method is only available
+    /// with the `ntriples` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let someone = Node::blank();
+    /// let graph = graph! {
+    ///     "http://example.org/alice" -> "http://example.org/knows" -> (someone);
+    ///     (someone) -> "http://example.org/name" -> "Bob";
+    /// };
+    ///
+    /// assert!(graph.assert_serialization_roundtrip().is_ok());
+    /// ```
+    #[cfg(feature = "ntriples")]
+    pub fn assert_serialization_roundtrip(&self) -> Result<(), RoundtripError> {
+        self.check_roundtrip(write_ntriples)
+    }
+
+    #[cfg(feature = "ntriples")]
+    fn check_roundtrip<F>(&self, serialize: F) -> Result<(), RoundtripError>
+    where
+        F: FnOnce(&HashGraph<S>, &mut Vec<u8>) -> io::Result<()>,
+    {
+        let mut document: Vec<u8> = Vec::new();
+        serialize(self, &mut document).expect("Writing to a vector can't fail");
+        let parsed =
+            parse_ntriples(&String::from_utf8_lossy(&document)).map_err(RoundtripError::Parse)?;
+
+        if iso::is_isomorphic(self, &parsed) {
+            Ok(())
+        } else {
+            Err(RoundtripError::NotIsomorphic {
+                missing: clone_triples(set::difference(self, &parsed)),
+                unexpected: clone_triples(set::difference(&parsed, self)),
+            })
+        }
+    }
+}
+
+#[test]
+fn serialization_roundtrip() {
+    use std::io::Write;
+
+    let validator = crate::Validator::new(HashGraph::new());
+    let graph = validator.graph;
+    assert!(validator.node_c.is_blank());
+    assert_eq!(Ok(()), graph.assert_serialization_roundtrip());
+
+    // A serializer that drops a triple produces a graph that isn't isomorphic.
+    let error = graph
+        .check_roundtrip(|graph, document| {
+            let triples: Vec<_> = graph.iter().skip(1).collect();
+            write_ntriples(&triples.into_iter().collect::<HashGraph>(), document)
+        })
+        .unwrap_err();
+    match error {
+        RoundtripError::NotIsomorphic {
+            missing,
+            unexpected,
+        } => {
+            // Triples with blank nodes are always part of the diff.
+            assert_eq!(missing.len(), unexpected.len() + 1);
+        }
+        error => panic!("Unexpected error {:?}", error),
+    }
+
+    // A serializer that emits garbage produces a parse error.
+    let error = graph
+        .check_roundtrip(|_, document| writeln!(document, "<urn:a> <urn:b> ."))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        RoundtripError::Parse(ParseError { line: 1, .. })
+    ));
+}
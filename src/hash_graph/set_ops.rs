@@ -0,0 +1,238 @@
+use crate::diff::GraphDiff;
+use crate::{set, Graph, HashGraph};
+use std::hash::BuildHasher;
+
+#[cfg(test)]
+use super::set_operation_operands;
+
+/// Return the union of two graphs as a new graph.
+///
+/// This collects the triples of [`set::union`](set/fn.union.html), which produces every triple only once.
+impl<S: BuildHasher + Default> std::ops::BitOr<&HashGraph<S>> for &HashGraph<S> {
+    type Output = HashGraph<S>;
+
+    fn bitor(self, rhs: &HashGraph<S>) -> HashGraph<S> {
+        set::union(self, rhs).collect()
+    }
+}
+
+/// Return the intersection of two graphs as a new graph.
+///
+/// This collects the triples of [`set::intersection`](set/fn.intersection.html), which produces every triple only once.
+impl<S: BuildHasher + Default> std::ops::BitAnd<&HashGraph<S>> for &HashGraph<S> {
+    type Output = HashGraph<S>;
+
+    fn bitand(self, rhs: &HashGraph<S>) -> HashGraph<S> {
+        set::intersection(self, rhs).collect()
+    }
+}
+
+/// Return the difference of two graphs as a new graph.
+///
+/// This collects the triples of [`set::difference`](set/fn.difference.html), which produces every triple only once.
+impl<S: BuildHasher + Default> std::ops::Sub<&HashGraph<S>> for &HashGraph<S> {
+    type Output = HashGraph<S>;
+
+    fn sub(self, rhs: &HashGraph<S>) -> HashGraph<S> {
+        set::difference(self, rhs).collect()
+    }
+}
+
+/// Return the symmetric difference of two graphs as a new graph.
+///
+/// This collects the triples of [`set::symmetric_difference`](set/fn.symmetric_difference.html), which produces every triple only once.
+impl<S: BuildHasher + Default> std::ops::BitXor<&HashGraph<S>> for &HashGraph<S> {
+    type Output = HashGraph<S>;
+
+    fn bitxor(self, rhs: &HashGraph<S>) -> HashGraph<S> {
+        set::symmetric_difference(self, rhs).collect()
+    }
+}
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Insert all triples of `other` into this graph.
+    ///
+    /// This is the in-place version of [`set::union`](set/fn.union.html).
+    pub fn union_with(&mut self, other: &HashGraph<S>) {
+        self.clone_extend(other.iter());
+    }
+
+    /// Remove all triples from this graph that aren't contained in `other`.
+    ///
+    /// This is the in-place version of [`set::intersection`](set/fn.intersection.html).
+    pub fn intersect_with(&mut self, other: &HashGraph<S>) {
+        self.retain(|s, p, o| other.contains(s, p, o));
+    }
+
+    /// Remove all triples from this graph that are contained in `other`.
+    ///
+    /// This is the in-place version of [`set::difference`](set/fn.difference.html).
+    pub fn difference_with(&mut self, other: &HashGraph<S>) {
+        self.remove_all(other.iter());
+    }
+
+    /// Return the number of triples that are contained in both this graph and `other`.
+    ///
+    /// This is the same as `set::intersection(self, other).count()`. It takes O(len(self)) time and
+    /// O(1) extra space, since no graph is built for the result.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::graph;
+    ///
+    /// let old = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:carol";
+    /// };
+    /// let new = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:dave";
+    ///     "urn:alice" -> "urn:knows" -> "urn:eve";
+    /// };
+    ///
+    /// assert_eq!(1, old.intersection_size(&new));
+    /// assert_eq!(1, old.difference_size(&new));
+    /// assert_eq!(2, new.difference_size(&old));
+    /// ```
+    pub fn intersection_size(&self, other: &HashGraph<S>) -> usize {
+        self.iter()
+            .filter(|(s, p, o)| other.contains(s, p, o))
+            .count()
+    }
+
+    /// Return the number of triples of this graph that aren't contained in `other`.
+    ///
+    /// This is the same as `set::difference(self, other).count()`. It takes O(len(self)) time and
+    /// O(1) extra space, since no graph is built for the result.
+    pub fn difference_size(&self, other: &HashGraph<S>) -> usize {
+        self.iter()
+            .filter(|(s, p, o)| !other.contains(s, p, o))
+            .count()
+    }
+
+    /// Return the patch that turns this graph into `other`.
+    ///
+    /// The [`added`](diff/struct.GraphDiff.html#structfield.added) triples are contained in `other`,
+    /// but not in this graph, and the [`removed`](diff/struct.GraphDiff.html#structfield.removed)
+    /// triples are contained in this graph, but not in `other`. This is the same as
+    /// [`GraphDiff::between(self, other)`](diff/struct.GraphDiff.html#method.between).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph};
+    ///
+    /// let server = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:carol";
+    /// };
+    /// let mut client = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:dave";
+    /// };
+    ///
+    /// let patch = client.diff(&server);
+    /// assert_eq!(1, patch.added.len());
+    /// assert_eq!(1, patch.removed.len());
+    ///
+    /// client.apply(&patch);
+    /// assert_eq!(server, client);
+    /// ```
+    pub fn diff(&self, other: &HashGraph<S>) -> GraphDiff {
+        GraphDiff::between(self, other)
+    }
+
+    /// Apply a patch by removing its removed triples and then inserting its added triples.
+    ///
+    /// Applying [`self.diff(other)`](#method.diff) to this graph makes it equal to `other`.
+    /// Removing first means that a triple that is both removed and added by a patch ends up in
+    /// the graph.
+    pub fn apply(&mut self, patch: &GraphDiff) {
+        self.remove_all(patch.removed.iter());
+        self.clone_extend(patch.added.iter());
+    }
+}
+
+#[test]
+fn union_with() {
+    let (mut validator, other) = set_operation_operands();
+    validator.graph.union_with(&other);
+
+    let graph = &validator.graph;
+    assert_eq!(4, graph.len());
+    assert!(graph.contains(&validator.node_a, &validator.predicate_a, &validator.node_b));
+    assert!(graph.contains(&validator.node_b, &validator.predicate_b, &validator.node_c));
+    assert!(graph.contains(&validator.node_c, &validator.predicate_c, &validator.node_a));
+    assert!(graph.contains(&validator.node_b, &validator.predicate_a, &validator.node_a));
+}
+
+#[test]
+fn intersect_with() {
+    let (mut validator, other) = set_operation_operands();
+    validator.graph.intersect_with(&other);
+
+    let graph = &validator.graph;
+    assert_eq!(1, graph.len());
+    assert!(graph.contains(&validator.node_a, &validator.predicate_a, &validator.node_b));
+}
+
+#[test]
+fn difference_with() {
+    let (mut validator, other) = set_operation_operands();
+    validator.graph.difference_with(&other);
+
+    let graph = &validator.graph;
+    assert_eq!(2, graph.len());
+    assert!(graph.contains(&validator.node_b, &validator.predicate_b, &validator.node_c));
+    assert!(graph.contains(&validator.node_c, &validator.predicate_c, &validator.node_a));
+}
+
+#[test]
+fn operators() {
+    let (validator, other) = set_operation_operands();
+    let graph = &validator.graph;
+
+    assert_eq!(
+        set::union(graph, &other).collect::<HashGraph>(),
+        graph | &other
+    );
+    assert_eq!(
+        set::intersection(graph, &other).collect::<HashGraph>(),
+        graph & &other
+    );
+    assert_eq!(
+        set::difference(graph, &other).collect::<HashGraph>(),
+        graph - &other
+    );
+    assert_eq!(
+        set::symmetric_difference(graph, &other).collect::<HashGraph>(),
+        graph ^ &other
+    );
+
+    assert_eq!(4, (graph | &other).len());
+    assert_eq!(1, (graph & &other).len());
+    assert_eq!(2, (graph - &other).len());
+    assert_eq!(3, (graph ^ &other).len());
+}
+
+#[test]
+fn set_sizes() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let graph = &validator.graph;
+    let mut other = graph.clone();
+    other.remove(&validator.node_a, &validator.predicate_a, &validator.node_b);
+    other.clone_insert(&validator.node_b, &validator.predicate_a, &validator.node_a);
+
+    assert_eq!(2, graph.intersection_size(&other));
+    assert_eq!(1, graph.difference_size(&other));
+    assert_eq!(1, other.difference_size(graph));
+    assert_eq!(3, graph.intersection_size(graph));
+    assert_eq!(0, graph.difference_size(graph));
+    assert_eq!(0, HashGraph::new().intersection_size(graph));
+    assert_eq!(3, graph.difference_size(&HashGraph::new()));
+    assert_eq!(
+        crate::set::intersection(graph, &other).count(),
+        graph.intersection_size(&other)
+    );
+}
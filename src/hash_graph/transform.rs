@@ -0,0 +1,528 @@
+use crate::vocab::{rdf, rdfs};
+use crate::{Graph, HashGraph, Node, NodeKind};
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+
+fn skolem_prefix(base_iri: &str) -> String {
+    format!("{}/genid/", base_iri.trim_end_matches('/'))
+}
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Add all triples that are entailed by the RDFS vocabulary in the graph.
+    ///
+    /// The following [RDFS entailment rules](https://www.w3.org/TR/rdf11-mt/#patterns-of-rdfs-entailment-informative)
+    /// are applied until no new triples are entailed:
+    ///
+    /// * `rdfs2` and `rdfs3`: The subjects of a property are instances of its `rdfs:domain` and the
+    ///   objects are instances of its `rdfs:range`. Literal objects don't get types.
+    /// * `rdfs5` and `rdfs11`: `rdfs:subPropertyOf` and `rdfs:subClassOf` are transitive.
+    /// * `rdfs7`: Triples with a property also hold for its super-properties.
+    /// * `rdfs9`: Instances of a class are instances of its super-classes.
+    ///
+    /// The remaining rules, which state that everything is an `rdfs:Resource`, that every property
+    /// and class is a sub-property or sub-class of itself, and the rules for datatypes and container
+    /// memberships, aren't applied since they mostly add noise.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node, vocab::{rdf, rdfs}};
+    ///
+    /// let mut graph = graph! {
+    ///     "urn:Cat" -> (rdfs::sub_class_of()) -> "urn:Mammal";
+    ///     "urn:Mammal" -> (rdfs::sub_class_of()) -> "urn:Animal";
+    ///     "urn:tom" -> (rdf::type_()) -> "urn:Cat";
+    /// };
+    /// graph.materialize_rdfs();
+    ///
+    /// assert!(graph.contains(&Node::from("urn:tom"), &rdf::type_(), &Node::from("urn:Animal")));
+    /// ```
+    pub fn materialize_rdfs(&mut self) {
+        let type_ = rdf::type_();
+        let sub_class_of = rdfs::sub_class_of();
+        let sub_property_of = rdfs::sub_property_of();
+        let domain = rdfs::domain();
+        let range = rdfs::range();
+
+        loop {
+            let entailed: Vec<(Node, Node, Node)> = {
+                let index = |predicate: &Node| -> HashMap<&Node, Vec<&Node>> {
+                    let mut index: HashMap<&Node, Vec<&Node>> = HashMap::new();
+                    for (subject, relationships) in self.nodes.iter() {
+                        if let Some(objects) = relationships.get(predicate) {
+                            index.entry(subject).or_default().extend(objects);
+                        }
+                    }
+                    index
+                };
+                let super_classes = index(&sub_class_of);
+                let super_properties = index(&sub_property_of);
+                let domains = index(&domain);
+                let ranges = index(&range);
+                let lookup = |index: &HashMap<&Node, Vec<&Node>>, node: &Node| -> Vec<Node> {
+                    index
+                        .get(node)
+                        .into_iter()
+                        .flatten()
+                        .map(|node| (*node).clone())
+                        .collect()
+                };
+
+                let mut entailed = Vec::new();
+                for (s, p, o) in self.iter() {
+                    for super_property in lookup(&super_properties, p) {
+                        entailed.push((s.clone(), super_property, o.clone()));
+                    }
+                    for class in lookup(&domains, p) {
+                        entailed.push((s.clone(), type_.clone(), class));
+                    }
+                    if o.kind() != NodeKind::Literal {
+                        for class in lookup(&ranges, p) {
+                            entailed.push((o.clone(), type_.clone(), class));
+                        }
+                    }
+                    if p == &type_ {
+                        for class in lookup(&super_classes, o) {
+                            entailed.push((s.clone(), type_.clone(), class));
+                        }
+                    } else if p == &sub_class_of {
+                        for class in lookup(&super_classes, o) {
+                            entailed.push((s.clone(), sub_class_of.clone(), class));
+                        }
+                    } else if p == &sub_property_of {
+                        for property in lookup(&super_properties, o) {
+                            entailed.push((s.clone(), sub_property_of.clone(), property));
+                        }
+                    }
+                }
+                entailed
+                    .into_iter()
+                    .filter(|(s, p, o)| !self.contains(s, p, o))
+                    .collect()
+            };
+
+            if entailed.is_empty() {
+                break;
+            }
+            self.extend(entailed);
+        }
+    }
+
+    /// Merge all nodes that are declared to be the same.
+    ///
+    /// The triples with the `same_as` predicate, usually `owl:sameAs`, partition the nodes into
+    /// classes of equivalent nodes, where the equivalence is reflexive, symmetric and transitive.
+    /// Every occurrence of a node as a subject or object is replaced by the representative of its
+    /// class, which is the lexicographically smallest IRI or literal in it. Blank nodes are only
+    /// chosen as representatives if the class doesn't contain anything else. Afterwards, the
+    /// `same_as` triples are redundant and removed.
+    ///
+    /// The returned map maps every replaced node to its representative.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let same_as = Node::from("http://www.w3.org/2002/07/owl#sameAs");
+    /// let mut graph = graph! {
+    ///     "urn:b" -> (same_as) -> "urn:a";
+    ///     "urn:b" -> "urn:name" -> "Alice";
+    /// };
+    ///
+    /// let mapping = graph.merge_same_as(&same_as);
+    /// assert_eq!(Node::from("urn:a"), mapping[&Node::from("urn:b")]);
+    /// assert_eq!(1, graph.len());
+    /// assert!(graph.contains(&Node::from("urn:a"), &Node::from("urn:name"), &Node::from("Alice")));
+    /// ```
+    pub fn merge_same_as(&mut self, same_as: &Node) -> HashMap<Node, Node> {
+        let mut neighbors: HashMap<&Node, Vec<&Node>> = HashMap::new();
+        for (subject, relationships) in self.nodes.iter() {
+            for object in relationships.get(same_as).into_iter().flatten() {
+                neighbors.entry(subject).or_default().push(object);
+                neighbors.entry(object).or_default().push(subject);
+            }
+        }
+
+        let mut mapping: HashMap<Node, Node> = HashMap::new();
+        let mut visited: HashSet<&Node> = HashSet::new();
+        for start in neighbors.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut class = vec![*start];
+            let mut stack = vec![*start];
+            while let Some(node) = stack.pop() {
+                for neighbor in neighbors[node].iter() {
+                    if visited.insert(neighbor) {
+                        class.push(neighbor);
+                        stack.push(neighbor);
+                    }
+                }
+            }
+
+            let representative = class
+                .iter()
+                .min_by_key(|node| (node.is_blank(), node.as_str()))
+                .cloned()
+                .unwrap();
+            for node in class {
+                if node != representative {
+                    mapping.insert(node.clone(), representative.clone());
+                }
+            }
+        }
+
+        let nodes = std::mem::take(&mut self.nodes);
+        let merge = |node: Node| mapping.get(&node).cloned().unwrap_or(node);
+        for (subject, relationships) in nodes {
+            for (predicate, objects) in relationships {
+                if &predicate == same_as {
+                    continue;
+                }
+                for object in objects {
+                    self.insert(merge(subject.clone()), predicate.clone(), merge(object));
+                }
+            }
+        }
+        mapping
+    }
+
+    /// Replace every occurrence of the node `from` with `to`, as subject, predicate and object.
+    ///
+    /// The relationships of `from` are moved to `to`, and the predicates and objects are rewritten
+    /// in a single pass over the subjects and their predicates, without looking at the triples one by
+    /// one. If `to` is already in the graph, the triples are merged and duplicates are removed.
+    /// Like [`modify_subject`](#method.modify_subject), this bypasses the literal length limit of
+    /// the graph.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let mut graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:carol" -> "urn:knows" -> "urn:alice";
+    ///     "urn:alicia" -> "urn:knows" -> "urn:bob";
+    /// };
+    /// let (alice, alicia) = (Node::from("urn:alice"), Node::from("urn:alicia"));
+    /// graph.rename_node(&alicia, &alice);
+    ///
+    /// assert_eq!(2, graph.len());
+    /// assert!(graph.contains(&alice, &Node::from("urn:knows"), &Node::from("urn:bob")));
+    /// ```
+    pub fn rename_node(&mut self, from: &Node, to: &Node) {
+        if from == to {
+            return;
+        }
+        if let Some(relationships) = self.nodes.remove(from) {
+            let target = self.nodes.entry(to.clone()).or_default();
+            for (predicate, objects) in relationships {
+                target.entry(predicate).or_default().extend(objects);
+            }
+        }
+        for relationships in self.nodes.values_mut() {
+            if let Some(objects) = relationships.remove(from) {
+                relationships.entry(to.clone()).or_default().extend(objects);
+            }
+            for objects in relationships.values_mut() {
+                if objects.remove(from) {
+                    objects.insert(to.clone());
+                }
+            }
+        }
+    }
+
+    /// Replace all blank nodes with skolem IRIs.
+    ///
+    /// Every blank node is replaced by an IRI of the form `<base_iri>/genid/<n>`, where `n` counts
+    /// the blank nodes, so the same blank node is replaced by the same IRI in all triples. The IRIs
+    /// below `<base_iri>/genid/` should be reserved for skolem IRIs, since they are turned into blank
+    /// nodes again by [`deskolemize`](#method.deskolemize). The returned map maps every blank node
+    /// to its skolem IRI.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let blank = Node::blank();
+    /// let mut graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> (blank);
+    ///     (blank) -> "urn:name" -> "Bob";
+    /// };
+    ///
+    /// let mapping = graph.skolemize("https://example.org");
+    /// let skolem_iri = Node::from("https://example.org/genid/0");
+    /// assert_eq!(skolem_iri, mapping[&blank]);
+    /// assert!(graph.contains(&skolem_iri, &Node::from("urn:name"), &Node::from("Bob")));
+    ///
+    /// graph.deskolemize("https://example.org");
+    /// assert!(graph.iter().all(|(s, _, _)| s.as_str() != skolem_iri.as_str()));
+    /// ```
+    pub fn skolemize(&mut self, base_iri: &str) -> HashMap<Node, Node> {
+        let prefix = skolem_prefix(base_iri);
+        let mut mapping: HashMap<Node, Node> = HashMap::new();
+        self.map_nodes_in_place(|node| {
+            if node.is_blank() {
+                let n_blanks = mapping.len();
+                mapping
+                    .entry(node)
+                    .or_insert_with(|| Node::from(format!("{}{}", prefix, n_blanks).as_str()))
+                    .clone()
+            } else {
+                node
+            }
+        });
+        mapping
+    }
+
+    /// Replace all skolem IRIs below `base_iri` with blank nodes.
+    ///
+    /// This is the inverse of [`skolemize`](#method.skolemize): Every IRI of the form
+    /// `<base_iri>/genid/<n>` is replaced by a new blank node, and the same IRI is replaced by the
+    /// same blank node in all triples. The returned map maps every skolem IRI to its blank node.
+    pub fn deskolemize(&mut self, base_iri: &str) -> HashMap<Node, Node> {
+        let prefix = skolem_prefix(base_iri);
+        let mut mapping: HashMap<Node, Node> = HashMap::new();
+        self.map_nodes_in_place(|node| {
+            if !node.is_blank() && node.starts_with(prefix.as_str()) {
+                mapping.entry(node).or_insert_with(Node::blank).clone()
+            } else {
+                node
+            }
+        });
+        mapping
+    }
+
+    /// Return a new graph with every node of every triple replaced by the result of `f`.
+    ///
+    /// `f` is called for the subject, the predicate and the object of every triple. Triples that
+    /// become equal are merged, and the new graph has the same [maximal literal
+    /// length](#method.with_max_literal_len) as this one.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "http://old.example.org/alice" -> "http://old.example.org/knows" -> "http://new.example.org/bob";
+    /// };
+    /// let rebased = graph.map_nodes(|node| {
+    ///     match node.strip_prefix("http://old.example.org/") {
+    ///         Some(local) => Node::from(format!("http://new.example.org/{}", local).as_str()),
+    ///         None => node.clone(),
+    ///     }
+    /// });
+    ///
+    /// assert!(rebased.contains(
+    ///     &Node::from("http://new.example.org/alice"),
+    ///     &Node::from("http://new.example.org/knows"),
+    ///     &Node::from("http://new.example.org/bob"),
+    /// ));
+    /// ```
+    pub fn map_nodes<F: FnMut(&Node) -> Node>(&self, mut f: F) -> HashGraph<S> {
+        let mut graph = HashGraph {
+            nodes: HashMap::default(),
+            max_literal_len: self.max_literal_len,
+        };
+        for (subject, predicate, object) in self.iter() {
+            graph.insert(f(subject), f(predicate), f(object));
+        }
+        graph
+    }
+
+    /// Replace every node of every triple with the result of `f`.
+    fn map_nodes_in_place<F: FnMut(Node) -> Node>(&mut self, mut f: F) {
+        for (subject, relationships) in std::mem::take(&mut self.nodes) {
+            let subject = f(subject);
+            for (predicate, objects) in relationships {
+                let predicate = f(predicate);
+                for object in objects {
+                    let object = f(object);
+                    self.insert(subject.clone(), predicate.clone(), object);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn materialize_rdfs() {
+    let person = Node::from("urn:arrdf:tests:class:person");
+    let agent = Node::from("urn:arrdf:tests:class:agent");
+    let thing = Node::from("urn:arrdf:tests:class:thing");
+    let organization = Node::from("urn:arrdf:tests:class:organization");
+    let knows = Node::from("urn:arrdf:tests:property:knows");
+    let related = Node::from("urn:arrdf:tests:property:related");
+    let member_of = Node::from("urn:arrdf:tests:property:member-of");
+    let alice = Node::from("urn:arrdf:tests:node:alice");
+    let bob = Node::from("urn:arrdf:tests:node:bob");
+    let acme = Node::from("urn:arrdf:tests:node:acme");
+
+    let mut graph = crate::graph! {
+        (person) -> (rdfs::sub_class_of()) -> (agent);
+        (agent) -> (rdfs::sub_class_of()) -> (thing);
+        (knows) -> (rdfs::sub_property_of()) -> (related);
+        (member_of) -> (rdfs::domain()) -> (person);
+        (member_of) -> (rdfs::range()) -> (organization);
+        (alice) -> (rdf::type_()) -> (person);
+        (alice) -> (knows) -> (bob);
+        (bob) -> (member_of) -> (acme);
+        (bob) -> (member_of) -> "ACME Inc.";
+    };
+    graph.materialize_rdfs();
+
+    let type_ = rdf::type_();
+    // rdfs11
+    assert!(graph.contains(&person, &rdfs::sub_class_of(), &thing));
+    // rdfs9
+    assert!(graph.contains(&alice, &type_, &agent));
+    assert!(graph.contains(&alice, &type_, &thing));
+    // rdfs7
+    assert!(graph.contains(&alice, &related, &bob));
+    // rdfs2, followed by rdfs9
+    assert!(graph.contains(&bob, &type_, &person));
+    assert!(graph.contains(&bob, &type_, &thing));
+    // rdfs3, but not for literals
+    assert!(graph.contains(&acme, &type_, &organization));
+    assert!(!graph.contains(&Node::from("ACME Inc."), &type_, &organization));
+    assert_eq!(17, graph.len());
+
+    // The materialization is a fixpoint.
+    let materialized = graph.clone();
+    graph.materialize_rdfs();
+    assert_eq!(materialized, graph);
+}
+
+#[test]
+fn merge_same_as() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let same_as = Node::from("http://www.w3.org/2002/07/owl#sameAs");
+    let node_d = Node::from("urn:arrdf:tests:node:d");
+    let node_e = Node::from("urn:arrdf:tests:node:e");
+    let node_f = Node::from("urn:arrdf:tests:node:f");
+    // A, D and the blank node C are the same, even though that's only declared transitively. D
+    // has the smallest IRI and represents them.
+    graph.clone_insert(&node_d, &same_as, &validator.node_a);
+    graph.clone_insert(&validator.node_c, &same_as, &node_d);
+    graph.clone_insert(&node_d, &validator.predicate_b, &node_f);
+    graph.clone_insert(&node_e, &same_as, &node_e);
+
+    let mapping = graph.merge_same_as(&same_as);
+    assert_eq!(2, mapping.len());
+    assert_eq!(node_d, mapping[&validator.node_a]);
+    assert_eq!(node_d, mapping[&validator.node_c]);
+
+    assert_eq!(4, graph.len());
+    assert!(graph.contains(&node_d, &validator.predicate_a, &validator.node_b));
+    assert!(graph.contains(&validator.node_b, &validator.predicate_b, &node_d));
+    assert!(graph.contains(&node_d, &validator.predicate_c, &node_d));
+    assert!(graph.contains(&node_d, &validator.predicate_b, &node_f));
+}
+
+#[test]
+fn rename_node() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let (a, b, c) = (&validator.node_a, &validator.node_b, &validator.node_c);
+    let (p_a, p_b) = (&validator.predicate_a, &validator.predicate_b);
+    let from = Node::from("urn:arrdf:tests:node:from");
+
+    // `from` is subject, predicate and object of a single triple and of other triples.
+    let mut graph = validator.graph.clone();
+    graph.clone_insert(&from, &from, &from);
+    graph.clone_insert(b, &from, c);
+    graph.clone_insert(c, p_b, &from);
+    // These triples already exist with `a` instead of `from`.
+    graph.clone_insert(&from, p_a, b);
+    graph.clone_insert(c, &validator.predicate_c, &from);
+
+    graph.rename_node(&from, a);
+    let expected = crate::graph! {
+        (a) -> (a) -> (a);
+        (a) -> (p_a) -> (b);
+        (b) -> (a) -> (c);
+        (b) -> (p_b) -> (c);
+        (c) -> (p_b) -> (a);
+        (c) -> (validator.predicate_c) -> (a);
+    };
+    assert_eq!(expected, graph);
+    assert_eq!(0, graph.out_degree(&from) + graph.in_degree(&from));
+
+    // Renaming a node to itself or renaming a missing node changes nothing.
+    graph.rename_node(a, a);
+    graph.rename_node(&from, b);
+    assert_eq!(expected, graph);
+}
+
+#[test]
+fn skolemize() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let blank = Node::blank();
+    graph.clone_insert(&validator.node_c, &validator.predicate_a, &blank);
+    let original = graph.clone();
+
+    let mapping = graph.skolemize("urn:arrdf:tests:skolem/");
+    assert_eq!(2, mapping.len());
+    assert_ne!(mapping[&validator.node_c], mapping[&blank]);
+    for skolem_iri in mapping.values() {
+        assert!(skolem_iri.starts_with("urn:arrdf:tests:skolem/genid/"));
+        assert!(skolem_iri.is_iri());
+    }
+    assert_eq!(4, graph.len());
+    assert!(graph
+        .iter()
+        .all(|(s, p, o)| !s.is_blank() && !p.is_blank() && !o.is_blank()));
+    let skolem_c = &mapping[&validator.node_c];
+    assert!(graph.contains(skolem_c, &validator.predicate_a, &mapping[&blank]));
+    assert!(graph.contains(skolem_c, &validator.predicate_c, &validator.node_a));
+    assert!(graph.contains(&validator.node_b, &validator.predicate_b, skolem_c));
+
+    // Skolem IRIs of other bases are left untouched.
+    let mut other = graph.clone();
+    assert!(other.deskolemize("urn:arrdf:tests:other").is_empty());
+    assert_eq!(graph, other);
+
+    let mapping = graph.deskolemize("urn:arrdf:tests:skolem");
+    assert_eq!(2, mapping.len());
+    assert_ne!(original, graph);
+    assert!(graph.is_isomorphic(&original));
+}
+
+#[test]
+fn map_nodes() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let node_b = &validator.node_b;
+    let mapped = validator.graph.map_nodes(|node| {
+        if node == node_b {
+            validator.node_a.clone()
+        } else {
+            node.clone()
+        }
+    });
+
+    assert_eq!(3, validator.graph.len());
+    assert_eq!(3, mapped.len());
+    assert!(mapped.contains(&validator.node_a, &validator.predicate_a, &validator.node_a));
+    assert!(mapped.contains(&validator.node_a, &validator.predicate_b, &validator.node_c));
+    assert!(mapped.contains(&validator.node_c, &validator.predicate_c, &validator.node_a));
+
+    // Collapsing both predicates of node_a into one merges the triples.
+    let mut graph = HashGraph::with_max_literal_len(8);
+    graph.clone_insert(&validator.node_a, &validator.predicate_a, &validator.node_b);
+    graph.clone_insert(&validator.node_a, &validator.predicate_b, &validator.node_b);
+    let predicate_b = &validator.predicate_b;
+    let mapped = graph.map_nodes(|node| {
+        if node == predicate_b {
+            validator.predicate_a.clone()
+        } else {
+            node.clone()
+        }
+    });
+    assert_eq!(1, mapped.len());
+    assert_eq!(Some(8), mapped.max_literal_len());
+}
@@ -0,0 +1,580 @@
+use crate::{Graph, HashGraph, Node};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::BuildHasher;
+
+impl<S: BuildHasher + Default> HashGraph<S> {
+    /// Return an iterator over all nodes that are reachable from `start`, in breadth-first order.
+    ///
+    /// Only triples with one of the given predicates are followed from their subject to their
+    /// object, or all triples if `predicates` is empty. The start node is always yielded first and
+    /// every node is only yielded once, so the traversal terminates even if the graph contains cycles.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     a -> next -> b;
+    ///     b -> next -> c;
+    ///     c -> next -> a;
+    ///     a -> other -> d;
+    /// };
+    ///
+    /// let (a, next) = (Node::from("a"), [Node::from("next")]);
+    /// let reachable: Vec<&Node> = graph.bfs(&a, &next).collect();
+    /// assert_eq!(vec!["a", "b", "c"], reachable.iter().map(|node| node.as_str()).collect::<Vec<_>>());
+    /// assert_eq!(4, graph.bfs(&a, &[]).count());
+    /// ```
+    pub fn bfs<'a>(
+        &'a self,
+        start: &'a Node,
+        predicates: &'a [Node],
+    ) -> impl 'a + Iterator<Item = &'a Node> {
+        self.bfs_with_depth(start, predicates).map(|(node, _)| node)
+    }
+
+    /// Like [`bfs`](#method.bfs), but yield every node together with its depth, which is the
+    /// minimal number of triples between it and `start`.
+    fn bfs_with_depth<'a>(
+        &'a self,
+        start: &'a Node,
+        predicates: &'a [Node],
+    ) -> impl 'a + Iterator<Item = (&'a Node, usize)> {
+        let mut queue: VecDeque<(&Node, usize)> = VecDeque::new();
+        let mut visited: HashSet<&Node> = HashSet::new();
+        queue.push_back((start, 0));
+        visited.insert(start);
+
+        std::iter::from_fn(move || {
+            let (node, depth) = queue.pop_front()?;
+            let objects = self
+                .nodes
+                .get(node)
+                .into_iter()
+                .flatten()
+                .filter(|(predicate, _)| predicates.is_empty() || predicates.contains(predicate))
+                .flat_map(|(_, objects)| objects);
+            for object in objects {
+                if visited.insert(object) {
+                    queue.push_back((object, depth + 1));
+                }
+            }
+            Some((node, depth))
+        })
+    }
+
+    /// Walk the graph in breadth-first order and call `visit` for every reachable node.
+    ///
+    /// All triples are followed from their subject to their object, starting at `start`. Every
+    /// reachable node, including `start`, is visited exactly once together with its depth, which is
+    /// the minimal number of triples between it and `start`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     a -> next -> b;
+    ///     b -> next -> c;
+    ///     a -> next -> c;
+    /// };
+    ///
+    /// let mut depths = Vec::new();
+    /// graph.traverse(&Node::from("a"), |node, depth| depths.push((node.to_string(), depth)));
+    /// depths.sort();
+    /// assert_eq!(vec![("a".to_string(), 0), ("b".to_string(), 1), ("c".to_string(), 1)], depths);
+    /// ```
+    pub fn traverse<F: FnMut(&Node, usize)>(&self, start: &Node, mut visit: F) {
+        for (node, depth) in self.bfs_with_depth(start, &[]) {
+            visit(node, depth);
+        }
+    }
+
+    /// Compute the transitive closure of a predicate.
+    ///
+    /// The returned graph contains a triple `x predicate y` for every pair of nodes where `y` can be
+    /// reached from `x` by following one or more triples with the predicate, including the original
+    /// triples. Only the given predicate is closed, the graph contains no other triples. A node is only
+    /// related to itself if it lies on a cycle.
+    ///
+    /// The closure is computed by walking from every subject of the predicate, which takes
+    /// O(n · m) time for n subjects and m triples with the predicate.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node, vocab::rdfs};
+    ///
+    /// let graph = graph! {
+    ///     cat -> (rdfs::sub_class_of()) -> mammal;
+    ///     mammal -> (rdfs::sub_class_of()) -> animal;
+    /// };
+    ///
+    /// let closure = graph.transitive_closure(&rdfs::sub_class_of());
+    /// assert_eq!(3, closure.len());
+    /// assert!(closure.contains(&Node::from("cat"), &rdfs::sub_class_of(), &Node::from("animal")));
+    /// ```
+    pub fn transitive_closure(&self, predicate: &Node) -> HashGraph<S> {
+        let successors = |node: &Node| {
+            self.nodes
+                .get(node)
+                .and_then(|relationships| relationships.get(predicate))
+                .into_iter()
+                .flatten()
+        };
+
+        let mut closure = HashGraph::default();
+        for subject in self.nodes.keys() {
+            let mut visited: HashSet<&Node> = HashSet::new();
+            let mut stack: Vec<&Node> = successors(subject).collect();
+            while let Some(node) = stack.pop() {
+                if visited.insert(node) {
+                    stack.extend(successors(node));
+                }
+            }
+            for node in visited {
+                closure.clone_insert(subject, predicate, node);
+            }
+        }
+        closure
+    }
+
+    /// Follow a sequence of predicates from a start node and return the nodes at the end.
+    ///
+    /// Every step maps the current set of nodes to the union of their objects for the next
+    /// predicate in `path`, like a sequence path in SPARQL. An empty path returns only the start
+    /// node, and the result is empty if no node has an object for one of the predicates.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:knows" -> "urn:bob";
+    ///     "urn:alice" -> "urn:knows" -> "urn:carol";
+    ///     "urn:bob" -> "urn:worksFor" -> "urn:acme";
+    ///     "urn:carol" -> "urn:worksFor" -> "urn:initech";
+    /// };
+    ///
+    /// let alice = Node::from("urn:alice");
+    /// let employers = graph.follow_path(&alice, &[Node::from("urn:knows"), Node::from("urn:worksFor")]);
+    /// assert_eq!(2, employers.len());
+    /// assert!(employers.contains(&Node::from("urn:acme")));
+    ///
+    /// assert!(graph.follow_path(&alice, &[Node::from("urn:worksFor")]).is_empty());
+    /// assert_eq!(1, graph.follow_path(&alice, &[]).len());
+    /// ```
+    pub fn follow_path(&self, start: &Node, path: &[Node]) -> HashSet<Node> {
+        let mut nodes: HashSet<&Node> = HashSet::new();
+        nodes.insert(start);
+        for predicate in path {
+            nodes = nodes
+                .into_iter()
+                .filter_map(|node| self.nodes.get(node))
+                .filter_map(|relationships| relationships.get(predicate))
+                .flatten()
+                .collect();
+            if nodes.is_empty() {
+                break;
+            }
+        }
+        nodes.into_iter().cloned().collect()
+    }
+
+    /// Partition the nodes of the graph into weakly connected components.
+    ///
+    /// Two nodes are in the same component if they are linked by a chain of triples, regardless of
+    /// the direction and the predicates of the triples. Only subjects and objects are considered as
+    /// nodes, so predicates don't connect the triples they appear in. The order of the components is
+    /// unspecified.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::graph;
+    ///
+    /// let graph = graph! {
+    ///     alice -> knows -> bob;
+    ///     carol -> knows -> bob;
+    ///     dave -> knows -> erin;
+    /// };
+    ///
+    /// let mut sizes: Vec<usize> = graph
+    ///     .weakly_connected_components()
+    ///     .iter()
+    ///     .map(|component| component.len())
+    ///     .collect();
+    /// sizes.sort();
+    /// assert_eq!(vec![2, 3], sizes);
+    /// ```
+    pub fn weakly_connected_components(&self) -> Vec<HashSet<Node>> {
+        let mut neighbors: HashMap<&Node, Vec<&Node>> = HashMap::new();
+        for (subject, _, object) in self.iter() {
+            neighbors.entry(subject).or_default().push(object);
+            neighbors.entry(object).or_default().push(subject);
+        }
+
+        let mut visited: HashSet<&Node> = HashSet::new();
+        let mut components = Vec::new();
+        for start in neighbors.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut component = HashSet::new();
+            let mut stack = vec![*start];
+            while let Some(node) = stack.pop() {
+                component.insert(node.clone());
+                for neighbor in neighbors[node].iter() {
+                    if visited.insert(neighbor) {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Extract all triples within `depth` hops around a node.
+    ///
+    /// Triples are followed in both directions: With a depth of 0, the returned graph contains all
+    /// triples that have `center` as their subject or object. Every additional level of depth adds
+    /// the triples of the nodes on the other end of these triples, and so on. Nodes are only visited
+    /// once, so the extraction terminates on cyclic graphs.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let graph = graph! {
+    ///     alice -> knows -> bob;
+    ///     carol -> knows -> alice;
+    ///     bob -> knows -> dave;
+    /// };
+    ///
+    /// let alice = Node::from("alice");
+    /// assert_eq!(2, graph.subgraph_around(&alice, 0).len());
+    /// assert_eq!(3, graph.subgraph_around(&alice, 1).len());
+    /// ```
+    pub fn subgraph_around(&self, center: &Node, depth: usize) -> HashGraph<S> {
+        let mut incoming: HashMap<&Node, Vec<(&Node, &Node)>> = HashMap::new();
+        for (subject, predicate, object) in self.iter() {
+            incoming
+                .entry(object)
+                .or_default()
+                .push((subject, predicate));
+        }
+
+        let mut subgraph = HashGraph::default();
+        let mut visited: HashSet<&Node> = HashSet::new();
+        visited.insert(center);
+        let mut frontier: Vec<&Node> = vec![center];
+        for _ in 0..=depth {
+            let mut next_frontier = Vec::new();
+            for node in frontier {
+                let outgoing =
+                    self.nodes
+                        .get(node)
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|(predicate, objects)| {
+                            objects.iter().map(move |object| (node, predicate, object))
+                        });
+                let incoming = incoming
+                    .get(node)
+                    .into_iter()
+                    .flatten()
+                    .map(|(subject, predicate)| (*subject, *predicate, node));
+                for (subject, predicate, object) in outgoing.chain(incoming) {
+                    subgraph.clone_insert(subject, predicate, object);
+                    let neighbor = if subject == node { object } else { subject };
+                    if visited.insert(neighbor) {
+                        next_frontier.push(neighbor);
+                    }
+                }
+            }
+            frontier = next_frontier;
+        }
+        subgraph
+    }
+
+    /// Return the symmetric concise bounded description of a node.
+    ///
+    /// The [symmetric CBD](https://www.w3.org/Submission/CBD/#alternatives) contains all triples
+    /// that have `node` as their subject or object. For every blank object of an outgoing triple, the
+    /// outgoing triples of the blank node are added too, and for every blank subject of an incoming
+    /// triple, the incoming triples of the blank node are added, recursively. The description stops
+    /// at IRIs and literals, which can be described on their own.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{graph, Graph, Node};
+    ///
+    /// let address = Node::blank();
+    /// let graph = graph! {
+    ///     "urn:alice" -> "urn:address" -> (address);
+    ///     (address) -> "urn:city" -> "Berlin";
+    ///     "urn:bob" -> "urn:knows" -> "urn:alice";
+    ///     "urn:bob" -> "urn:name" -> "Bob";
+    /// };
+    ///
+    /// let description = graph.symmetric_cbd(&Node::from("urn:alice"));
+    /// assert_eq!(3, description.len());
+    /// assert!(description.contains(&address, &Node::from("urn:city"), &Node::from("Berlin")));
+    /// ```
+    pub fn symmetric_cbd(&self, node: &Node) -> HashGraph<S> {
+        let mut incoming: HashMap<&Node, Vec<(&Node, &Node)>> = HashMap::new();
+        for (subject, predicate, object) in self.iter() {
+            incoming
+                .entry(object)
+                .or_default()
+                .push((subject, predicate));
+        }
+
+        let mut description = HashGraph::default();
+
+        let mut visited: HashSet<&Node> = HashSet::new();
+        visited.insert(node);
+        let mut queue: Vec<&Node> = vec![node];
+        while let Some(subject) = queue.pop() {
+            for (predicate, objects) in self.nodes.get(subject).into_iter().flatten() {
+                for object in objects {
+                    description.clone_insert(subject, predicate, object);
+                    if object.is_blank() && visited.insert(object) {
+                        queue.push(object);
+                    }
+                }
+            }
+        }
+
+        let mut visited: HashSet<&Node> = HashSet::new();
+        visited.insert(node);
+        let mut queue: Vec<&Node> = vec![node];
+        while let Some(object) = queue.pop() {
+            for (subject, predicate) in incoming.get(object).into_iter().flatten() {
+                description.clone_insert(subject, predicate, object);
+                if subject.is_blank() && visited.insert(subject) {
+                    queue.push(subject);
+                }
+            }
+        }
+
+        description
+    }
+}
+
+#[test]
+fn bfs() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let node_d = Node::from("urn:arrdf:tests:node:d");
+    graph.clone_insert(&validator.node_a, &validator.predicate_a, &node_d);
+
+    // The cycle a -> b -> c -> a terminates and every node is only yielded once.
+    let reachable: Vec<&Node> = graph.bfs(&validator.node_a, &[]).collect();
+    assert_eq!(4, reachable.len());
+    assert_eq!(&validator.node_a, reachable[0]);
+    assert_eq!(&validator.node_c, reachable[3]);
+
+    let predicates = [validator.predicate_b.clone()];
+    let reachable: Vec<&Node> = graph.bfs(&validator.node_b, &predicates).collect();
+    assert_eq!(vec![&validator.node_b, &validator.node_c], reachable);
+
+    let predicates = [validator.predicate_a.clone(), validator.predicate_c.clone()];
+    let reachable: Vec<&Node> = graph.bfs(&validator.node_c, &predicates).collect();
+    assert_eq!(4, reachable.len());
+    assert_eq!(&validator.node_c, reachable[0]);
+    assert_eq!(&validator.node_a, reachable[1]);
+
+    let unknown = Node::from("urn:arrdf:tests:node:unknown");
+    assert_eq!(vec![&unknown], graph.bfs(&unknown, &[]).collect::<Vec<_>>());
+}
+
+#[test]
+fn traverse() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let graph = validator.graph;
+
+    let mut visits: Vec<(Node, usize)> = Vec::new();
+    graph.traverse(&validator.node_a, |node, depth| {
+        visits.push((node.clone(), depth))
+    });
+    assert_eq!(
+        vec![
+            (validator.node_a.clone(), 0),
+            (validator.node_b.clone(), 1),
+            (validator.node_c.clone(), 2),
+        ],
+        visits
+    );
+
+    let mut visits: Vec<(Node, usize)> = Vec::new();
+    graph.traverse(&validator.node_c, |node, depth| {
+        visits.push((node.clone(), depth))
+    });
+    assert_eq!(
+        vec![
+            (validator.node_c.clone(), 0),
+            (validator.node_a.clone(), 1),
+            (validator.node_b.clone(), 2),
+        ],
+        visits
+    );
+}
+
+#[test]
+fn transitive_closure() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let (node_a, node_b, node_c) = (&validator.node_a, &validator.node_b, &validator.node_c);
+    let (predicate_a, predicate_b) = (&validator.predicate_a, &validator.predicate_b);
+    graph.clone_insert(node_b, predicate_a, node_c);
+    graph.clone_insert(node_c, predicate_a, node_a);
+
+    // a -> b -> c -> a is a cycle, so every node is related to every node, including itself.
+    let closure = graph.transitive_closure(predicate_a);
+    assert_eq!(9, closure.len());
+    for subject in &[node_a, node_b, node_c] {
+        for object in &[node_a, node_b, node_c] {
+            assert!(closure.contains(subject, predicate_a, object));
+        }
+    }
+
+    // Other predicates aren't closed.
+    let closure = graph.transitive_closure(predicate_b);
+    assert_eq!(1, closure.len());
+    assert!(closure.contains(node_b, predicate_b, node_c));
+    assert_eq!(1, graph.transitive_closure(&validator.predicate_c).len());
+    assert!(graph
+        .transitive_closure(&Node::from("urn:arrdf:tests:predicate:unknown"))
+        .is_empty());
+}
+
+#[test]
+fn follow_path() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let (node_a, node_b, node_c) = (&validator.node_a, &validator.node_b, &validator.node_c);
+    let (predicate_a, predicate_b, predicate_c) = (
+        &validator.predicate_a,
+        &validator.predicate_b,
+        &validator.predicate_c,
+    );
+    graph.clone_insert(node_a, predicate_a, node_c);
+
+    let path = |path: &[&Node]| -> Vec<Node> { path.iter().map(|node| (*node).clone()).collect() };
+    let result = graph.follow_path(node_a, &path(&[predicate_a]));
+    assert_eq!(2, result.len());
+    assert!(result.contains(node_b) && result.contains(node_c));
+
+    // The objects of both nodes are merged in the next step.
+    let result = graph.follow_path(node_a, &path(&[predicate_a, predicate_c]));
+    assert_eq!(1, result.len());
+    assert!(result.contains(node_a));
+
+    let result = graph.follow_path(node_a, &path(&[predicate_a, predicate_b, predicate_c]));
+    assert_eq!(1, result.len());
+    assert!(result.contains(node_a));
+
+    // Broken links and empty paths.
+    assert!(graph
+        .follow_path(node_a, &path(&[predicate_b, predicate_a]))
+        .is_empty());
+    assert!(graph
+        .follow_path(&validator.predicate_a, &path(&[predicate_a]))
+        .is_empty());
+    let result = graph.follow_path(node_c, &[]);
+    assert_eq!(1, result.len());
+    assert!(result.contains(node_c));
+}
+
+#[test]
+fn weakly_connected_components() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+
+    let components = graph.weakly_connected_components();
+    assert_eq!(1, components.len());
+    assert_eq!(3, components[0].len());
+    assert!(components[0].contains(&validator.node_a));
+    assert!(components[0].contains(&validator.node_b));
+    assert!(components[0].contains(&validator.node_c));
+    assert!(!components[0].contains(&validator.predicate_a));
+
+    // Sharing a predicate doesn't connect two triples.
+    let node_d = Node::from("urn:arrdf:tests:node:d");
+    let node_e = Node::from("urn:arrdf:tests:node:e");
+    graph.clone_insert(&node_d, &validator.predicate_a, &node_e);
+    let components = graph.weakly_connected_components();
+    assert_eq!(2, components.len());
+    let component = components
+        .iter()
+        .find(|component| component.contains(&node_d))
+        .unwrap();
+    assert_eq!(2, component.len());
+    assert!(component.contains(&node_e));
+
+    assert!(HashGraph::new().weakly_connected_components().is_empty());
+}
+
+#[test]
+fn subgraph_around() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let node_d = Node::from("urn:arrdf:tests:node:d");
+    let node_e = Node::from("urn:arrdf:tests:node:e");
+    graph.clone_insert(&validator.node_b, &validator.predicate_a, &node_d);
+    graph.clone_insert(&node_e, &validator.predicate_a, &node_d);
+
+    let subgraph = graph.subgraph_around(&validator.node_a, 0);
+    assert_eq!(2, subgraph.len());
+    assert!(subgraph.contains(&validator.node_a, &validator.predicate_a, &validator.node_b));
+    assert!(subgraph.contains(&validator.node_c, &validator.predicate_c, &validator.node_a));
+
+    // The cycle a -> b -> c -> a terminates.
+    let subgraph = graph.subgraph_around(&validator.node_a, 1);
+    assert_eq!(4, subgraph.len());
+    assert!(subgraph.contains(&validator.node_b, &validator.predicate_a, &node_d));
+    assert!(!subgraph.contains(&node_e, &validator.predicate_a, &node_d));
+
+    assert_eq!(graph, graph.subgraph_around(&validator.node_a, 2));
+    assert_eq!(graph, graph.subgraph_around(&validator.node_a, 10));
+    assert!(graph
+        .subgraph_around(&Node::from("urn:arrdf:tests:node:unknown"), 1)
+        .is_empty());
+}
+
+#[test]
+fn symmetric_cbd() {
+    let validator = crate::Validator::new(HashGraph::new());
+    let mut graph = validator.graph;
+    let node_d = Node::from("urn:arrdf:tests:node:d");
+    let blank = Node::blank();
+    let literal = Node::from("Hello World");
+    graph.clone_insert(&validator.node_b, &validator.predicate_a, &node_d);
+    graph.clone_insert(&validator.node_a, &validator.predicate_b, &blank);
+    graph.clone_insert(&blank, &validator.predicate_c, &literal);
+    graph.clone_insert(&literal, &validator.predicate_c, &blank);
+
+    let description = graph.symmetric_cbd(&validator.node_a);
+    assert_eq!(5, description.len());
+    // Outgoing triples, including the closure of the blank object.
+    assert!(description.contains(&validator.node_a, &validator.predicate_a, &validator.node_b));
+    assert!(description.contains(&validator.node_a, &validator.predicate_b, &blank));
+    assert!(description.contains(&blank, &validator.predicate_c, &literal));
+    // Incoming triples, including the closure of the blank subject node_c.
+    assert!(description.contains(&validator.node_c, &validator.predicate_c, &validator.node_a));
+    assert!(description.contains(&validator.node_b, &validator.predicate_b, &validator.node_c));
+    // The description stops at IRIs and literals.
+    assert!(!description.contains(&validator.node_b, &validator.predicate_a, &node_d));
+    assert!(!description.contains(&literal, &validator.predicate_c, &blank));
+
+    assert!(graph
+        .symmetric_cbd(&Node::from("urn:arrdf:tests:node:unknown"))
+        .is_empty());
+}
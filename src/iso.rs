@@ -0,0 +1,282 @@
+//! Isomorphism checks for graphs with blank nodes.
+//!
+//! Since blank nodes are only distinguished by their identity, two graphs that were created
+//! independently are usually not equal, even if they describe the same structure. Two graphs are
+//! isomorphic if there is a bijection between their blank nodes that turns one graph into the other.
+use crate::{Graph, Node};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+type Triple<'a> = (&'a Node, &'a Node, &'a Node);
+
+/// Return `true` if the two graphs are isomorphic.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{graph, iso, Node};
+///
+/// let (x, y) = (Node::blank(), Node::blank());
+/// let a = graph! { (x) -> knows -> (y); };
+///
+/// let (x, y) = (Node::blank(), Node::blank());
+/// let b = graph! { (x) -> knows -> (y); };
+///
+/// assert_ne!(a, b);
+/// assert!(iso::is_isomorphic(&a, &b));
+/// ```
+pub fn is_isomorphic<G, H>(lhs: &G, rhs: &H) -> bool
+where
+    G: Graph,
+    H: Graph,
+{
+    isomorphism_mapping(lhs, rhs).is_some()
+}
+
+/// Return the bijection between the blank nodes of two graphs if they are isomorphic.
+///
+/// Every blank node of `lhs` is mapped to the blank node of `rhs` it corresponds to. If the graphs
+/// aren't isomorphic, `None` is returned.
+///
+/// The blank nodes are first partitioned by their surroundings in the graph, which usually
+/// determines the bijection right away. Only blank nodes that can't be told apart this way are
+/// matched by trial and error.
+pub fn isomorphism_mapping<G, H>(lhs: &G, rhs: &H) -> Option<HashMap<Node, Node>>
+where
+    G: Graph,
+    H: Graph,
+{
+    if lhs.len() != rhs.len() {
+        return None;
+    }
+
+    let lhs_colors = blank_colors(lhs);
+    let rhs_colors = blank_colors(rhs);
+    if lhs_colors.len() != rhs_colors.len() {
+        return None;
+    }
+
+    let mut candidates: HashMap<u64, Vec<&Node>> = HashMap::new();
+    for (node, color) in rhs_colors.iter() {
+        candidates.entry(*color).or_default().push(*node);
+    }
+
+    let mut blanks: Vec<&Node> = lhs_colors.keys().cloned().collect();
+    for blank in blanks.iter() {
+        candidates.get(&lhs_colors[blank])?;
+    }
+    blanks.sort_by_key(|blank| candidates[&lhs_colors[blank]].len());
+
+    let mut triples: HashMap<&Node, Vec<Triple>> = HashMap::new();
+    for (s, p, o) in lhs.iter() {
+        for node in vec![s, p, o].into_iter().filter(|node| node.is_blank()) {
+            triples.entry(node).or_default().push((s, p, o));
+        }
+        if !s.is_blank() && !p.is_blank() && !o.is_blank() && !rhs.contains(s, p, o) {
+            return None;
+        }
+    }
+
+    let mut search = Search {
+        rhs,
+        colors: &lhs_colors,
+        candidates: &candidates,
+        triples: &triples,
+        mapping: HashMap::new(),
+        used: HashSet::new(),
+    };
+    if search.extend(&blanks) {
+        Some(
+            search
+                .mapping
+                .into_iter()
+                .map(|(l, r)| (l.clone(), r.clone()))
+                .collect(),
+        )
+    } else {
+        None
+    }
+}
+
+/// Assign a color to every blank node so that blank nodes with different colors can't correspond to each other.
+///
+/// This is the classic color refinement: Every blank node starts with the same color, and in every round, its
+/// new color is derived from the colors and nodes of all triples it appears in. Since the colors only depend on
+/// the structure of the graph and not on the identity of the blank nodes, isomorphic graphs yield equal colorings.
+fn blank_colors<G: Graph>(graph: &G) -> HashMap<&Node, u64> {
+    let mut colors: HashMap<&Node, u64> = graph
+        .iter()
+        .flat_map(|(s, p, o)| vec![s, p, o])
+        .filter(|node| node.is_blank())
+        .map(|node| (node, 0))
+        .collect();
+    let mut n_colors = 1;
+
+    for _ in 0..colors.len() {
+        let mut signatures: HashMap<&Node, Vec<u64>> = HashMap::new();
+        for (s, p, o) in graph.iter() {
+            let triple = [s, p, o];
+            for (position, node) in triple.iter().enumerate() {
+                if !node.is_blank() {
+                    continue;
+                }
+                let mut hasher = DefaultHasher::new();
+                position.hash(&mut hasher);
+                for other in triple.iter() {
+                    if other == node {
+                        0u8.hash(&mut hasher);
+                    } else if other.is_blank() {
+                        1u8.hash(&mut hasher);
+                        colors[other].hash(&mut hasher);
+                    } else {
+                        2u8.hash(&mut hasher);
+                        other.as_str().hash(&mut hasher);
+                    }
+                }
+                signatures.entry(node).or_default().push(hasher.finish());
+            }
+        }
+
+        let new_colors: HashMap<&Node, u64> = signatures
+            .into_iter()
+            .map(|(node, mut signature)| {
+                signature.sort_unstable();
+                let mut hasher = DefaultHasher::new();
+                colors[node].hash(&mut hasher);
+                signature.hash(&mut hasher);
+                (node, hasher.finish())
+            })
+            .collect();
+        let new_n_colors = new_colors.values().collect::<HashSet<_>>().len();
+
+        colors = new_colors;
+        if new_n_colors == n_colors {
+            break;
+        }
+        n_colors = new_n_colors;
+    }
+
+    colors
+}
+
+struct Search<'a, 'b, H> {
+    rhs: &'b H,
+    colors: &'b HashMap<&'a Node, u64>,
+    candidates: &'b HashMap<u64, Vec<&'b Node>>,
+    triples: &'b HashMap<&'a Node, Vec<Triple<'a>>>,
+    mapping: HashMap<&'a Node, &'b Node>,
+    used: HashSet<&'b Node>,
+}
+
+impl<'a, 'b, H: Graph> Search<'a, 'b, H> {
+    fn extend(&mut self, blanks: &[&'a Node]) -> bool {
+        let (blank, remaining) = match blanks.split_first() {
+            Some(split) => split,
+            None => return true,
+        };
+
+        for candidate in self.candidates[&self.colors[blank]].iter() {
+            if self.used.contains(candidate) {
+                continue;
+            }
+            self.mapping.insert(blank, candidate);
+            self.used.insert(candidate);
+
+            if self.is_consistent(blank) && self.extend(remaining) {
+                return true;
+            }
+
+            self.mapping.remove(blank);
+            self.used.remove(candidate);
+        }
+        false
+    }
+
+    /// Check that all triples of the newly mapped blank node, that only contain mapped blank nodes, are in the other graph.
+    fn is_consistent(&self, blank: &Node) -> bool {
+        let map = |node: &'a Node| -> Option<&Node> {
+            if node.is_blank() {
+                self.mapping.get(node).cloned()
+            } else {
+                Some(node)
+            }
+        };
+        self.triples[blank]
+            .iter()
+            .all(|(s, p, o)| match (map(s), map(p), map(o)) {
+                (Some(s), Some(p), Some(o)) => self.rhs.contains(s, p, o),
+                _ => true,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn isomorphism_mapping() {
+        let (alice_a, bob_a) = (Node::blank(), Node::blank());
+        let a = graph! {
+            (alice_a) -> knows -> (bob_a);
+            (alice_a) -> name -> "Alice";
+            (bob_a) -> name -> "Bob";
+        };
+
+        let (alice_b, bob_b) = (Node::blank(), Node::blank());
+        let b = graph! {
+            (bob_b) -> name -> "Bob";
+            (alice_b) -> name -> "Alice";
+            (alice_b) -> knows -> (bob_b);
+        };
+
+        let mapping = iso::isomorphism_mapping(&a, &b).unwrap();
+        assert_eq!(2, mapping.len());
+        assert_eq!(alice_b, mapping[&alice_a]);
+        assert_eq!(bob_b, mapping[&bob_a]);
+
+        let mapping = iso::isomorphism_mapping(&b, &a).unwrap();
+        assert_eq!(alice_a, mapping[&alice_b]);
+        assert_eq!(bob_a, mapping[&bob_b]);
+
+        let c = graph! {
+            (alice_b) -> name -> "Alice";
+            (bob_b) -> name -> "Bob";
+            (bob_b) -> knows -> (alice_b);
+        };
+        assert!(iso::isomorphism_mapping(&a, &c).is_none());
+        assert!(!iso::is_isomorphic(&a, &c));
+    }
+
+    #[test]
+    fn symmetric_blanks() {
+        // A cycle of blank nodes can't be partitioned by colors, so the mapping has to be searched.
+        let (a_1, a_2, a_3) = (Node::blank(), Node::blank(), Node::blank());
+        let a = graph! {
+            (a_1) -> next -> (a_2);
+            (a_2) -> next -> (a_3);
+            (a_3) -> next -> (a_1);
+        };
+
+        let (b_1, b_2, b_3) = (Node::blank(), Node::blank(), Node::blank());
+        let b = graph! {
+            (b_2) -> next -> (b_1);
+            (b_1) -> next -> (b_3);
+            (b_3) -> next -> (b_2);
+        };
+
+        let mapping = iso::isomorphism_mapping(&a, &b).unwrap();
+        assert_eq!(3, mapping.len());
+        for (s, p, o) in a.iter() {
+            assert!(b.contains(&mapping[s], p, &mapping[o]));
+        }
+
+        let c = graph! {
+            (b_1) -> next -> (b_2);
+            (b_2) -> next -> (b_1);
+            (b_3) -> next -> (b_3);
+        };
+        assert!(!iso::is_isomorphic(&a, &c));
+    }
+}
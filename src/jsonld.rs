@@ -0,0 +1,724 @@
+//! A parser for a subset of [JSON-LD](https://www.w3.org/TR/json-ld11/), enabled by the `jsonld` feature.
+//!
+//! JSON-LD is huge, so the parser only handles documents in expanded or flattened form with
+//! inline contexts, which covers most data that is published as JSON-LD, like schema.org markup:
+//!
+//! * The document is a node object, an array of node objects, or an object with a `@graph` and an
+//!   optional `@context`.
+//! * Contexts are objects or arrays of objects. They may define `@base`, `@vocab`, `@language` and
+//!   terms, which map to an IRI or to an object with an `@id` and an optional `@type` for type
+//!   coercion. Nested contexts in node objects extend the surrounding context.
+//! * In a node object, `@id` names the subject, which is a blank node if it's missing. `@type`
+//!   becomes `rdf:type` triples, and every other key is expanded to a predicate IRI via the context.
+//!   A node object with both an `@id` and a `@graph` names a graph of the dataset.
+//! * Values may be strings, numbers, booleans, value objects with `@value` and either `@type` or
+//!   `@language`, `@set` objects, or nested node objects. `null` values are ignored.
+//!
+//! All other constructs, like remote contexts, `@list` or `@reverse`, are rejected with an error
+//! instead of being silently dropped. Properties that don't expand to an IRI are rejected too.
+use crate::blank_node_allocator::BlankNodes;
+use crate::node::{is_language_tag, resolve_reference};
+use crate::vocab::{rdf, xsd};
+use crate::{Dataset, Node, ParseError};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// The maximal number of terms that are expanded to expand a single IRI.
+const MAX_EXPANSION_DEPTH: usize = 16;
+
+/// Parse a JSON-LD document and collect all quads in a dataset.
+///
+/// Relative IRIs are resolved against `base`, unless the context declares another `@base`. Errors
+/// in the JSON syntax are reported at their exact position, while errors in the JSON-LD structure
+/// are reported at the first occurrence of the offending string in the document.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{vocab::rdf, Node};
+///
+/// let dataset = arrdf::jsonld::parse_jsonld(r#"{
+///     "@context": {"@vocab": "http://schema.org/"},
+///     "@id": "alice",
+///     "@type": "Person",
+///     "name": "Alice",
+///     "knows": {"name": "Bob"}
+/// }"#, "http://example.org/").unwrap();
+///
+/// let alice = Node::from("http://example.org/alice");
+/// let person = Node::from("http://schema.org/Person");
+/// let name = Node::from("http://schema.org/name");
+/// assert_eq!(4, dataset.len());
+/// assert!(dataset.contains_quad(None, &alice, &rdf::type_(), &person));
+/// assert!(dataset.contains_quad(None, &alice, &name, &Node::from("Alice")));
+/// ```
+pub fn parse_jsonld(input: &str, base: &str) -> Result<Dataset, ParseError> {
+    let document: Value = serde_json::from_str(input).map_err(|error| {
+        // The message of serde_json ends with the position, which the `ParseError` contains anyway.
+        let message = error.to_string();
+        let message = match message.rfind(" at line ") {
+            Some(index) => message[..index].to_owned(),
+            None => message,
+        };
+        ParseError {
+            line: error.line(),
+            column: error.column(),
+            message,
+        }
+    })?;
+
+    let mut parser = Parser {
+        dataset: Dataset::new(),
+        blanks: BlankNodes::default(),
+    };
+    let context = Context {
+        base: base.to_owned(),
+        ..Context::default()
+    };
+    parser
+        .document(&document, context)
+        .map_err(|error| error.locate(input))?;
+    Ok(parser.dataset)
+}
+
+/// An error in the JSON-LD structure, together with the string that caused it.
+struct Error {
+    culprit: String,
+    message: String,
+}
+
+impl Error {
+    fn new(culprit: &str, message: String) -> Self {
+        Self {
+            culprit: culprit.to_owned(),
+            message,
+        }
+    }
+
+    fn unsupported(keyword: &str) -> Self {
+        Self::new(
+            keyword,
+            format!("the JSON-LD keyword '{}' is not supported", keyword),
+        )
+    }
+
+    /// Turn the error into a parse error at the first occurrence of the culprit in the input.
+    fn locate(self, input: &str) -> ParseError {
+        let quoted = Value::from(self.culprit.as_str()).to_string();
+        let rest = match input.find(&quoted).or_else(|| input.find(&self.culprit)) {
+            Some(index) => &input[index..],
+            None => input,
+        };
+        ParseError::at(input, rest, self.message)
+    }
+}
+
+/// A term of the context and the IRI it stands for.
+#[derive(Clone)]
+struct Definition {
+    iri: String,
+    /// The type that string values of the term are coerced to, e.g. `@id` or a datatype.
+    coercion: Option<String>,
+}
+
+#[derive(Clone, Default)]
+struct Context {
+    base: String,
+    vocab: Option<String>,
+    language: Option<String>,
+    terms: HashMap<String, Definition>,
+}
+
+impl Context {
+    fn update(&mut self, context: &Value) -> Result<(), Error> {
+        let definitions = match context {
+            Value::Null => {
+                // A null context removes all definitions, but keeps the base of the document.
+                *self = Context {
+                    base: self.base.clone(),
+                    ..Context::default()
+                };
+                return Ok(());
+            }
+            Value::Array(contexts) => {
+                for context in contexts {
+                    self.update(context)?;
+                }
+                return Ok(());
+            }
+            Value::Object(definitions) => definitions,
+            Value::String(iri) => {
+                return Err(Error::new(
+                    iri,
+                    format!("the remote context '{}' is not supported", iri),
+                ))
+            }
+            _ => {
+                return Err(Error::new(
+                    "@context",
+                    "expected a context object".to_owned(),
+                ))
+            }
+        };
+
+        for (key, value) in definitions {
+            match (key.as_str(), value) {
+                ("@base", Value::String(base)) => {
+                    self.base = resolve_reference(base, &self.base)
+                        .map_err(|error| Error::new(base, error.to_string()))?
+                        .as_str()
+                        .to_owned();
+                }
+                ("@vocab", Value::String(vocab)) => {
+                    self.vocab = Some(self.expand(vocab, true)?.as_str().to_owned());
+                }
+                ("@language", Value::String(language)) => {
+                    if !is_language_tag(language) {
+                        return Err(Error::new(
+                            language,
+                            format!("'{}' is not a valid language tag", language),
+                        ));
+                    }
+                    self.language = Some(language.clone());
+                }
+                ("@vocab", Value::Null) => self.vocab = None,
+                ("@language", Value::Null) => self.language = None,
+                ("@version", _) => (),
+                (keyword, _) if keyword.starts_with('@') => {
+                    return Err(Error::unsupported(keyword))
+                }
+                (term, Value::Null) => {
+                    self.terms.remove(term);
+                }
+                (term, Value::String(iri)) => {
+                    self.terms.insert(
+                        term.to_owned(),
+                        Definition {
+                            iri: iri.clone(),
+                            coercion: None,
+                        },
+                    );
+                }
+                (term, Value::Object(definition)) => {
+                    let definition = self.definition(term, definition)?;
+                    self.terms.insert(term.to_owned(), definition);
+                }
+                (term, _) => {
+                    return Err(Error::new(
+                        term,
+                        format!("expected an IRI or an object to define the term '{}'", term),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse an expanded term definition like `{"@id": "...", "@type": "@id"}`.
+    fn definition(&self, term: &str, definition: &Map<String, Value>) -> Result<Definition, Error> {
+        let mut iri = None;
+        let mut coercion = None;
+        for (key, value) in definition {
+            match (key.as_str(), value) {
+                ("@id", Value::String(value)) => iri = Some(value.clone()),
+                ("@type", Value::String(value)) => coercion = Some(value.clone()),
+                (keyword, _) if keyword.starts_with('@') => {
+                    return Err(Error::unsupported(keyword))
+                }
+                (key, _) => {
+                    return Err(Error::new(
+                        key,
+                        format!("unexpected key '{}' in the definition of '{}'", key, term),
+                    ))
+                }
+            }
+        }
+        // Without an `@id`, the term is expanded like any other property.
+        let iri = match iri {
+            Some(iri) => iri,
+            None => self.expand(term, true)?.as_str().to_owned(),
+        };
+        Ok(Definition { iri, coercion })
+    }
+
+    /// Expand a term, a compact IRI or a relative IRI to an IRI.
+    ///
+    /// Property names and types are relative to the vocabulary, while `@id`s are relative to the
+    /// base IRI.
+    fn expand(&self, value: &str, vocab: bool) -> Result<Node, Error> {
+        self.expand_with_depth(value, vocab, 0)
+            .ok_or_else(|| Error::new(value, format!("'{}' doesn't expand to an IRI", value)))
+    }
+
+    fn expand_with_depth(&self, value: &str, vocab: bool, depth: usize) -> Option<Node> {
+        if depth > MAX_EXPANSION_DEPTH {
+            return None;
+        }
+        if vocab {
+            if let Some(definition) = self.terms.get(value) {
+                return self.expand_with_depth(&definition.iri, true, depth + 1);
+            }
+        }
+        if let Some(colon) = value.find(':') {
+            let (prefix, suffix) = (&value[..colon], &value[colon + 1..]);
+            if let Some(definition) = self.terms.get(prefix).filter(|_| !suffix.starts_with("//")) {
+                let namespace = self.expand_with_depth(&definition.iri, true, depth + 1)?;
+                return Some(Node::from(
+                    format!("{}{}", namespace.as_str(), suffix).as_str(),
+                ));
+            }
+        }
+
+        let node = Node::from(value);
+        if node.is_iri() {
+            Some(node)
+        } else if vocab {
+            let vocab = self.vocab.as_ref()?;
+            Some(Node::from(format!("{}{}", vocab, value).as_str()))
+        } else {
+            resolve_reference(value, &self.base).ok()
+        }
+    }
+}
+
+struct Parser {
+    dataset: Dataset,
+    blanks: BlankNodes,
+}
+
+impl Parser {
+    fn document(&mut self, document: &Value, mut context: Context) -> Result<(), Error> {
+        match document {
+            Value::Array(nodes) => {
+                for node in nodes {
+                    self.node(node, &context, None)?;
+                }
+                Ok(())
+            }
+            // An object with only a `@graph` and a context contains the nodes of the default graph.
+            Value::Object(object)
+                if object.contains_key("@graph")
+                    && object
+                        .keys()
+                        .all(|key| key == "@graph" || key == "@context") =>
+            {
+                if let Some(local_context) = object.get("@context") {
+                    context.update(local_context)?;
+                }
+                self.document(&object["@graph"], context)
+            }
+            _ => self.node(document, &context, None).map(|_| ()),
+        }
+    }
+
+    /// Insert the quads of a node object and return its subject.
+    fn node(
+        &mut self,
+        node: &Value,
+        context: &Context,
+        graph: Option<&Node>,
+    ) -> Result<Node, Error> {
+        let object = match node {
+            Value::Object(object) => object,
+            _ => {
+                let culprit = node.to_string();
+                return Err(Error::new(&culprit, "expected a node object".to_owned()));
+            }
+        };
+
+        let mut local_context;
+        let context = match object.get("@context") {
+            Some(definitions) => {
+                local_context = context.clone();
+                local_context.update(definitions)?;
+                &local_context
+            }
+            None => context,
+        };
+
+        let subject = match object.get("@id") {
+            Some(Value::String(id)) => self.resource(id, context, false)?,
+            Some(_) => return Err(Error::new("@id", "expected a string as '@id'".to_owned())),
+            None => Node::blank(),
+        };
+
+        for (key, value) in object {
+            match key.as_str() {
+                "@context" | "@id" => (),
+                "@type" => {
+                    for node_type in strings(key, value)? {
+                        let node_type = self.resource(node_type, context, true)?;
+                        self.insert(graph, &subject, rdf::type_(), node_type);
+                    }
+                }
+                "@graph" => {
+                    let nodes = match value {
+                        Value::Array(nodes) => nodes.iter().collect(),
+                        node => vec![node],
+                    };
+                    for node in nodes {
+                        self.node(node, context, Some(&subject))?;
+                    }
+                }
+                keyword if keyword.starts_with('@') => return Err(Error::unsupported(keyword)),
+                property => {
+                    let predicate = context.expand(property, true)?;
+                    if predicate.is_blank() {
+                        return Err(Error::new(
+                            property,
+                            format!("the property '{}' is a blank node", property),
+                        ));
+                    }
+                    let coercion = context
+                        .terms
+                        .get(property)
+                        .and_then(|definition| definition.coercion.as_deref());
+                    for object in self.values(value, context, coercion, graph)? {
+                        self.insert(graph, &subject, predicate.clone(), object);
+                    }
+                }
+            }
+        }
+
+        Ok(subject)
+    }
+
+    /// Return the nodes of a property value.
+    fn values(
+        &mut self,
+        value: &Value,
+        context: &Context,
+        coercion: Option<&str>,
+        graph: Option<&Node>,
+    ) -> Result<Vec<Node>, Error> {
+        let node = match value {
+            Value::Null => return Ok(Vec::new()),
+            Value::Array(values) => {
+                let mut nodes = Vec::new();
+                for value in values {
+                    nodes.extend(self.values(value, context, coercion, graph)?);
+                }
+                return Ok(nodes);
+            }
+            Value::Bool(_) | Value::Number(_) => native_literal(value),
+            Value::String(string) => match coercion {
+                Some("@id") => self.resource(string, context, false)?,
+                Some("@vocab") => self.resource(string, context, true)?,
+                Some(datatype) => {
+                    Node::typed_literal(string, context.expand(datatype, true)?.as_str())
+                }
+                None => match context.language.as_ref() {
+                    Some(language) => Node::lang_literal(string, language),
+                    None => Node::plain_literal(string),
+                },
+            },
+            Value::Object(object) if object.contains_key("@value") => {
+                match value_object(object, context)? {
+                    Some(node) => node,
+                    None => return Ok(Vec::new()),
+                }
+            }
+            Value::Object(object) if object.contains_key("@set") => {
+                return self.values(&object["@set"], context, coercion, graph);
+            }
+            Value::Object(_) => self.node(value, context, graph)?,
+        };
+        Ok(vec![node])
+    }
+
+    /// Expand an `@id` or a type, mapping blank node labels like `_:b0` to blank nodes.
+    fn resource(&mut self, value: &str, context: &Context, vocab: bool) -> Result<Node, Error> {
+        match value.strip_prefix("_:") {
+            Some(label) => Ok(self.blanks.get(label)),
+            None => context.expand(value, vocab),
+        }
+    }
+
+    fn insert(&mut self, graph: Option<&Node>, subject: &Node, predicate: Node, object: Node) {
+        self.dataset
+            .insert_quad(graph.cloned(), subject.clone(), predicate, object);
+    }
+}
+
+/// Return a list of strings, given as a single string or as an array.
+fn strings<'a>(key: &str, value: &'a Value) -> Result<Vec<&'a str>, Error> {
+    let values = match value {
+        Value::Array(values) => values.iter().collect(),
+        value => vec![value],
+    };
+    values
+        .into_iter()
+        .map(|value| {
+            value
+                .as_str()
+                .ok_or_else(|| Error::new(key, format!("expected strings as '{}'", key)))
+        })
+        .collect()
+}
+
+/// Convert a JSON boolean or number to a typed literal.
+fn native_literal(value: &Value) -> Node {
+    match value {
+        Value::Bool(value) => Node::typed_literal(&value.to_string(), &xsd::boolean()),
+        Value::Number(number) if number.is_f64() => {
+            // JSON-LD uses the canonical form of doubles, like `1.5E1`.
+            let lexical_form = format!("{:E}", number.as_f64().unwrap_or(f64::NAN));
+            let lexical_form = match lexical_form.find('E') {
+                Some(index) if !lexical_form.contains('.') => {
+                    format!("{}.0{}", &lexical_form[..index], &lexical_form[index..])
+                }
+                _ => lexical_form,
+            };
+            Node::typed_literal(&lexical_form, &xsd::double())
+        }
+        value => Node::typed_literal(&value.to_string(), &xsd::integer()),
+    }
+}
+
+/// Convert a value object like `{"@value": "chat", "@language": "fr"}` to a literal.
+fn value_object(object: &Map<String, Value>, context: &Context) -> Result<Option<Node>, Error> {
+    if let Some(key) = object
+        .keys()
+        .find(|key| !["@value", "@type", "@language"].contains(&key.as_str()))
+    {
+        return Err(Error::new(
+            key,
+            format!("unexpected key '{}' in a value object", key),
+        ));
+    }
+
+    let lexical_form = match &object["@value"] {
+        Value::Null => return Ok(None),
+        Value::String(string) => string.clone(),
+        value @ Value::Bool(_) | value @ Value::Number(_) => {
+            if object.contains_key("@type") || object.contains_key("@language") {
+                value.to_string()
+            } else {
+                return Ok(Some(native_literal(value)));
+            }
+        }
+        _ => {
+            return Err(Error::new(
+                "@value",
+                "expected a string, a number or a boolean as '@value'".to_owned(),
+            ))
+        }
+    };
+
+    match (object.get("@type"), object.get("@language")) {
+        (Some(_), Some(_)) => Err(Error::new(
+            "@language",
+            "a value object can't have both '@type' and '@language'".to_owned(),
+        )),
+        (Some(Value::String(datatype)), None) => Ok(Some(Node::typed_literal(
+            &lexical_form,
+            context.expand(datatype, true)?.as_str(),
+        ))),
+        (None, Some(Value::String(language))) if is_language_tag(language) => {
+            Ok(Some(Node::lang_literal(&lexical_form, language)))
+        }
+        (Some(_), None) => Err(Error::new(
+            "@type",
+            "expected a string as '@type'".to_owned(),
+        )),
+        (None, Some(language)) => {
+            let language = language.as_str().unwrap_or("@language");
+            Err(Error::new(
+                language,
+                format!("'{}' is not a valid language tag", language),
+            ))
+        }
+        (None, None) => Ok(Some(match context.language.as_ref() {
+            Some(language) => Node::lang_literal(&lexical_form, language),
+            None => Node::plain_literal(&lexical_form),
+        })),
+    }
+}
+
+#[cfg(test)]
+use crate::Graph;
+
+#[test]
+fn test_flattened_document() {
+    let dataset = parse_jsonld(
+        r#"{
+            "@context": {
+                "schema": "http://schema.org/",
+                "name": "schema:name",
+                "knows": {"@id": "schema:knows", "@type": "@id"},
+                "born": {"@id": "schema:birthDate", "@type": "http://www.w3.org/2001/XMLSchema#date"}
+            },
+            "@graph": [
+                {
+                    "@id": "people/alice",
+                    "@type": ["schema:Person", "http://example.org/Admin"],
+                    "name": [{"@value": "Alice", "@language": "en"}, "Alicia"],
+                    "knows": "people/bob",
+                    "born": "1990-01-01",
+                    "schema:age": 30,
+                    "schema:height": 1.7,
+                    "schema:member": true,
+                    "schema:email": null
+                },
+                {"@id": "_:b0", "name": "Blank", "knows": "_:b0"}
+            ]
+        }"#,
+        "http://example.org/base/",
+    )
+    .unwrap();
+
+    let schema = |name: &str| Node::from(format!("http://schema.org/{}", name).as_str());
+    let alice = Node::from("http://example.org/base/people/alice");
+    let graph = dataset.default_graph();
+    assert_eq!(11, graph.len());
+    assert!(graph.contains(&alice, &rdf::type_(), &schema("Person")));
+    assert!(graph.contains(
+        &alice,
+        &rdf::type_(),
+        &Node::from("http://example.org/Admin")
+    ));
+    assert!(graph.contains(&alice, &schema("name"), &Node::lang_literal("Alice", "en")));
+    assert!(graph.contains(&alice, &schema("name"), &Node::from("Alicia")));
+    assert!(graph.contains(
+        &alice,
+        &schema("knows"),
+        &Node::from("http://example.org/base/people/bob")
+    ));
+    assert!(graph.contains(
+        &alice,
+        &schema("birthDate"),
+        &Node::typed_literal("1990-01-01", &xsd::date())
+    ));
+    assert!(graph.contains(
+        &alice,
+        &schema("age"),
+        &Node::typed_literal("30", &xsd::integer())
+    ));
+    assert!(graph.contains(
+        &alice,
+        &schema("height"),
+        &Node::typed_literal("1.7E0", &xsd::double())
+    ));
+    assert!(graph.contains(
+        &alice,
+        &schema("member"),
+        &Node::typed_literal("true", &xsd::boolean())
+    ));
+
+    let blank = graph
+        .iter()
+        .find(|(_, _, o)| o.as_str() == "Blank")
+        .unwrap()
+        .0;
+    assert!(blank.is_blank());
+    assert!(graph.contains(blank, &schema("knows"), blank));
+}
+
+#[test]
+fn test_nested_nodes_and_graphs() {
+    let dataset = parse_jsonld(
+        r#"[
+            {
+                "@context": {"@vocab": "http://schema.org/", "@language": "de"},
+                "@id": "http://example.org/graph",
+                "@graph": {"@id": "http://example.org/carol", "name": "Carol"},
+                "author": {"name": "Dave", "url": {"@id": "http://example.org/dave"}}
+            },
+            {"http://schema.org/count": {"@value": 1.0, "@type": "http://schema.org/Number"}}
+        ]"#,
+        "http://example.org/",
+    )
+    .unwrap();
+
+    let name = Node::from("http://schema.org/name");
+    let graph_name = Node::from("http://example.org/graph");
+    assert_eq!(5, dataset.len());
+    assert!(dataset.contains_quad(
+        Some(&graph_name),
+        &Node::from("http://example.org/carol"),
+        &name,
+        &Node::lang_literal("Carol", "de")
+    ));
+
+    let author_predicate = Node::from("http://schema.org/author");
+    let author = dataset
+        .default_graph()
+        .objects(&graph_name, &author_predicate)
+        .next()
+        .unwrap()
+        .2;
+    assert!(author.is_blank());
+    assert!(dataset.contains_quad(None, author, &name, &Node::lang_literal("Dave", "de")));
+    assert!(dataset.contains_quad(
+        None,
+        author,
+        &Node::from("http://schema.org/url"),
+        &Node::from("http://example.org/dave")
+    ));
+    assert_eq!(
+        1,
+        dataset
+            .iter()
+            .filter(|(_, _, _, o)| o.as_str() == "\"1.0\"^^<http://schema.org/Number>")
+            .count()
+    );
+}
+
+#[test]
+fn test_empty_literal() {
+    let dataset = parse_jsonld(
+        r#"{
+            "@id": "http://example.org/alice",
+            "http://schema.org/name": "",
+            "http://schema.org/alternateName": {"@value": ""}
+        }"#,
+        "http://example.org/",
+    )
+    .unwrap();
+    assert_eq!(2, dataset.len());
+    for (_, _, object) in dataset.default_graph().iter() {
+        assert!(object.is_literal());
+        assert_eq!("", object.lexical_form());
+    }
+}
+
+#[test]
+fn test_unsupported_constructs() {
+    let error = parse_jsonld(
+        "{\n  \"http://example.org/p\": {\"@list\": [1, 2]}\n}",
+        "http://example.org/",
+    )
+    .unwrap_err();
+    assert_eq!((2, 28), (error.line, error.column));
+    assert_eq!(
+        "the JSON-LD keyword '@list' is not supported",
+        error.message
+    );
+
+    let error = parse_jsonld(
+        r#"{"@context": "http://schema.org/", "name": "Alice"}"#,
+        "http://example.org/",
+    )
+    .unwrap_err();
+    assert_eq!(
+        "the remote context 'http://schema.org/' is not supported",
+        error.message
+    );
+
+    let error = parse_jsonld(r#"{"name": "Alice"}"#, "http://example.org/").unwrap_err();
+    assert_eq!((1, 2), (error.line, error.column));
+    assert_eq!("'name' doesn't expand to an IRI", error.message);
+
+    let error = parse_jsonld(
+        r#"{"http://example.org/p": {"@value": "x", "@language": "en", "@type": "urn:t"}}"#,
+        "http://example.org/",
+    )
+    .unwrap_err();
+    assert!(error.message.contains("both '@type' and '@language'"));
+
+    let error = parse_jsonld("{\"@id\": \"x\",\n}", "http://example.org/").unwrap_err();
+    assert_eq!(2, error.line);
+    assert!(!error.message.contains("at line"));
+
+    assert!(parse_jsonld("[1]", "http://example.org/").is_err());
+}
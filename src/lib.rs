@@ -204,15 +204,77 @@
 //! assert!(rust_lv2_maintainers.contains(&janonard));
 //! assert!(rust_lv2_maintainers.contains(&torvalds));
 //! ```
+//!
+//! # Serialization
+//!
+//! With the `serde` feature enabled, [`Node`](struct.Node.html) and [`HashGraph`](struct.HashGraph.html) implement
+//! `Serialize` and `Deserialize`. A node is represented as `{"iri": "..."}`, `{"blank": 0}` or
+//! `{"literal": "...", "datatype": "..."}` and a graph as a list of `[subject, predicate, object]`
+//! triples. Blank nodes are numbered per document, so equal numbers within one serialized graph
+//! are deserialized to the same blank node.
+//!
+//! # Features
+//!
+//! The in-memory graph, the [set operations](set/index.html) and the [transactions](transaction/index.html)
+//! are always available and only depend on `iri-string`. Everything else is opt-in, so that
+//! downstream crates only pull in the dependencies they need:
+//!
+//! | Feature            | Enables                                                                                     | Dependencies       |
+//! |--------------------|---------------------------------------------------------------------------------------------|--------------------|
+//! | `ntriples`         | The [`ntriples`](ntriples/index.html) parser and serializer                                 | `nom`              |
+//! | `nquads`           | The [`nquads`](nquads/index.html) parser and serializer                                     | `nom`              |
+//! | `turtle`           | The [`turtle`](turtle/index.html) parser and serializer                                     | `nom`              |
+//! | `jsonld`           | The [`jsonld`](jsonld/index.html) parser                                                    | `serde_json`       |
+//! | `serde`            | `Serialize` and `Deserialize` for nodes and graphs                                          | `serde`            |
+//! | `snapshot`         | [Snapshots](transaction/struct.TransactionGraph.html#method.snapshot) of transaction graphs | `serde`, `bincode` |
+//! | `canonicalization` | [`Dataset::canonicalize`](struct.Dataset.html#method.canonicalize)                          | `sha2`             |
+//! | `petgraph`         | Conversions from and to [`petgraph`](https://docs.rs/petgraph) graphs                       | `petgraph`         |
+//! | `rayon`            | Parallel iterators over the triples of a graph                                              | `rayon`            |
+//!
+//! No feature is enabled by default. The `nquads` feature also enables `ntriples`, and the
+//! `snapshot` feature also enables `serde`.
+mod blank_node_allocator;
+#[cfg(feature = "canonicalization")]
+mod canonicalization;
+mod counting_graph;
+mod dataset;
+pub mod diff;
+mod error;
 mod graph;
 mod hash_graph;
+pub mod iso;
+#[cfg(feature = "jsonld")]
+pub mod jsonld;
+mod macros;
 mod node;
+#[cfg(feature = "nquads")]
+pub mod nquads;
+#[cfg(feature = "ntriples")]
+pub mod ntriples;
+mod prefix_map;
+#[cfg(feature = "serde")]
+mod serialization;
 pub mod set;
+#[cfg(feature = "nom")]
+mod syntax;
 pub mod transaction;
+#[cfg(feature = "turtle")]
+pub mod turtle;
+pub mod util;
+pub mod vocab;
 
+pub use blank_node_allocator::BlankNodeAllocator;
+pub use counting_graph::CountingGraph;
+pub use dataset::Dataset;
+pub use error::ParseError;
 pub use graph::Graph;
-pub use hash_graph::HashGraph;
-pub use node::Node;
+#[cfg(feature = "ntriples")]
+pub use hash_graph::RoundtripError;
+pub use hash_graph::{
+    GraphBuilder, GraphMetrics, HashGraph, LiteralLengthError, ObjectsEntry, ValidationIssue,
+};
+pub use node::{LiteralBuilder, LiteralError, Node, NodeError, NodeKind, ValueError};
+pub use prefix_map::PrefixMap;
 
 #[cfg(test)]
 mod validator;
@@ -0,0 +1,83 @@
+/// Create a [`HashGraph`](struct.HashGraph.html) from a list of triples.
+///
+/// Every triple is written as `subject -> predicate -> object` and triples are separated by
+/// semicolons. A node can either be an identifier, which is turned into a node with the same
+/// name, a string literal, or an expression in parentheses that evaluates to a `Node` or a
+/// `&Node`, which is cloned into the graph.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{graph, Node, Graph};
+///
+/// let janonard = Node::from("https://github.com/Janonard");
+/// let graph = graph! {
+///     rust_lv2 -> maintainer -> (janonard);
+///     rust_lv2 -> "https://schema.org/programmingLanguage" -> "https://www.rust-lang.org/";
+/// };
+///
+/// assert_eq!(2, graph.len());
+/// assert!(graph.contains(
+///     &Node::from("rust_lv2"),
+///     &Node::from("maintainer"),
+///     &janonard,
+/// ));
+/// ```
+#[macro_export]
+macro_rules! graph {
+    (@node $node:ident) => {
+        $crate::Node::from(stringify!($node))
+    };
+    (@node $node:literal) => {
+        $crate::Node::from($node)
+    };
+    (@node ($node:expr)) => {
+        $crate::Node::clone(&$node)
+    };
+    ($($subject:tt -> $predicate:tt -> $object:tt);* $(;)?) => {{
+        #[allow(unused_mut)]
+        let mut graph = $crate::HashGraph::new();
+        $(
+            $crate::Graph::insert(
+                &mut graph,
+                $crate::graph!(@node $subject),
+                $crate::graph!(@node $predicate),
+                $crate::graph!(@node $object),
+            );
+        )*
+        graph
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn graph_macro() {
+        let validator = Validator::new(HashGraph::new());
+        let node_a = &validator.node_a;
+        let node_b = &validator.node_b;
+        let node_c = &validator.node_c;
+        let predicate_a = &validator.predicate_a;
+        let predicate_b = &validator.predicate_b;
+        let predicate_c = &validator.predicate_c;
+
+        let graph = graph! {
+            (node_a) -> (predicate_a) -> (node_b);
+            (node_b) -> (predicate_b) -> (node_c);
+            (node_c) -> (predicate_c) -> (validator.node_a);
+        };
+        assert_eq!(validator.graph, graph);
+
+        let graph = graph! {
+            a -> b -> c;
+            x -> "y" -> (node_c)
+        };
+        assert_eq!(2, graph.len());
+        assert!(graph.contains(&Node::from("a"), &Node::from("b"), &Node::from("c")));
+        assert!(graph.contains(&Node::from("x"), &Node::from("y"), node_c));
+
+        assert!(graph! {}.is_empty());
+    }
+}
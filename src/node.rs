@@ -1,7 +1,27 @@
+use crate::vocab::xsd;
 use iri_string::spec::IriSpec;
+use iri_string::types::{IriAbsoluteStr, IriReferenceStr};
 use iri_string::validate::iri;
+use std::convert::TryFrom;
+use std::fmt;
+use std::num::IntErrorKind;
 use std::sync::Arc;
 
+/// The datatypes of literals that are converted to integers.
+pub(crate) const INTEGER_DATATYPES: &[fn() -> Node] =
+    &[xsd::integer, xsd::long, xsd::int, xsd::non_negative_integer];
+
+/// The datatypes of literals that are converted to floating-point numbers.
+pub(crate) const FLOAT_DATATYPES: &[fn() -> Node] = &[xsd::decimal, xsd::double, xsd::float];
+
+/// The kind of a node, as returned by [`Node::kind`](struct.Node.html#method.kind).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum NodeKind {
+    Iri,
+    Literal,
+    Blank,
+}
+
 #[derive(Clone, Eq)]
 pub struct Node {
     referent: Arc<str>,
@@ -25,6 +45,28 @@ impl std::cmp::PartialEq for Node {
     }
 }
 
+/// Nodes are ordered by their strings, and blank nodes are ordered after all other nodes.
+///
+/// Blank nodes are ordered by the address of their shared string among each other, which is
+/// consistent with their equality but may differ between runs of a program.
+impl std::cmp::Ord for Node {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        match (self.is_blank(), other.is_blank()) {
+            (true, true) => self.referent.as_ptr().cmp(&other.referent.as_ptr()),
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.as_str().cmp(other.as_str()),
+        }
+    }
+}
+
+impl std::cmp::PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl std::hash::Hash for Node {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         if self.is_blank() {
@@ -61,6 +103,17 @@ impl Node {
         }
     }
 
+    /// Return whether the node is an IRI, a literal or a blank node.
+    pub fn kind(&self) -> NodeKind {
+        if self.is_blank() {
+            NodeKind::Blank
+        } else if self.is_iri() {
+            NodeKind::Iri
+        } else {
+            NodeKind::Literal
+        }
+    }
+
     pub fn is_blank(&self) -> bool {
         self.referent.is_empty()
     }
@@ -73,6 +126,223 @@ impl Node {
         !self.is_iri()
     }
 
+    /// Return `true` if both nodes are IRIs that only differ in their fragments.
+    ///
+    /// This is useful to group resources by the document they are defined in. Blank nodes and literals
+    /// are never in the same document as another node.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::Node;
+    ///
+    /// let a = Node::from("http://example.org/doc#a");
+    /// let b = Node::from("http://example.org/doc#b");
+    /// let doc = Node::from("http://example.org/doc");
+    /// assert!(a.same_document(&b));
+    /// assert!(a.same_document(&doc));
+    /// ```
+    pub fn same_document(&self, other: &Node) -> bool {
+        // A valid IRI contains at most one '#', which starts the fragment.
+        self.is_iri()
+            && other.is_iri()
+            && self.as_str().split('#').next() == other.as_str().split('#').next()
+    }
+
+    /// Resolve the node as an IRI reference against a base IRI.
+    ///
+    /// Relative references like `foo` or `../foo` aren't valid IRIs and are therefore treated as
+    /// literals by arrdf. This method resolves them as described in
+    /// [RFC 3986](https://tools.ietf.org/html/rfc3986#section-5.2), including the removal of `.`
+    /// and `..` segments. The fragment of the base IRI is ignored, and an absolute IRI resolves to
+    /// an equal node.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::Node;
+    ///
+    /// let base = "http://example.org/dir/doc";
+    /// assert_eq!(
+    ///     Node::from("http://example.org/dir/foo"),
+    ///     Node::from("foo").resolve_relative(base).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     Node::from("http://example.org/bar"),
+    ///     Node::from("../bar").resolve_relative(base).unwrap()
+    /// );
+    /// assert!(Node::from("foo").resolve_relative("not a base").is_err());
+    /// ```
+    pub fn resolve_relative(&self, base: &str) -> Result<Node, NodeError> {
+        if self.is_blank() {
+            return Err(NodeError::Blank);
+        }
+        resolve_reference(self.as_str(), base)
+    }
+
+    /// Create a literal node with an explicit datatype.
+    ///
+    /// Typed literals are stored in their N-Triples form, e.g. `"42"^^<http://www.w3.org/2001/XMLSchema#integer>`,
+    /// so two typed literals are equal if and only if their lexical forms and datatypes are equal.
+    /// Since every simple literal is an `xsd:string` in RDF 1.1, literals with this datatype are
+    /// stored as plain literals, just like `Node::from` would create them. The only exception is the
+    /// empty string, which `Node::from` would turn into a blank node.
+    pub fn typed_literal(lexical_form: &str, datatype: &str) -> Self {
+        if datatype == xsd::string().as_str() {
+            Self::plain_literal(lexical_form)
+        } else {
+            Self::from(format!("\"{}\"^^<{}>", lexical_form, datatype).as_str())
+        }
+    }
+
+    /// Create a plain literal node, i.e. a literal with the datatype `xsd:string`.
+    ///
+    /// Plain literals are stored as their lexical form, except for the empty string, which is the
+    /// representation of blank nodes. An empty plain literal is stored as `""^^<xsd:string>`
+    /// instead, so that it stays a literal.
+    pub(crate) fn plain_literal(lexical_form: &str) -> Self {
+        if lexical_form.is_empty() {
+            Self::from(format!("\"\"^^<{}>", xsd::string().as_str()).as_str())
+        } else {
+            Self::from(lexical_form)
+        }
+    }
+
+    /// Start building a literal node with the given lexical form.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{vocab::xsd, Node};
+    ///
+    /// let answer = Node::literal("42").with_datatype(xsd::integer()).build().unwrap();
+    /// assert_eq!(Node::typed_literal("42", xsd::integer().as_str()), answer);
+    ///
+    /// let cat = Node::literal("chat").with_language("fr").build().unwrap();
+    /// assert_eq!(Some("fr"), cat.language());
+    /// assert_eq!("chat", cat.lexical_form());
+    ///
+    /// assert!(Node::literal("chat")
+    ///     .with_datatype(xsd::string())
+    ///     .with_language("fr")
+    ///     .build()
+    ///     .is_err());
+    /// ```
+    pub fn literal(lexical_form: &str) -> LiteralBuilder {
+        LiteralBuilder {
+            lexical_form: lexical_form.to_owned(),
+            datatype: None,
+            language: None,
+        }
+    }
+
+    /// Create a language-tagged literal without checking the language tag.
+    pub(crate) fn lang_literal(lexical_form: &str, language: &str) -> Self {
+        Self::from(format!("\"{}\"@{}", lexical_form, language.to_ascii_lowercase()).as_str())
+    }
+
+    /// Return the datatype IRI of a typed literal.
+    ///
+    /// Plain literals, language-tagged literals, IRIs and blank nodes don't have an explicit
+    /// datatype and therefore return `None`.
+    pub fn datatype(&self) -> Option<&str> {
+        self.split_typed_literal().map(|(_, datatype)| datatype)
+    }
+
+    /// Return the language tag of a language-tagged literal.
+    ///
+    /// Language-tagged literals are stored in their N-Triples form, e.g. `"chat"@fr`. All other
+    /// nodes return `None`.
+    pub fn language(&self) -> Option<&str> {
+        self.split_lang_literal().map(|(_, language)| language)
+    }
+
+    /// Return the lexical form of the node.
+    ///
+    /// For typed and language-tagged literals, this is the part without the quotes and the
+    /// datatype or language tag. For all other nodes, this is the same as [`as_str`](#method.as_str).
+    pub fn lexical_form(&self) -> &str {
+        self.split_typed_literal()
+            .or_else(|| self.split_lang_literal())
+            .map(|(lexical_form, _)| lexical_form)
+            .unwrap_or_else(|| self.as_str())
+    }
+
+    fn split_lang_literal(&self) -> Option<(&str, &str)> {
+        let inner = self.as_str().strip_prefix('"')?;
+        let separator = inner.rfind("\"@")?;
+        let language = &inner[separator + 2..];
+        if is_language_tag(language) {
+            Some((&inner[..separator], language))
+        } else {
+            None
+        }
+    }
+
+    fn split_typed_literal(&self) -> Option<(&str, &str)> {
+        let inner = self.as_str().strip_prefix('"')?.strip_suffix('>')?;
+        let separator = inner.rfind("\"^^<")?;
+        Some((&inner[..separator], &inner[separator + 4..]))
+    }
+
+    /// Compare the values of two numeric typed literals.
+    ///
+    /// Literals with the datatypes `xsd:integer`, `xsd:long`, `xsd:int`,
+    /// `xsd:nonNegativeInteger`, `xsd:decimal`, `xsd:double` and `xsd:float` are compared by their
+    /// numeric values, so `"10"` is greater than `"9"` and `"1.50"` is equal to `"1.5"`. If one
+    /// of the nodes isn't a numeric literal or its lexical form isn't valid, the comparison isn't
+    /// well-defined and `None` is returned. Integers are compared exactly, all other combinations
+    /// as floating-point numbers.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::{vocab::xsd, Node};
+    /// use std::cmp::Ordering;
+    ///
+    /// let nine = Node::typed_literal("9", xsd::integer().as_str());
+    /// let ten = Node::typed_literal("10", xsd::integer().as_str());
+    /// let half = Node::typed_literal("0.5", xsd::decimal().as_str());
+    /// assert_eq!(Some(Ordering::Less), nine.compare_values(&ten));
+    /// assert_eq!(Some(Ordering::Greater), nine.compare_values(&half));
+    /// assert_eq!(None, nine.compare_values(&Node::from("10")));
+    /// ```
+    pub fn compare_values(&self, other: &Node) -> Option<std::cmp::Ordering> {
+        match (self.numeric_value()?, other.numeric_value()?) {
+            (Numeric::Integer(a), Numeric::Integer(b)) => Some(a.cmp(&b)),
+            (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+        }
+    }
+
+    fn numeric_value(&self) -> Option<Numeric> {
+        let (lexical_form, datatype) = self.split_typed_literal()?;
+        let lexical_form = lexical_form.trim();
+        if has_datatype(datatype, INTEGER_DATATYPES) {
+            lexical_form.parse().ok().map(Numeric::Integer)
+        } else if has_datatype(datatype, FLOAT_DATATYPES) {
+            parse_float(lexical_form, datatype).map(Numeric::Float)
+        } else {
+            None
+        }
+    }
+
+    /// Return the trimmed lexical form and the datatype of a literal with one of the datatypes.
+    fn typed_value(
+        &self,
+        datatypes: &[fn() -> Node],
+        expected: &'static str,
+    ) -> Result<(&str, &str), ValueError> {
+        match self.split_typed_literal() {
+            Some((lexical_form, datatype)) if has_datatype(datatype, datatypes) => {
+                Ok((lexical_form.trim(), datatype))
+            }
+            split => Err(ValueError::Datatype {
+                expected,
+                datatype: split.map(|(_, datatype)| datatype.to_owned()),
+            }),
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         self.referent.as_ref()
     }
@@ -83,9 +353,326 @@ impl Node {
     }
 }
 
+/// Return `true` if the tag matches the `LANGTAG` production of N-Triples, e.g. `en` or `de-CH`.
+pub(crate) fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or("");
+    !primary.is_empty()
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags
+            .all(|subtag| !subtag.is_empty() && subtag.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// A builder for literal nodes, created by [`Node::literal`](struct.Node.html#method.literal).
+///
+/// A literal may either have a datatype or a language tag, but not both. This is checked by
+/// [`build`](#method.build).
+#[derive(Clone, Debug)]
+pub struct LiteralBuilder {
+    lexical_form: String,
+    datatype: Option<Node>,
+    language: Option<String>,
+}
+
+impl LiteralBuilder {
+    /// Set the datatype of the literal.
+    pub fn with_datatype(mut self, datatype: Node) -> Self {
+        self.datatype = Some(datatype);
+        self
+    }
+
+    /// Set the language tag of the literal.
+    ///
+    /// Language tags are case-insensitive, so they're stored in lower case.
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = Some(language.to_ascii_lowercase());
+        self
+    }
+
+    /// Create the literal node.
+    ///
+    /// An `xsd:string` literal is the same as a literal without a datatype, just like with
+    /// [`Node::typed_literal`](struct.Node.html#method.typed_literal).
+    pub fn build(self) -> Result<Node, LiteralError> {
+        match (self.datatype, self.language) {
+            (Some(_), Some(_)) => Err(LiteralError::DatatypeAndLanguage),
+            (Some(datatype), None) if !datatype.is_iri() => {
+                Err(LiteralError::InvalidDatatype(datatype))
+            }
+            (Some(datatype), None) => {
+                Ok(Node::typed_literal(&self.lexical_form, datatype.as_str()))
+            }
+            (None, Some(language)) if !is_language_tag(&language) => {
+                Err(LiteralError::InvalidLanguageTag(language))
+            }
+            (None, Some(language)) => Ok(Node::lang_literal(&self.lexical_form, &language)),
+            (None, None) => Ok(Node::plain_literal(&self.lexical_form)),
+        }
+    }
+}
+
+/// The error returned by [`LiteralBuilder::build`](struct.LiteralBuilder.html#method.build) for
+/// an invalid literal.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LiteralError {
+    /// Both a datatype and a language tag were set, which isn't allowed in RDF.
+    DatatypeAndLanguage,
+    /// The datatype isn't an IRI.
+    InvalidDatatype(Node),
+    /// The language tag isn't well-formed.
+    InvalidLanguageTag(String),
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LiteralError::DatatypeAndLanguage => {
+                f.write_str("a literal can't have both a datatype and a language tag")
+            }
+            LiteralError::InvalidDatatype(datatype) => {
+                write!(f, "the datatype {:?} is not an IRI", datatype.as_str())
+            }
+            LiteralError::InvalidLanguageTag(language) => {
+                write!(f, "'{}' is not a valid language tag", language)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+/// Escape the lexical form of a literal for the string syntax of N-Triples and Turtle.
+#[cfg(any(feature = "nom", feature = "canonicalization"))]
+pub(crate) fn escape(literal: &str) -> String {
+    let mut escaped = String::with_capacity(literal.len());
+    for c in literal.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Resolve an IRI reference against a base IRI, like [`Node::resolve_relative`](struct.Node.html#method.resolve_relative).
+///
+/// In contrast to nodes, the reference may be empty, which resolves to the base IRI without its
+/// fragment.
+pub(crate) fn resolve_reference(reference: &str, base: &str) -> Result<Node, NodeError> {
+    let iri_reference = IriReferenceStr::new(reference)
+        .map_err(|_| NodeError::InvalidReference(reference.to_owned()))?;
+    let absolute_base = base.split('#').next().unwrap_or(base);
+    let absolute_base =
+        IriAbsoluteStr::new(absolute_base).map_err(|_| NodeError::InvalidBase(base.to_owned()))?;
+    Ok(Node::from(
+        iri_reference.resolve_against(absolute_base).as_str(),
+    ))
+}
+
+/// The error returned by [`Node::resolve_relative`](struct.Node.html#method.resolve_relative).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum NodeError {
+    /// Blank nodes can't be resolved against a base IRI.
+    Blank,
+    /// The node isn't a valid IRI reference.
+    InvalidReference(String),
+    /// The base isn't a valid IRI.
+    InvalidBase(String),
+}
+
+impl fmt::Display for NodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NodeError::Blank => f.write_str("a blank node is not an IRI reference"),
+            NodeError::InvalidReference(reference) => {
+                write!(f, "'{}' is not a valid IRI reference", reference)
+            }
+            NodeError::InvalidBase(base) => write!(f, "'{}' is not a valid base IRI", base),
+        }
+    }
+}
+
+impl std::error::Error for NodeError {}
+
+/// Return `true` if the datatype IRI is one of the given datatypes.
+pub(crate) fn has_datatype(datatype: &str, datatypes: &[fn() -> Node]) -> bool {
+    datatypes.iter().any(|term| datatype == term().as_str())
+}
+
+/// Parse the lexical form of an `xsd:decimal`, `xsd:double` or `xsd:float`.
+fn parse_float(lexical_form: &str, datatype: &str) -> Option<f64> {
+    match lexical_form {
+        _ if datatype == xsd::decimal().as_str() => {
+            // Unlike Rust, decimals don't allow exponents or special values like `inf`.
+            if lexical_form.contains(|c: char| c.is_ascii_alphabetic()) {
+                None
+            } else {
+                lexical_form.parse().ok()
+            }
+        }
+        "INF" | "+INF" => Some(f64::INFINITY),
+        "-INF" => Some(f64::NEG_INFINITY),
+        "NaN" => Some(f64::NAN),
+        _ if lexical_form.contains(|c: char| c.is_ascii_alphabetic() && c != 'e' && c != 'E') => {
+            None
+        }
+        _ => lexical_form.parse().ok(),
+    }
+}
+
+/// The error returned by the conversions of typed literals into Rust values, like
+/// `i64::try_from(&node)`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ValueError {
+    /// The node isn't a literal with one of the datatypes of the requested value.
+    Datatype {
+        /// A description of the expected datatypes.
+        expected: &'static str,
+        /// The datatype of the node, if it's a typed literal.
+        datatype: Option<String>,
+    },
+    /// The lexical form isn't a valid value of the datatype.
+    InvalidLexicalForm {
+        lexical_form: String,
+        datatype: String,
+    },
+    /// The value is valid, but can't be represented by the requested type.
+    OutOfRange {
+        lexical_form: String,
+        datatype: String,
+    },
+}
+
+impl ValueError {
+    fn invalid(lexical_form: &str, datatype: &str) -> Self {
+        ValueError::InvalidLexicalForm {
+            lexical_form: lexical_form.to_owned(),
+            datatype: datatype.to_owned(),
+        }
+    }
+
+    fn out_of_range(lexical_form: &str, datatype: &str) -> Self {
+        ValueError::OutOfRange {
+            lexical_form: lexical_form.to_owned(),
+            datatype: datatype.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValueError::Datatype {
+                expected,
+                datatype: Some(datatype),
+            } => write!(
+                f,
+                "expected a literal with {} datatype, found <{}>",
+                expected, datatype
+            ),
+            ValueError::Datatype {
+                expected,
+                datatype: None,
+            } => write!(
+                f,
+                "expected a literal with {} datatype, found a node without datatype",
+                expected
+            ),
+            ValueError::InvalidLexicalForm {
+                lexical_form,
+                datatype,
+            } => write!(f, "'{}' is not a valid <{}>", lexical_form, datatype),
+            ValueError::OutOfRange {
+                lexical_form,
+                datatype,
+            } => write!(f, "<{}> '{}' is out of range", datatype, lexical_form),
+        }
+    }
+}
+
+impl std::error::Error for ValueError {}
+
+/// Convert a literal with an integer datatype, like `xsd:integer` or `xsd:long`.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{vocab::xsd, Node};
+/// use std::convert::TryFrom;
+///
+/// let answer = Node::typed_literal("42", xsd::integer().as_str());
+/// assert_eq!(Ok(42), i64::try_from(&answer));
+/// assert!(i64::try_from(&Node::from("42")).is_err());
+/// ```
+impl TryFrom<&Node> for i64 {
+    type Error = ValueError;
+
+    fn try_from(node: &Node) -> Result<Self, ValueError> {
+        let (lexical_form, datatype) = node.typed_value(INTEGER_DATATYPES, "an integer")?;
+        lexical_form
+            .parse()
+            .map_err(|error: std::num::ParseIntError| match error.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                    ValueError::out_of_range(lexical_form, datatype)
+                }
+                _ => ValueError::invalid(lexical_form, datatype),
+            })
+    }
+}
+
+/// Convert a literal with the datatype `xsd:double`, `xsd:float` or `xsd:decimal`.
+impl TryFrom<&Node> for f64 {
+    type Error = ValueError;
+
+    fn try_from(node: &Node) -> Result<Self, ValueError> {
+        let (lexical_form, datatype) =
+            node.typed_value(FLOAT_DATATYPES, "a floating-point or decimal")?;
+        match parse_float(lexical_form, datatype) {
+            Some(value) if value.is_infinite() && !lexical_form.ends_with("INF") => {
+                Err(ValueError::out_of_range(lexical_form, datatype))
+            }
+            Some(value) => Ok(value),
+            None => Err(ValueError::invalid(lexical_form, datatype)),
+        }
+    }
+}
+
+/// Convert a literal with the datatype `xsd:boolean`.
+impl TryFrom<&Node> for bool {
+    type Error = ValueError;
+
+    fn try_from(node: &Node) -> Result<Self, ValueError> {
+        let (lexical_form, datatype) = node.typed_value(&[xsd::boolean], "a boolean")?;
+        match lexical_form {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(ValueError::invalid(lexical_form, datatype)),
+        }
+    }
+}
+
+/// The value of a numeric literal, as used by [`Node::compare_values`](struct.Node.html#method.compare_values).
+#[derive(Clone, Copy)]
+enum Numeric {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Numeric {
+    fn as_f64(self) -> f64 {
+        match self {
+            Numeric::Integer(value) => value as f64,
+            Numeric::Float(value) => value,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Node;
+    use crate::{Node, NodeKind};
     use std::collections::HashMap;
 
     #[test]
@@ -144,4 +731,281 @@ mod tests {
             format!("{:?}", node)
         );
     }
+
+    #[test]
+    fn node_kind() {
+        assert_eq!(NodeKind::Blank, Node::blank().kind());
+        assert_eq!(NodeKind::Iri, Node::from("http://example.org/").kind());
+        assert_eq!(NodeKind::Literal, Node::from("Hello").kind());
+    }
+
+    #[test]
+    fn node_ordering() {
+        let blank_a = Node::blank();
+        let blank_b = Node::blank();
+        let mut nodes = [
+            blank_a.clone(),
+            Node::from("urn:b"),
+            blank_b.clone(),
+            Node::from("Hello"),
+            Node::from("urn:a"),
+        ];
+        nodes.sort();
+
+        assert_eq!(Node::from("Hello"), nodes[0]);
+        assert_eq!(Node::from("urn:a"), nodes[1]);
+        assert_eq!(Node::from("urn:b"), nodes[2]);
+        assert!(nodes[3].is_blank() && nodes[4].is_blank());
+        assert_ne!(nodes[3], nodes[4]);
+        assert_eq!(std::cmp::Ordering::Equal, blank_a.cmp(&blank_a.clone()));
+        assert_ne!(std::cmp::Ordering::Equal, blank_a.cmp(&blank_b));
+    }
+
+    #[test]
+    fn same_document() {
+        let a = Node::from("http://x#a");
+        let b = Node::from("http://x#b");
+        assert!(a.same_document(&b));
+        assert!(a.same_document(&a));
+        assert!(Node::from("http://x").same_document(&a));
+        assert!(!Node::from("http://x").same_document(&Node::from("http://y")));
+        assert!(!Node::from("http://x#a").same_document(&Node::from("http://y#a")));
+
+        let blank = Node::blank();
+        assert!(!blank.same_document(&blank));
+        assert!(!Node::from("Hello").same_document(&Node::from("Hello")));
+    }
+
+    #[test]
+    fn resolve_relative() {
+        use crate::NodeError;
+
+        let base = "http://ex/a/b/c#frag";
+        let resolve = |reference: &str| {
+            Node::from(reference)
+                .resolve_relative(base)
+                .unwrap()
+                .as_str()
+                .to_owned()
+        };
+        assert_eq!("http://ex/a/b/foo", resolve("foo"));
+        assert_eq!("http://ex/a/b/", resolve("."));
+        assert_eq!("http://ex/a/b/", resolve("./"));
+        assert_eq!("http://ex/a/b/foo", resolve("./foo"));
+        assert_eq!("http://ex/a/", resolve(".."));
+        assert_eq!("http://ex/a/foo", resolve("../foo"));
+        assert_eq!("http://ex/foo", resolve("../../foo"));
+        assert_eq!("http://ex/foo", resolve("../../../foo"));
+        assert_eq!("http://ex/a/foo", resolve("./../b/./../foo"));
+        assert_eq!("http://ex/foo", resolve("/foo"));
+        assert_eq!("http://ex/a/b/c#other", resolve("#other"));
+        assert_eq!("http://ex/a/b/c?q", resolve("?q"));
+        assert_eq!(
+            "http://ex/a/b/c",
+            super::resolve_reference("", base).unwrap().as_str()
+        );
+
+        let absolute = Node::from("http://other/x/../y");
+        assert_eq!(absolute, absolute.resolve_relative(base).unwrap());
+
+        assert_eq!(Err(NodeError::Blank), Node::blank().resolve_relative(base));
+        assert_eq!(
+            Err(NodeError::InvalidReference("a b".to_owned())),
+            Node::from("a b").resolve_relative(base)
+        );
+        assert_eq!(
+            Err(NodeError::InvalidBase("foo".to_owned())),
+            Node::from("bar").resolve_relative("foo")
+        );
+    }
+
+    #[test]
+    fn typed_literals() {
+        let integer = "http://www.w3.org/2001/XMLSchema#integer";
+        let node = Node::typed_literal("42", integer);
+        assert_eq!(
+            "\"42\"^^<http://www.w3.org/2001/XMLSchema#integer>",
+            node.as_str()
+        );
+        assert_eq!(Some(integer), node.datatype());
+        assert_eq!("42", node.lexical_form());
+        assert!(node.is_literal());
+        assert_eq!(node, Node::typed_literal("42", integer));
+        assert_ne!(node, Node::from("42"));
+
+        let node = Node::typed_literal("Hello", "http://www.w3.org/2001/XMLSchema#string");
+        assert_eq!(Node::from("Hello"), node);
+        assert_eq!(None, node.datatype());
+        assert_eq!("Hello", node.lexical_form());
+    }
+
+    #[test]
+    fn value_comparison() {
+        use crate::vocab::xsd;
+        use std::cmp::Ordering;
+
+        let literal =
+            |value: &str, datatype: fn() -> Node| Node::typed_literal(value, datatype().as_str());
+        let nine = literal("9", xsd::integer);
+        let ten = literal("10", xsd::int);
+        assert!(ten < nine);
+        assert_eq!(Some(Ordering::Less), nine.compare_values(&ten));
+        assert_eq!(Some(Ordering::Greater), ten.compare_values(&nine));
+        assert_eq!(Some(Ordering::Equal), nine.compare_values(&nine));
+
+        // Integers beyond the precision of floats are still compared exactly.
+        let big = literal("9007199254740993", xsd::long);
+        let bigger = literal("9007199254740994", xsd::integer);
+        assert_eq!(Some(Ordering::Less), big.compare_values(&bigger));
+
+        assert_eq!(
+            Some(Ordering::Equal),
+            literal("1.50", xsd::decimal).compare_values(&literal("1.5", xsd::decimal))
+        );
+        assert_eq!(
+            Some(Ordering::Less),
+            literal("-2", xsd::integer).compare_values(&literal("-1.5e0", xsd::double))
+        );
+        assert_eq!(
+            Some(Ordering::Greater),
+            literal("INF", xsd::float).compare_values(&ten)
+        );
+
+        // Comparisons with non-numeric or invalid values aren't defined.
+        let nan = literal("NaN", xsd::double);
+        assert_eq!(None, nan.compare_values(&nan));
+        assert_eq!(None, nine.compare_values(&Node::from("10")));
+        assert_eq!(None, nine.compare_values(&literal("10", xsd::string)));
+        assert_eq!(None, nine.compare_values(&literal("true", xsd::boolean)));
+        assert_eq!(None, nine.compare_values(&literal("ten", xsd::integer)));
+        assert_eq!(None, nine.compare_values(&literal("1.5", xsd::integer)));
+        assert_eq!(None, nine.compare_values(&literal("1e5", xsd::decimal)));
+        assert_eq!(None, nine.compare_values(&Node::blank()));
+        assert_eq!(None, nine.compare_values(&xsd::integer()));
+    }
+
+    #[test]
+    fn value_conversion() {
+        use crate::vocab::xsd;
+        use crate::ValueError;
+        use std::convert::TryFrom;
+
+        let literal =
+            |value: &str, datatype: fn() -> Node| Node::typed_literal(value, datatype().as_str());
+        assert_eq!(Ok(-42), i64::try_from(&literal(" -42 ", xsd::integer)));
+        assert_eq!(
+            Ok(7),
+            i64::try_from(&literal("+7", xsd::non_negative_integer))
+        );
+        assert_eq!(Ok(1.5), f64::try_from(&literal("1.50", xsd::decimal)));
+        assert_eq!(Ok(-1500.0), f64::try_from(&literal("-1.5E3", xsd::double)));
+        assert_eq!(
+            Ok(f64::NEG_INFINITY),
+            f64::try_from(&literal("-INF", xsd::float))
+        );
+        assert!(f64::try_from(&literal("NaN", xsd::double))
+            .unwrap()
+            .is_nan());
+        assert_eq!(Ok(true), bool::try_from(&literal("true", xsd::boolean)));
+        assert_eq!(Ok(false), bool::try_from(&literal("0", xsd::boolean)));
+
+        let error = i64::try_from(&literal("42", xsd::string)).unwrap_err();
+        assert_eq!(
+            ValueError::Datatype {
+                expected: "an integer",
+                datatype: None
+            },
+            error
+        );
+        assert_eq!(
+            "expected a literal with an integer datatype, found a node without datatype",
+            error.to_string()
+        );
+        let error = i64::try_from(&literal("1.5", xsd::decimal)).unwrap_err();
+        assert_eq!(
+            "expected a literal with an integer datatype, found <http://www.w3.org/2001/XMLSchema#decimal>",
+            error.to_string()
+        );
+        assert!(bool::try_from(&xsd::boolean()).is_err());
+        assert!(f64::try_from(&Node::blank()).is_err());
+
+        let error = i64::try_from(&literal("12345678901234567890", xsd::integer)).unwrap_err();
+        assert_eq!(
+            "<http://www.w3.org/2001/XMLSchema#integer> '12345678901234567890' is out of range",
+            error.to_string()
+        );
+        assert!(matches!(
+            f64::try_from(&literal("1e400", xsd::double)),
+            Err(ValueError::OutOfRange { .. })
+        ));
+
+        let error = bool::try_from(&literal("yes", xsd::boolean)).unwrap_err();
+        assert_eq!(
+            "'yes' is not a valid <http://www.w3.org/2001/XMLSchema#boolean>",
+            error.to_string()
+        );
+        let invalid = |result: Result<(), ValueError>| {
+            matches!(result, Err(ValueError::InvalidLexicalForm { .. }))
+        };
+        assert!(invalid(
+            i64::try_from(&literal("1.5", xsd::integer)).map(|_| ())
+        ));
+        assert!(invalid(
+            f64::try_from(&literal("inf", xsd::double)).map(|_| ())
+        ));
+        assert!(invalid(
+            f64::try_from(&literal("1e3", xsd::decimal)).map(|_| ())
+        ));
+        assert!(invalid(f64::try_from(&literal("", xsd::float)).map(|_| ())));
+    }
+
+    #[test]
+    fn literal_builder() {
+        use crate::vocab::xsd;
+        use crate::LiteralError;
+
+        assert_eq!(Ok(Node::from("Hello")), Node::literal("Hello").build());
+        assert_eq!(
+            Ok(Node::from("Hello")),
+            Node::literal("Hello").with_datatype(xsd::string()).build()
+        );
+        let answer = Node::literal("42")
+            .with_datatype(xsd::integer())
+            .build()
+            .unwrap();
+        assert_eq!(Some(xsd::integer().as_str()), answer.datatype());
+        assert_eq!(None, answer.language());
+
+        let node = Node::literal("Grüezi")
+            .with_language("de-CH")
+            .build()
+            .unwrap();
+        assert_eq!("\"Grüezi\"@de-ch", node.as_str());
+        assert_eq!(Some("de-ch"), node.language());
+        assert_eq!(None, node.datatype());
+        assert_eq!("Grüezi", node.lexical_form());
+        assert!(node.is_literal());
+
+        assert_eq!(
+            Err(LiteralError::DatatypeAndLanguage),
+            Node::literal("chat")
+                .with_language("fr")
+                .with_datatype(xsd::string())
+                .build()
+        );
+        assert_eq!(
+            Err(LiteralError::InvalidLanguageTag("fr_fr".to_owned())),
+            Node::literal("chat").with_language("fr_fr").build()
+        );
+        assert!(Node::literal("chat").with_language("").build().is_err());
+        assert!(Node::literal("chat").with_language("fr-").build().is_err());
+        assert!(Node::literal("42")
+            .with_datatype(Node::from("integer"))
+            .build()
+            .is_err());
+
+        // Strings that only look like language-tagged literals partially are plain literals.
+        assert_eq!(None, Node::from("\"chat\"@").language());
+        assert_eq!(None, Node::from("mail@example.org").language());
+    }
 }
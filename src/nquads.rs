@@ -0,0 +1,197 @@
+//! A parser and serializer for the [N-Quads](https://www.w3.org/TR/n-quads/) format.
+//!
+//! N-Quads extends [N-Triples](../ntriples/index.html) with an optional fourth term that names the
+//! graph a triple belongs to. Statements without a graph name belong to the default graph of the
+//! [`Dataset`](../struct.Dataset.html). Blank node labels are scoped to the whole document, so a
+//! blank node that names a graph is the same node as a blank node with the same label in any other
+//! position.
+//!
+//! This module is only available with the `nquads` feature.
+use crate::blank_node_allocator::BlankNodes;
+use crate::ntriples::{expect_resource, parse_line, write_statement};
+use crate::{BlankNodeAllocator, Dataset};
+use std::io::{self, Write};
+
+pub use crate::ntriples::ParseError;
+
+/// Parse an N-Quads document and collect all quads in a dataset.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{Graph, Node};
+///
+/// let dataset = arrdf::nquads::parse_nquads(r#"
+///     <http://example.org/alice> <http://example.org/age> "42" .
+///     <http://example.org/alice> <http://example.org/age> "43" <http://example.org/next-year> .
+/// "#).unwrap();
+///
+/// let alice = Node::from("http://example.org/alice");
+/// let age = Node::from("http://example.org/age");
+/// let next_year = Node::from("http://example.org/next-year");
+/// assert!(dataset.contains_quad(None, &alice, &age, &Node::from("42")));
+/// assert!(dataset.contains_quad(Some(&next_year), &alice, &age, &Node::from("43")));
+/// ```
+pub fn parse_nquads(input: &str) -> Result<Dataset, ParseError> {
+    let mut dataset = Dataset::new();
+    let mut blanks = BlankNodes::default();
+
+    for (index, line) in input.lines().enumerate() {
+        let number = index + 1;
+        let statement = match parse_line(line, number, &mut blanks)? {
+            Some(statement) => statement,
+            None => continue,
+        };
+        let mut terms = statement.terms.into_iter();
+        match (
+            terms.next(),
+            terms.next(),
+            terms.next(),
+            terms.next(),
+            terms.next(),
+        ) {
+            (Some(subject), Some(predicate), Some(object), graph, None) => {
+                expect_resource(&subject, "subject", number)?;
+                expect_resource(&predicate, "predicate", number)?;
+                if let Some(graph) = graph.as_ref() {
+                    expect_resource(graph, "graph name", number)?;
+                }
+                dataset.insert_quad(
+                    graph.map(|(_, graph)| graph),
+                    subject.1,
+                    predicate.1,
+                    object.1,
+                );
+            }
+            (_, _, _, _, Some((column, _))) => {
+                return Err(ParseError {
+                    line: number,
+                    column,
+                    message: "expected '.' after the graph name".to_owned(),
+                })
+            }
+            _ => {
+                return Err(ParseError {
+                    line: number,
+                    column: statement.end,
+                    message:
+                        "expected a subject, a predicate, an object and an optional graph name"
+                            .to_owned(),
+                })
+            }
+        }
+    }
+
+    Ok(dataset)
+}
+
+/// Write all quads of a dataset as an N-Quads document.
+///
+/// Triples of the default graph are written without a graph name. Blank nodes are labeled `_:b0`,
+/// `_:b1`, and so on, in the order they're encountered, regardless of the graph they're in.
+pub fn write_nquads<W: Write>(dataset: &Dataset, out: &mut W) -> io::Result<()> {
+    let mut labels = BlankNodeAllocator::new();
+    for (graph, s, p, o) in dataset.iter() {
+        match graph {
+            Some(graph) => write_statement(out, &[s, p, o, graph], &mut labels)?,
+            None => write_statement(out, &[s, p, o], &mut labels)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+use crate::Node;
+
+#[test]
+fn test_blank_graph_names() {
+    let dataset = parse_nquads(
+        "_:g <urn:p> <urn:o> .\n\
+         _:g <urn:p> <urn:o> _:g .\n\
+         <urn:s> <urn:p> _:g _:g .\n",
+    )
+    .unwrap();
+
+    let names: Vec<&Node> = dataset.graph_names().collect();
+    assert_eq!(1, names.len());
+    let name = names[0];
+    assert!(name.is_blank());
+    assert!(dataset.contains_quad(None, name, &Node::from("urn:p"), &Node::from("urn:o")));
+    assert!(dataset.contains_quad(Some(name), name, &Node::from("urn:p"), &Node::from("urn:o")));
+    assert!(dataset.contains_quad(Some(name), &Node::from("urn:s"), &Node::from("urn:p"), name));
+
+    // Blank nodes of different documents are never the same.
+    let other = parse_nquads("_:g <urn:p> <urn:o> _:g .").unwrap();
+    assert!(other.graph(name).is_none());
+
+    assert_eq!(
+        2,
+        parse_nquads("<urn:s> <urn:p> <urn:o> .\n<urn:s> <urn:p> <urn:o> \"g\" .")
+            .unwrap_err()
+            .line
+    );
+    let error = parse_nquads("<urn:s> <urn:p> <urn:o> <urn:g> <urn:h> .").unwrap_err();
+    assert_eq!((1, 33), (error.line, error.column));
+    let error = parse_nquads("<urn:s> <urn:p> .").unwrap_err();
+    assert_eq!((1, 17), (error.line, error.column));
+}
+
+#[test]
+fn test_nquads_roundtrip() {
+    use crate::{iso, Graph};
+
+    let iri_name = Node::from("http://example.org/graph");
+    let blank_name = Node::blank();
+    let alice = Node::from("http://example.org/alice");
+    let knows = Node::from("http://example.org/knows");
+    let bob = Node::blank();
+
+    let mut dataset = Dataset::new();
+    dataset.insert_quad(None, blank_name.clone(), knows.clone(), alice.clone());
+    dataset.insert_quad(
+        Some(iri_name.clone()),
+        alice.clone(),
+        knows.clone(),
+        bob.clone(),
+    );
+    dataset.insert_quad(
+        Some(blank_name.clone()),
+        bob.clone(),
+        knows.clone(),
+        Node::from("Bob"),
+    );
+
+    let mut document: Vec<u8> = Vec::new();
+    write_nquads(&dataset, &mut document).unwrap();
+    let parsed = parse_nquads(std::str::from_utf8(&document).unwrap()).unwrap();
+
+    assert_eq!(3, parsed.len());
+    assert!(iso::is_isomorphic(
+        dataset.default_graph(),
+        parsed.default_graph()
+    ));
+    assert!(iso::is_isomorphic(
+        dataset.graph(&iri_name).unwrap(),
+        parsed.graph(&iri_name).unwrap()
+    ));
+
+    // The blank graph name is still the subject of the default graph, and bob is still shared
+    // between the named graphs.
+    let parsed_blank_name = parsed.graph_names().find(|name| name.is_blank()).unwrap();
+    assert!(parsed
+        .default_graph()
+        .contains(parsed_blank_name, &knows, &alice));
+    let parsed_bob = parsed
+        .graph(&iri_name)
+        .unwrap()
+        .objects(&alice, &knows)
+        .next()
+        .unwrap()
+        .2;
+    assert!(parsed.contains_quad(
+        Some(parsed_blank_name),
+        parsed_bob,
+        &knows,
+        &Node::from("Bob")
+    ));
+}
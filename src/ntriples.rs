@@ -0,0 +1,612 @@
+//! A parser and serializer for the [N-Triples](https://www.w3.org/TR/n-triples/) format.
+//!
+//! N-Triples is a line-based format where every line contains at most one triple and every term is
+//! written out in full. Blank node labels are only meaningful within one document: Parsing creates
+//! fresh blank nodes for every document, and writing labels the blank nodes from scratch.
+//!
+//! This module is only available with the `ntriples` feature.
+use crate::blank_node_allocator::BlankNodes;
+use crate::node::escape;
+use crate::syntax::{blank_label, comment, iriref, langtag, string_literal, unparsed, Term};
+use crate::{BlankNodeAllocator, Graph, HashGraph, Node, NodeKind};
+use nom::branch::*;
+use nom::bytes::complete::*;
+use nom::character::complete::*;
+use nom::combinator::*;
+use nom::multi::*;
+use nom::sequence::*;
+use nom::*;
+use std::io::{self, BufRead, Write};
+
+pub use crate::ParseError;
+
+fn literal(i: &str) -> IResult<&str, Node> {
+    let (i, lexical_form) = string_literal(i)?;
+    if let Ok((i, language)) = langtag(i) {
+        return Ok((i, Node::lang_literal(&lexical_form, language)));
+    }
+    let (i, datatype) = opt(preceded(tag("^^"), iriref))(i)?;
+    let node = match datatype {
+        Some(datatype) => Node::typed_literal(&lexical_form, &datatype),
+        None => Node::plain_literal(&lexical_form),
+    };
+    Ok((i, node))
+}
+
+fn term(i: &str) -> IResult<&str, Term<'_>> {
+    alt((
+        map(iriref, |iri| Term::Node(Node::from(iri.as_str()))),
+        map(blank_label, Term::Blank),
+        map(literal, Term::Node),
+    ))(i)
+}
+
+/// A term together with the input it starts at.
+type PositionedTerm<'a> = (&'a str, Term<'a>);
+
+fn positioned_term(i: &str) -> IResult<&str, PositionedTerm<'_>> {
+    let (rest, term) = term(i)?;
+    Ok((rest, (i, term)))
+}
+
+fn statement(i: &str) -> IResult<&str, (Vec<PositionedTerm<'_>>, &str)> {
+    all_consuming(delimited(
+        space0,
+        pair(
+            many1(terminated(positioned_term, space0)),
+            recognize(char('.')),
+        ),
+        pair(space0, opt(comment)),
+    ))(i)
+}
+
+/// The terms of a statement in a line-based document.
+pub(crate) struct Statement {
+    /// The terms and the columns they start at.
+    pub terms: Vec<(usize, Node)>,
+    /// The column of the terminating '.'.
+    pub end: usize,
+}
+
+/// Return the column of the line where `rest` starts.
+fn column(line: &str, rest: &str) -> usize {
+    ParseError::at(line, rest, String::new()).column
+}
+
+/// Describe what was expected at the start of `rest` if a statement couldn't be parsed.
+fn expectation(line: &str, rest: &str) -> &'static str {
+    let consumed = &line[..line.len() - rest.len()];
+    if rest.is_empty() {
+        "expected '.' to end the statement"
+    } else if consumed.trim_end().ends_with('.') {
+        "expected the end of the line after '.'"
+    } else if rest.starts_with('<') {
+        "expected a valid IRI enclosed in '<' and '>'"
+    } else if rest.starts_with('"') {
+        "expected a string literal enclosed in '\"', optionally followed by a datatype or a language tag"
+    } else if rest.starts_with("_:") {
+        "expected a blank node label after '_:'"
+    } else {
+        "expected '<' to start an IRI, '_:' to start a blank node or '\"' to start a literal"
+    }
+}
+
+/// Parse a line of a line-based document.
+///
+/// Empty lines and comments yield `None`, statements yield all of their terms. Checking the number
+/// and kinds of the terms is up to the caller.
+pub(crate) fn parse_line(
+    line: &str,
+    number: usize,
+    blanks: &mut BlankNodes,
+) -> Result<Option<Statement>, ParseError> {
+    if all_consuming(pair(space0, opt(comment)))(line).is_ok() {
+        return Ok(None);
+    }
+
+    let (_, (terms, end)) = statement(line).map_err(|error| {
+        let rest = unparsed(error, line);
+        ParseError {
+            line: number,
+            ..ParseError::at(line, rest, expectation(line, rest).to_owned())
+        }
+    })?;
+    let terms = terms
+        .into_iter()
+        .map(|(rest, term)| (column(line, rest), blanks.resolve(term)))
+        .collect();
+    Ok(Some(Statement {
+        terms,
+        end: column(line, end),
+    }))
+}
+
+/// Check that a term in a subject, predicate or graph name position isn't a literal.
+pub(crate) fn expect_resource(
+    (column, node): &(usize, Node),
+    position: &str,
+    line: usize,
+) -> Result<(), ParseError> {
+    if node.kind() == NodeKind::Literal {
+        Err(ParseError {
+            line,
+            column: *column,
+            message: format!("expected an IRI or a blank node as the {}", position),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Write a statement consisting of the given terms, terminated by a '.' and a line break.
+pub(crate) fn write_statement<W: Write>(
+    out: &mut W,
+    terms: &[&Node],
+    labels: &mut BlankNodeAllocator,
+) -> io::Result<()> {
+    for node in terms {
+        match node.kind() {
+            NodeKind::Iri => write!(out, "<{}> ", node.as_str())?,
+            NodeKind::Blank => write!(out, "_:b{} ", labels.label_of_blank(node))?,
+            NodeKind::Literal => match (node.datatype(), node.language()) {
+                (Some(datatype), _) => {
+                    write!(out, "\"{}\"^^<{}> ", escape(node.lexical_form()), datatype)?
+                }
+                (None, Some(language)) => {
+                    write!(out, "\"{}\"@{} ", escape(node.lexical_form()), language)?
+                }
+                (None, None) => write!(out, "\"{}\" ", escape(node.as_str()))?,
+            },
+        }
+    }
+    writeln!(out, ".")
+}
+
+/// Parse a line of an N-Triples document.
+///
+/// Empty lines and comments yield `None`.
+fn parse_triple(
+    line: &str,
+    number: usize,
+    blanks: &mut BlankNodes,
+) -> Result<Option<(Node, Node, Node)>, ParseError> {
+    let statement = match parse_line(line, number, blanks)? {
+        Some(statement) => statement,
+        None => return Ok(None),
+    };
+    let mut terms = statement.terms.into_iter();
+    match (terms.next(), terms.next(), terms.next(), terms.next()) {
+        (Some(subject), Some(predicate), Some(object), None) => {
+            expect_resource(&subject, "subject", number)?;
+            expect_resource(&predicate, "predicate", number)?;
+            Ok(Some((subject.1, predicate.1, object.1)))
+        }
+        (_, _, _, Some((column, _))) => Err(ParseError {
+            line: number,
+            column,
+            message: "expected '.' after the object".to_owned(),
+        }),
+        _ => Err(ParseError {
+            line: number,
+            column: statement.end,
+            message: "expected a subject, a predicate and an object".to_owned(),
+        }),
+    }
+}
+
+/// Parse an N-Triples document and collect all triples in a graph.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{Graph, Node};
+///
+/// let graph = arrdf::ntriples::parse_ntriples(r#"
+///     <http://example.org/alice> <http://example.org/knows> _:someone .
+///     _:someone <http://example.org/name> "Bob" .
+/// "#).unwrap();
+///
+/// assert_eq!(2, graph.len());
+/// assert!(graph
+///     .objects(&Node::from("http://example.org/alice"), &Node::from("http://example.org/knows"))
+///     .all(|(_, _, someone)| graph.contains(someone, &Node::from("http://example.org/name"), &Node::from("Bob"))));
+/// ```
+pub fn parse_ntriples(input: &str) -> Result<HashGraph, ParseError> {
+    let mut graph = HashGraph::new();
+    let mut blanks = BlankNodes::default();
+
+    for (index, line) in input.lines().enumerate() {
+        if let Some((subject, predicate, object)) = parse_triple(line, index + 1, &mut blanks)? {
+            graph.insert(subject, predicate, object);
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Parse an N-Triples document line by line and yield its triples lazily.
+///
+/// In contrast to [`parse_ntriples`](fn.parse_ntriples.html), the document is never loaded into
+/// memory as a whole, which makes this function suitable for huge files. Blank node labels are
+/// consistent throughout the whole stream. An invalid line yields an error, but parsing continues
+/// with the next line; an I/O error is yielded as a [`ParseError`](struct.ParseError.html) too, but
+/// ends the iteration.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{Graph, HashGraph};
+/// use std::io::BufReader;
+///
+/// let document = "<urn:alice> <urn:knows> _:x .\n_:x <urn:name> \"Bob\" .\n";
+/// let mut graph = HashGraph::new();
+/// for triple in arrdf::ntriples::parse_ntriples_reader(BufReader::new(document.as_bytes())) {
+///     let (s, p, o) = triple.unwrap();
+///     graph.insert(s, p, o);
+/// }
+///
+/// assert_eq!(2, graph.len());
+/// ```
+pub fn parse_ntriples_reader<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = Result<(Node, Node, Node), ParseError>> {
+    let mut lines = reader.lines().enumerate();
+    let mut blanks = BlankNodes::default();
+    let mut failed = false;
+    std::iter::from_fn(move || loop {
+        if failed {
+            return None;
+        }
+        let (index, line) = lines.next()?;
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                failed = true;
+                return Some(Err(ParseError {
+                    line: index + 1,
+                    column: 1,
+                    message: error.to_string(),
+                }));
+            }
+        };
+        match parse_triple(&line, index + 1, &mut blanks) {
+            Ok(Some(triple)) => return Some(Ok(triple)),
+            Ok(None) => continue,
+            Err(error) => return Some(Err(error)),
+        }
+    })
+}
+
+/// Write all triples of a graph as an N-Triples document.
+///
+/// Blank nodes are labeled `_:b0`, `_:b1`, and so on, in the order they're encountered.
+pub fn write_ntriples<G: Graph, W: Write>(graph: &G, out: &mut W) -> io::Result<()> {
+    write_ntriples_with_labels(graph, &mut BlankNodeAllocator::new(), out)
+}
+
+/// Write the triples of an iterator as an N-Triples document.
+///
+/// This serializes triples as they're produced, without collecting them in a graph first, for
+/// example the triples of a [set operation](../set/index.html). Blank nodes are labeled `_:b0`,
+/// `_:b1`, and so on, in the order they're encountered, and keep their label for the whole
+/// stream. Duplicate triples are written as often as the iterator yields them. Like
+/// [`write_ntriples`](fn.write_ntriples.html), this function doesn't flush `out` and stops at the
+/// first error.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{graph, set};
+///
+/// let a = graph! { "urn:alice" -> "urn:knows" -> "urn:bob"; };
+/// let b = graph! { "urn:alice" -> "urn:knows" -> "urn:bob"; };
+///
+/// let mut output: Vec<u8> = Vec::new();
+/// arrdf::ntriples::write_ntriples_iter(set::union(&a, &b), &mut output).unwrap();
+/// assert_eq!("<urn:alice> <urn:knows> <urn:bob> .\n", String::from_utf8(output).unwrap());
+/// ```
+pub fn write_ntriples_iter<'a, W, I>(iter: I, out: &mut W) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Node, &'a Node, &'a Node)>,
+{
+    write_triples(iter, &mut BlankNodeAllocator::new(), out)
+}
+
+/// Write all triples of a graph as an N-Triples document, using the labels of an allocator.
+///
+/// Blank nodes that already have a label in `labels` are written with it, and all other blank
+/// nodes are registered with `labels` in the order they're encountered. Writing several documents
+/// with the same allocator keeps the labels of the blank nodes consistent between them.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{BlankNodeAllocator, Graph, HashGraph, Node};
+///
+/// let mut labels = BlankNodeAllocator::new();
+/// labels.allocate();
+/// let blank = labels.allocate();
+///
+/// let mut graph = HashGraph::new();
+/// graph.clone_insert(&Node::from("urn:alice"), &Node::from("urn:knows"), &blank);
+///
+/// let mut output: Vec<u8> = Vec::new();
+/// arrdf::ntriples::write_ntriples_with_labels(&graph, &mut labels, &mut output).unwrap();
+/// assert_eq!("<urn:alice> <urn:knows> _:b1 .\n", String::from_utf8(output).unwrap());
+/// ```
+pub fn write_ntriples_with_labels<G: Graph, W: Write>(
+    graph: &G,
+    labels: &mut BlankNodeAllocator,
+    out: &mut W,
+) -> io::Result<()> {
+    write_triples(graph.iter(), labels, out)
+}
+
+fn write_triples<'a, W, I>(iter: I, labels: &mut BlankNodeAllocator, out: &mut W) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Node, &'a Node, &'a Node)>,
+{
+    for (s, p, o) in iter {
+        write_statement(out, &[s, p, o], labels)?;
+    }
+    Ok(())
+}
+
+/// Transform an N-Triples document line by line, without loading it into memory.
+///
+/// Every triple of `input` is passed to `f`, and the triple returned by `f`, if any, is written to
+/// `output`. Blank nodes are relabeled consistently: Every label of the input is mapped to the same
+/// label of the output, and new blank nodes created by `f` get new labels. Comments and empty lines
+/// are dropped.
+///
+/// Lines that can't be parsed are reported as errors of the kind
+/// [`InvalidData`](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData) that
+/// wrap the [`ParseError`](struct.ParseError.html).
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::Node;
+///
+/// let input = "<urn:alice> <urn:old> <urn:bob> .\n<urn:alice> <urn:other> _:x .\n";
+/// let mut output: Vec<u8> = Vec::new();
+///
+/// let (old, new) = (Node::from("urn:old"), Node::from("urn:new"));
+/// arrdf::ntriples::transform_ntriples(input.as_bytes(), &mut output, |s, p, o| {
+///     Some((s, if p == old { new.clone() } else { p }, o))
+/// })
+/// .unwrap();
+///
+/// assert_eq!(
+///     "<urn:alice> <urn:new> <urn:bob> .\n<urn:alice> <urn:other> _:b0 .\n",
+///     String::from_utf8(output).unwrap()
+/// );
+/// ```
+pub fn transform_ntriples<R, W, F>(input: R, mut output: W, mut f: F) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(Node, Node, Node) -> Option<(Node, Node, Node)>,
+{
+    let mut blanks = BlankNodes::default();
+    let mut labels = BlankNodeAllocator::new();
+    for (index, line) in input.lines().enumerate() {
+        let triple = parse_triple(&line?, index + 1, &mut blanks)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        if let Some((subject, predicate, object)) = triple.and_then(|(s, p, o)| f(s, p, o)) {
+            write_statement(&mut output, &[&subject, &predicate, &object], &mut labels)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_line() {
+    let mut blanks = BlankNodes::default();
+    assert!(parse_line("", 1, &mut blanks).unwrap().is_none());
+    assert!(parse_line("  # A comment", 1, &mut blanks)
+        .unwrap()
+        .is_none());
+
+    let statement = parse_line(
+        r#"_:a <http://example.org/p> "4\"2"^^<http://www.w3.org/2001/XMLSchema#integer> . # Comment"#,
+        1,
+        &mut blanks,
+    )
+    .unwrap()
+    .unwrap();
+    let terms: Vec<Node> = statement
+        .terms
+        .iter()
+        .map(|(_, node)| node.clone())
+        .collect();
+    let columns: Vec<usize> = statement.terms.iter().map(|(column, _)| *column).collect();
+    assert_eq!(3, terms.len());
+    assert_eq!(vec![1, 5, 28], columns);
+    assert_eq!(89, statement.end);
+    assert_eq!(blanks.get("a"), terms[0]);
+    assert_ne!(blanks.get("b"), terms[0]);
+    assert_eq!(Node::from("http://example.org/p"), terms[1]);
+    assert_eq!(
+        Node::typed_literal("4\"2", "http://www.w3.org/2001/XMLSchema#integer"),
+        terms[2]
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |line: &str| {
+        parse_line(line, 3, &mut BlankNodes::default())
+            .err()
+            .unwrap()
+    };
+
+    let missing_dot = error("<urn:a> <urn:b> <urn:c>");
+    assert_eq!((3, 24), (missing_dot.line, missing_dot.column));
+    assert_eq!("expected '.' to end the statement", missing_dot.message);
+
+    let invalid_iri = error("<urn:a> <urn:b<c> .");
+    assert_eq!(9, invalid_iri.column);
+    assert_eq!(
+        "expected a valid IRI enclosed in '<' and '>'",
+        invalid_iri.message
+    );
+
+    let unterminated_literal = error("<urn:a> <urn:b> \"abc .");
+    assert_eq!(17, unterminated_literal.column);
+    assert!(unterminated_literal
+        .message
+        .starts_with("expected a string literal"));
+
+    let garbage = error("<urn:a> <urn:b> <urn:c> . <urn:d>");
+    assert_eq!(27, garbage.column);
+    assert_eq!("expected the end of the line after '.'", garbage.message);
+
+    let unknown = error("<urn:a> <urn:b> urn:c .");
+    assert_eq!(17, unknown.column);
+    assert!(unknown.message.starts_with("expected '<' to start an IRI"));
+
+    let error = parse_ntriples("<urn:a> <urn:b> <urn:c> .\n<urn:a> \"b\" <urn:c> .").unwrap_err();
+    assert_eq!((2, 9), (error.line, error.column));
+    assert_eq!(
+        "Line 2, column 9: expected an IRI or a blank node as the predicate",
+        error.to_string()
+    );
+    let error = parse_ntriples("<urn:a> <urn:b> <urn:c> <urn:d> .").unwrap_err();
+    assert_eq!(25, error.column);
+    let error = parse_ntriples("<urn:a> <urn:b> .").unwrap_err();
+    assert_eq!(17, error.column);
+}
+
+#[test]
+fn test_ntriples_roundtrip() {
+    let blank = Node::blank();
+    let graph = crate::graph! {
+        (blank) -> "http://example.org/name" -> "Line\nbreak \"quoted\"";
+        (blank) -> "http://example.org/age" -> (Node::typed_literal("42", "http://www.w3.org/2001/XMLSchema#integer"));
+        (blank) -> "http://example.org/greeting" -> (Node::literal("Grüezi").with_language("de-CH").build().unwrap());
+        "http://example.org/alice" -> "http://example.org/knows" -> (blank);
+    };
+
+    let mut document: Vec<u8> = Vec::new();
+    write_ntriples(&graph, &mut document).unwrap();
+    let document = String::from_utf8(document).unwrap();
+    assert!(document.contains("\"Grüezi\"@de-ch ."));
+    let parsed = parse_ntriples(&document).unwrap();
+    assert!(crate::iso::is_isomorphic(&graph, &parsed));
+    let parsed = parse_ntriples("<urn:a> <urn:b> \"chat\"@FR .").unwrap();
+    assert_eq!(
+        Some("fr"),
+        parsed.iter().next().map(|(_, _, o)| o.language()).unwrap()
+    );
+
+    let error =
+        parse_ntriples("<urn:a> <urn:b> <urn:c> .\n\"literal\" <urn:b> <urn:c> .").unwrap_err();
+    assert_eq!(2, error.line);
+    let error = parse_ntriples("<urn:a> <urn:b> .").unwrap_err();
+    assert_eq!(1, error.line);
+}
+
+#[test]
+fn test_empty_literal() {
+    let graph = parse_ntriples("<urn:a> <urn:b> \"\" .").unwrap();
+    let (_, _, object) = graph.iter().next().unwrap();
+    assert!(object.is_literal());
+    assert!(!object.is_blank());
+    assert_eq!("", object.lexical_form());
+
+    let mut document: Vec<u8> = Vec::new();
+    write_ntriples(&graph, &mut document).unwrap();
+    let document = String::from_utf8(document).unwrap();
+    assert!(!document.contains("_:"));
+    assert_eq!(graph, parse_ntriples(&document).unwrap());
+}
+
+#[test]
+fn test_parse_ntriples_reader() {
+    let input = "# A comment\n\
+                 _:x <urn:knows> _:y .\n\
+                 <urn:a> <urn:b> .\n\
+                 _:y <urn:knows> _:x .\n";
+    let results: Vec<Result<(Node, Node, Node), ParseError>> =
+        parse_ntriples_reader(input.as_bytes()).collect();
+    assert_eq!(3, results.len());
+    assert_eq!(3, results[1].as_ref().unwrap_err().line);
+
+    let (x, _, y) = results[0].clone().unwrap();
+    let (other_y, _, other_x) = results[2].clone().unwrap();
+    assert!(x.is_blank() && y.is_blank());
+    assert_ne!(x, y);
+    assert_eq!(x, other_x);
+    assert_eq!(y, other_y);
+
+    // An I/O error ends the stream.
+    let invalid_utf8: &[u8] = b"<urn:a> <urn:b> <urn:c> .\n\xff\n<urn:a> <urn:b> <urn:d> .\n";
+    let results: Vec<Result<(Node, Node, Node), ParseError>> =
+        parse_ntriples_reader(invalid_utf8).collect();
+    assert_eq!(2, results.len());
+    assert!(results[0].is_ok());
+    assert_eq!(2, results[1].as_ref().unwrap_err().line);
+}
+
+#[test]
+fn test_transform_ntriples() {
+    let input = "# A comment\n\
+                 _:x <urn:old> _:y .\n\
+                 _:y <urn:old> _:x .\n\
+                 _:y <urn:drop> \"dropped\" .\n\
+                 <urn:a> <urn:old> \"A\\nB\" .\n";
+    let (old, new, drop) = (
+        Node::from("urn:old"),
+        Node::from("urn:new"),
+        Node::from("urn:drop"),
+    );
+
+    let mut output: Vec<u8> = Vec::new();
+    transform_ntriples(input.as_bytes(), &mut output, |s, p, o| {
+        if p == drop {
+            None
+        } else if p == old {
+            Some((s, new.clone(), o))
+        } else {
+            Some((s, p, o))
+        }
+    })
+    .unwrap();
+    assert_eq!(
+        "_:b0 <urn:new> _:b1 .\n_:b1 <urn:new> _:b0 .\n<urn:a> <urn:new> \"A\\nB\" .\n",
+        String::from_utf8(output).unwrap()
+    );
+
+    let error = transform_ntriples("<urn:a> <urn:b> .".as_bytes(), Vec::new(), |s, p, o| {
+        Some((s, p, o))
+    })
+    .unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, error.kind());
+}
+
+#[test]
+fn test_write_ntriples_iter() {
+    let (x, y) = (Node::blank(), Node::blank());
+    let knows = Node::from("urn:knows");
+    let a = crate::graph! { (x) -> "urn:knows" -> (y); };
+    let b = crate::graph! { (y) -> "urn:knows" -> (x); };
+
+    // Blank nodes keep their labels across the triples of both graphs.
+    let mut output: Vec<u8> = Vec::new();
+    write_ntriples_iter(crate::set::union(&a, &b), &mut output).unwrap();
+    assert_eq!(
+        "_:b0 <urn:knows> _:b1 .\n_:b1 <urn:knows> _:b0 .\n",
+        String::from_utf8(output).unwrap()
+    );
+
+    let mut output: Vec<u8> = Vec::new();
+    write_ntriples_iter(vec![(&x, &knows, &y), (&x, &knows, &y)], &mut output).unwrap();
+    assert_eq!(2, String::from_utf8(output).unwrap().lines().count());
+
+    let mut full: [u8; 8] = [0; 8];
+    let error = write_ntriples_iter(a.iter(), &mut &mut full[..]).unwrap_err();
+    assert_eq!(io::ErrorKind::WriteZero, error.kind());
+}
@@ -0,0 +1,178 @@
+use crate::Node;
+use std::collections::HashMap;
+
+/// A map from prefixes to namespace IRIs to expand and abbreviate compact IRIs like `ex:alice`.
+///
+/// The Turtle parser and serializer use a `PrefixMap` for their prefixed names, and it can be used
+/// the same way in application code. A compact IRI consists of a prefix and a local name, separated
+/// by a colon, and is expanded by appending the local name to the namespace of the prefix. Local
+/// names of abbreviated IRIs only contain alphanumeric characters, `_` and `-`, so that they are
+/// valid prefixed names in Turtle.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{Node, PrefixMap};
+///
+/// let mut prefixes = PrefixMap::new();
+/// prefixes.insert("ex", "http://example.org/");
+/// prefixes.insert("people", "http://example.org/people/");
+///
+/// let alice = Node::from("http://example.org/people/alice");
+/// assert_eq!(Some(alice.clone()), prefixes.expand("people:alice"));
+/// assert_eq!(None, prefixes.expand("foaf:Person"));
+///
+/// // The longest matching namespace wins.
+/// assert_eq!(Some("people:alice".to_owned()), prefixes.abbreviate(&alice));
+/// assert_eq!(None, prefixes.abbreviate(&Node::from("urn:alice")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrefixMap {
+    prefixes: HashMap<String, String>,
+}
+
+impl PrefixMap {
+    /// Create a new, empty prefix map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map a prefix to a namespace IRI and return the namespace it was mapped to before, if any.
+    pub fn insert(&mut self, prefix: &str, namespace: &str) -> Option<String> {
+        self.prefixes
+            .insert(prefix.to_owned(), namespace.to_owned())
+    }
+
+    /// Remove a prefix and return its namespace IRI, if it was mapped.
+    pub fn remove(&mut self, prefix: &str) -> Option<String> {
+        self.prefixes.remove(prefix)
+    }
+
+    /// Return the namespace IRI of a prefix, if it's mapped.
+    pub fn get(&self, prefix: &str) -> Option<&str> {
+        self.prefixes.get(prefix).map(String::as_str)
+    }
+
+    /// Iterate over all pairs of a prefix and a namespace IRI, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.prefixes
+            .iter()
+            .map(|(prefix, namespace)| (prefix.as_str(), namespace.as_str()))
+    }
+
+    /// Return the number of prefixes.
+    pub fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    /// Return `true` if no prefix is mapped.
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// Expand a compact IRI like `ex:alice` to a full IRI.
+    ///
+    /// Returns `None` if the string doesn't contain a colon or its prefix isn't mapped.
+    pub fn expand(&self, compact: &str) -> Option<Node> {
+        let colon = compact.find(':')?;
+        let namespace = self.get(&compact[..colon])?;
+        Some(Node::from(
+            format!("{}{}", namespace, &compact[colon + 1..]).as_str(),
+        ))
+    }
+
+    /// Abbreviate an IRI to a compact IRI, using the longest namespace the IRI starts with.
+    ///
+    /// Returns `None` if the node isn't an IRI, no namespace matches or the remaining local name
+    /// contains other characters than alphanumeric ones, `_` and `-`. If several prefixes map to
+    /// the same namespace, the lexicographically smallest prefix is used.
+    pub fn abbreviate(&self, node: &Node) -> Option<String> {
+        if !node.is_iri() {
+            return None;
+        }
+        self.abbreviate_iri(node.as_str())
+    }
+
+    /// Abbreviate an IRI that is given as a string.
+    pub(crate) fn abbreviate_iri(&self, iri: &str) -> Option<String> {
+        self.prefixes
+            .iter()
+            .filter_map(|(prefix, namespace)| {
+                let local = iri.strip_prefix(namespace.as_str())?;
+                if local.chars().all(is_name_char) {
+                    Some((namespace.len(), prefix, local))
+                } else {
+                    None
+                }
+            })
+            .max_by(|(len_a, prefix_a, _), (len_b, prefix_b, _)| {
+                len_a.cmp(len_b).then_with(|| prefix_b.cmp(prefix_a))
+            })
+            .map(|(_, prefix, local)| format!("{}:{}", prefix, local))
+    }
+}
+
+impl<'a> std::iter::FromIterator<(&'a str, &'a str)> for PrefixMap {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut prefixes = PrefixMap::new();
+        for (prefix, namespace) in iter {
+            prefixes.insert(prefix, namespace);
+        }
+        prefixes
+    }
+}
+
+/// Return `true` if the character may appear in prefixes and local names.
+pub(crate) fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn expand_and_abbreviate() {
+        let mut prefixes: PrefixMap = vec![
+            ("ex", "http://example.org/"),
+            ("alias", "http://example.org/"),
+            ("vocab", "http://example.org/vocab#"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(3, prefixes.len());
+
+        let term = Node::from("http://example.org/vocab#term");
+        assert_eq!(Some(term.clone()), prefixes.expand("vocab:term"));
+        assert_eq!(
+            Some(Node::from("http://example.org/")),
+            prefixes.expand("ex:")
+        );
+        assert_eq!(None, prefixes.expand("vocab"));
+        assert_eq!(None, prefixes.expand("other:term"));
+
+        assert_eq!(Some("vocab:term".to_owned()), prefixes.abbreviate(&term));
+        // Ties between prefixes of the same namespace are broken by the prefix.
+        let alice = Node::from("http://example.org/alice");
+        assert_eq!(Some("alias:alice".to_owned()), prefixes.abbreviate(&alice));
+        // Local names with other characters can't be abbreviated.
+        assert_eq!(
+            None,
+            prefixes.abbreviate(&Node::from("http://example.org/a/b"))
+        );
+        assert_eq!(
+            Some("alias:".to_owned()),
+            prefixes.abbreviate(&Node::from("http://example.org/"))
+        );
+        assert_eq!(None, prefixes.abbreviate(&Node::blank()));
+
+        assert_eq!(
+            Some("http://example.org/".to_owned()),
+            prefixes.remove("alias")
+        );
+        assert_eq!(Some("ex:alice".to_owned()), prefixes.abbreviate(&alice));
+    }
+}
@@ -0,0 +1,291 @@
+//! [Serde](https://serde.rs) support for nodes and graphs, enabled by the `serde` feature.
+//!
+//! A node is serialized as a map with a single tag that tells its kind: `{"iri": "..."}`,
+//! `{"blank": 0}`, `{"literal": "...", "datatype": "..."}` or `{"literal": "...", "lang": "..."}`,
+//! where the datatype is omitted for plain literals. A graph is serialized as a sequence of `[subject, predicate, object]` triples.
+//!
+//! Blank nodes don't have a name, so they are numbered in the order they're encountered while
+//! serializing a graph. When the graph is deserialized, all blank nodes with the same number become
+//! the same, new blank node. A single node doesn't have this context: A blank node on its own is
+//! always serialized as `{"blank": 0}` and always deserialized as a new blank node.
+use crate::{Graph, HashGraph, Node, NodeKind};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::BuildHasher;
+
+const FIELDS: &[&str] = &["iri", "blank", "literal", "datatype", "lang"];
+
+/// A node together with the number it has in the serialized document, if it's blank.
+struct NodeRef<'a> {
+    node: &'a Node,
+    blank_index: usize,
+}
+
+impl<'a> NodeRef<'a> {
+    /// Look up the number of a blank node, or assign it the next free one.
+    fn new(node: &'a Node, blank_indices: &mut HashMap<&'a Node, usize>) -> Self {
+        let next_index = blank_indices.len();
+        let blank_index = if node.is_blank() {
+            *blank_indices.entry(node).or_insert(next_index)
+        } else {
+            0
+        };
+        Self { node, blank_index }
+    }
+}
+
+impl<'a> Serialize for NodeRef<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.node.kind() {
+            NodeKind::Iri => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("iri", self.node.as_str())?;
+                map.end()
+            }
+            NodeKind::Blank => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("blank", &self.blank_index)?;
+                map.end()
+            }
+            NodeKind::Literal => {
+                let datatype = self.node.datatype();
+                let language = self.node.language();
+                let len = if datatype.is_some() || language.is_some() {
+                    2
+                } else {
+                    1
+                };
+                let mut map = serializer.serialize_map(Some(len))?;
+                map.serialize_entry("literal", self.node.lexical_form())?;
+                // The values are deserialized as options, which makes a difference for formats
+                // that aren't self-describing, like bincode.
+                if datatype.is_some() {
+                    map.serialize_entry("datatype", &datatype)?;
+                }
+                if language.is_some() {
+                    map.serialize_entry("lang", &language)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        NodeRef {
+            node: self,
+            blank_index: 0,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<H: BuildHasher + Default> Serialize for HashGraph<H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut blank_indices: HashMap<&Node, usize> = HashMap::new();
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for (s, p, o) in self.iter() {
+            seq.serialize_element(&(
+                NodeRef::new(s, &mut blank_indices),
+                NodeRef::new(p, &mut blank_indices),
+                NodeRef::new(o, &mut blank_indices),
+            ))?;
+        }
+        seq.end()
+    }
+}
+
+/// A deserialized node whose blank nodes haven't been resolved yet.
+enum Term {
+    Node(Node),
+    Blank(usize),
+}
+
+impl Term {
+    fn resolve(self, blanks: &mut HashMap<usize, Node>) -> Node {
+        match self {
+            Term::Node(node) => node,
+            Term::Blank(index) => blanks.entry(index).or_insert_with(Node::blank).clone(),
+        }
+    }
+}
+
+struct TermVisitor;
+
+impl<'de> Visitor<'de> for TermVisitor {
+    type Value = Term;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map with an \"iri\", a \"blank\" or a \"literal\" entry")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Term, A::Error> {
+        let mut iri: Option<String> = None;
+        let mut blank: Option<usize> = None;
+        let mut literal: Option<String> = None;
+        let mut datatype: Option<String> = None;
+        let mut language: Option<String> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "iri" => iri = Some(map.next_value()?),
+                "blank" => blank = Some(map.next_value()?),
+                "literal" => literal = Some(map.next_value()?),
+                "datatype" => datatype = map.next_value()?,
+                "lang" => language = map.next_value()?,
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+
+        match (iri, blank, literal) {
+            (Some(iri), None, None) if datatype.is_none() && language.is_none() => {
+                let node = Node::from(iri.as_str());
+                if node.is_iri() {
+                    Ok(Term::Node(node))
+                } else {
+                    Err(de::Error::invalid_value(
+                        de::Unexpected::Str(&iri),
+                        &"a valid IRI",
+                    ))
+                }
+            }
+            (None, Some(index), None) if datatype.is_none() && language.is_none() => {
+                Ok(Term::Blank(index))
+            }
+            (None, None, Some(literal)) => {
+                let mut builder = Node::literal(&literal);
+                if let Some(datatype) = datatype {
+                    builder = builder.with_datatype(Node::from(datatype.as_str()));
+                }
+                if let Some(language) = language {
+                    builder = builder.with_language(&language);
+                }
+                builder.build().map(Term::Node).map_err(de::Error::custom)
+            }
+            _ => Err(de::Error::custom(
+                "expected exactly one of \"iri\", \"blank\" or \"literal\"",
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Term {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(TermVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Term::deserialize(deserializer)?.resolve(&mut HashMap::new()))
+    }
+}
+
+impl<'de, H: BuildHasher + Default> Deserialize<'de> for HashGraph<H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let triples: Vec<(Term, Term, Term)> = Vec::deserialize(deserializer)?;
+        let mut blanks: HashMap<usize, Node> = HashMap::new();
+        let mut graph = HashGraph::default();
+        for (s, p, o) in triples {
+            graph.insert(
+                s.resolve(&mut blanks),
+                p.resolve(&mut blanks),
+                o.resolve(&mut blanks),
+            );
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn node_representation() {
+        let iri = Node::from("http://example.org/alice");
+        assert_eq!(
+            r#"{"iri":"http://example.org/alice"}"#,
+            serde_json::to_string(&iri).unwrap()
+        );
+        assert_eq!(
+            r#"{"literal":"Alice"}"#,
+            serde_json::to_string(&Node::from("Alice")).unwrap()
+        );
+        let age = Node::typed_literal("42", "http://www.w3.org/2001/XMLSchema#integer");
+        assert_eq!(
+            r#"{"literal":"42","datatype":"http://www.w3.org/2001/XMLSchema#integer"}"#,
+            serde_json::to_string(&age).unwrap()
+        );
+        assert_eq!(
+            r#"{"blank":0}"#,
+            serde_json::to_string(&Node::blank()).unwrap()
+        );
+
+        for node in &[&iri, &age] {
+            let json = serde_json::to_string(node).unwrap();
+            assert_eq!(**node, serde_json::from_str::<Node>(&json).unwrap());
+        }
+        let node: Node =
+            serde_json::from_str(r#"{"literal":"Alice","datatype":null,"lang":null}"#).unwrap();
+        assert_eq!(Node::from("Alice"), node);
+        let empty = serde_json::from_str::<Node>(r#"{"literal":""}"#).unwrap();
+        assert!(empty.is_literal());
+        assert_eq!("", empty.lexical_form());
+        assert!(serde_json::from_str::<Node>(r#"{"blank":3}"#)
+            .unwrap()
+            .is_blank());
+
+        assert!(serde_json::from_str::<Node>(r#"{"iri":"not an iri"}"#).is_err());
+        let chat = Node::literal("chat").with_language("fr").build().unwrap();
+        assert_eq!(
+            r#"{"literal":"chat","lang":"fr"}"#,
+            serde_json::to_string(&chat).unwrap()
+        );
+        assert_eq!(
+            chat,
+            serde_json::from_str::<Node>(r#"{"literal":"chat","lang":"fr"}"#).unwrap()
+        );
+        assert!(serde_json::from_str::<Node>(
+            r#"{"literal":"chat","lang":"fr","datatype":"urn:x"}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Node>(r#"{"iri":"urn:x","blank":0}"#).is_err());
+        assert!(serde_json::from_str::<Node>(r#"{}"#).is_err());
+    }
+
+    #[test]
+    fn graph_roundtrip() {
+        let validator = Validator::new(HashGraph::new());
+        let mut graph = validator.graph.clone();
+        let other_blank = Node::blank();
+        graph.clone_insert(&other_blank, &validator.predicate_a, &validator.node_c);
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let parsed: HashGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(4, parsed.len());
+        assert!(graph.is_isomorphic(&parsed));
+
+        // Both blank nodes survive as two distinct nodes.
+        let blanks: std::collections::HashSet<&Node> = parsed
+            .iter()
+            .flat_map(|(s, _, o)| vec![s, o])
+            .filter(|node| node.is_blank())
+            .collect();
+        assert_eq!(2, blanks.len());
+
+        // Equal indices become the same node, even across triples.
+        let parsed: HashGraph = serde_json::from_str(
+            r#"[
+                [{"blank": 7}, {"iri": "urn:p"}, {"literal": "x"}],
+                [{"iri": "urn:s"}, {"iri": "urn:p"}, {"blank": 7}]
+            ]"#,
+        )
+        .unwrap();
+        let subject = parsed.iter().find(|(_, _, o)| o.as_str() == "x").unwrap().0;
+        assert!(parsed.contains(&Node::from("urn:s"), &Node::from("urn:p"), subject));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Graph, Node};
+use crate::{Graph, HashGraph, Node};
 
 pub fn difference<'a, G, H>(
     lhs: &'a G,
@@ -22,15 +22,23 @@ where
     difference(lhs, rhs).chain(difference(rhs, lhs))
 }
 
+/// Return the triples contained in both graphs.
+///
+/// The smaller graph is iterated and the larger one is probed, so the number of lookups only
+/// depends on the size of the smaller graph.
 pub fn intersection<'a, G, H>(
     lhs: &'a G,
     rhs: &'a H,
-) -> impl 'a + Iterator<Item = (&'a Node, &'a Node, &'a Node)>
+) -> Box<dyn 'a + Iterator<Item = (&'a Node, &'a Node, &'a Node)>>
 where
     G: Graph,
     H: Graph,
 {
-    lhs.iter().filter(move |(s, p, o)| rhs.contains(s, p, o))
+    if rhs.len() < lhs.len() {
+        Box::new(rhs.iter().filter(move |(s, p, o)| lhs.contains(s, p, o)))
+    } else {
+        Box::new(lhs.iter().filter(move |(s, p, o)| rhs.contains(s, p, o)))
+    }
 }
 
 pub fn union<'a, G, H>(
@@ -68,6 +76,24 @@ where
     intersection(lhs, rhs).next().is_none()
 }
 
+/// Return the triples of every graph that aren't contained in any of the other graphs.
+pub fn unique_per_graph<'a>(graphs: &'a [&'a HashGraph]) -> Vec<HashGraph> {
+    graphs
+        .iter()
+        .enumerate()
+        .map(|(index, graph)| {
+            graph
+                .iter()
+                .filter(|(s, p, o)| {
+                    graphs.iter().enumerate().all(|(other_index, other)| {
+                        other_index == index || !other.contains(s, p, o)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -147,6 +173,28 @@ mod tests {
         assert!(union.contains(&validator.node_b, &validator.predicate_a, &validator.node_a));
     }
 
+    #[test]
+    fn intersection() {
+        let validator = Validator::new(HashGraph::new());
+
+        let a = validator.graph;
+        let mut b = HashGraph::new();
+        b.clone_insert(&validator.node_a, &validator.predicate_a, &validator.node_b);
+        b.clone_insert(&validator.node_b, &validator.predicate_a, &validator.node_a);
+
+        // The result doesn't depend on which operand is smaller.
+        for intersection in [set::intersection(&a, &b), set::intersection(&b, &a)] {
+            let intersection: HashGraph = intersection.collect();
+            assert_eq!(1, intersection.len());
+            assert!(intersection.contains(
+                &validator.node_a,
+                &validator.predicate_a,
+                &validator.node_b
+            ));
+        }
+        assert_eq!(0, set::intersection(&a, &HashGraph::new()).count());
+    }
+
     #[test]
     fn is_subset_superset() {
         let validator = Validator::new(HashGraph::new());
@@ -200,4 +248,30 @@ mod tests {
 
         assert!(set::is_disjoint(&a, &b));
     }
+
+    #[test]
+    fn unique_per_graph() {
+        let validator = Validator::new(HashGraph::new());
+        let (node_a, node_b, node_c) = (&validator.node_a, &validator.node_b, &validator.node_c);
+        let predicate_a = &validator.predicate_a;
+
+        let a = validator.graph.clone();
+        let mut b = HashGraph::new();
+        b.clone_insert(node_a, predicate_a, node_b);
+        b.clone_insert(node_b, predicate_a, node_a);
+        let mut c = HashGraph::new();
+        c.clone_insert(node_b, predicate_a, node_a);
+        c.clone_insert(node_c, predicate_a, node_a);
+
+        let unique = set::unique_per_graph(&[&a, &b, &c]);
+        assert_eq!(3, unique.len());
+        assert_eq!(2, unique[0].len());
+        assert!(unique[0].contains(node_b, &validator.predicate_b, node_c));
+        assert!(unique[0].contains(node_c, &validator.predicate_c, node_a));
+        assert!(unique[1].is_empty());
+        assert_eq!(1, unique[2].len());
+        assert!(unique[2].contains(node_c, predicate_a, node_a));
+
+        assert!(set::unique_per_graph(&[]).is_empty());
+    }
 }
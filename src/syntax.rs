@@ -0,0 +1,138 @@
+//! Parsers for the terms that N-Triples, N-Quads and Turtle have in common.
+//!
+//! This module is only compiled if one of the parser features is enabled, since it pulls in `nom`.
+use crate::blank_node_allocator::BlankNodes;
+use crate::Node;
+use nom::branch::*;
+use nom::bytes::complete::*;
+use nom::character::complete::*;
+use nom::combinator::*;
+use nom::error::ErrorKind;
+use nom::multi::*;
+use nom::sequence::*;
+use nom::{Err, IResult};
+use std::convert::TryFrom;
+
+/// A parsed term whose blank node label hasn't been resolved yet.
+#[derive(Clone)]
+pub(crate) enum Term<'a> {
+    Node(Node),
+    Blank(&'a str),
+}
+
+impl BlankNodes {
+    /// Return the node of a term, looking up the node of a blank node label.
+    pub fn resolve(&mut self, term: Term) -> Node {
+        match term {
+            Term::Node(node) => node,
+            Term::Blank(label) => self.get(label),
+        }
+    }
+}
+
+/// Return the part of `input` a nom parser failed on.
+pub(crate) fn unparsed<'a>(error: Err<(&'a str, ErrorKind)>, input: &'a str) -> &'a str {
+    match error {
+        Err::Error((rest, _)) | Err::Failure((rest, _)) => rest,
+        Err::Incomplete(_) => &input[input.len()..],
+    }
+}
+
+pub(crate) fn blank_label(i: &str) -> IResult<&str, &str> {
+    preceded(
+        tag("_:"),
+        take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-'),
+    )(i)
+}
+
+#[derive(Debug)]
+enum FromHexError {
+    ToCharError,
+    ToU32Error,
+}
+
+fn from_hex(input: &str) -> Result<char, FromHexError> {
+    char::try_from(u32::from_str_radix(input, 16).map_err(|_| FromHexError::ToU32Error)?)
+        .map_err(|_| FromHexError::ToCharError)
+}
+
+fn u16_char(i: &str) -> IResult<&str, char> {
+    let (i, (_, _, c)) = tuple((
+        char('\\'),
+        char('u'),
+        map_res(
+            take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),
+            from_hex,
+        ),
+    ))(i)?;
+    Ok((i, c))
+}
+
+fn u32_char(i: &str) -> IResult<&str, char> {
+    let (i, (_, _, c)) = tuple((
+        char('\\'),
+        char('U'),
+        map_res(
+            take_while_m_n(8, 8, |c: char| c.is_ascii_hexdigit()),
+            from_hex,
+        ),
+    ))(i)?;
+    Ok((i, c))
+}
+
+fn escaped_char(i: &str) -> IResult<&str, char> {
+    let (i, c) = preceded(char('\\'), one_of("tbnrf\"'\\"))(i)?;
+    let c = match c {
+        't' => '\t',
+        'b' => '\u{8}',
+        'n' => '\n',
+        'r' => '\r',
+        'f' => '\u{c}',
+        c => c,
+    };
+    Ok((i, c))
+}
+
+pub(crate) fn comment(i: &str) -> IResult<&str, &str> {
+    preceded(char('#'), take_while(|c| c != '\n' && c != '\r'))(i)
+}
+
+pub(crate) fn iriref(i: &str) -> IResult<&str, String> {
+    let (i, iri) = delimited(
+        char('<'),
+        many0(alt((none_of(r#"<>"{}|^`|\"#), u16_char, u32_char))),
+        char('>'),
+    )(i)?;
+
+    Ok((i, iri.into_iter().collect()))
+}
+
+pub(crate) fn string_literal(i: &str) -> IResult<&str, String> {
+    let (i, literal) = delimited(
+        char('"'),
+        many0(alt((none_of("\"\\\n\r"), escaped_char, u16_char, u32_char))),
+        char('"'),
+    )(i)?;
+
+    Ok((i, literal.into_iter().collect()))
+}
+
+/// Parse a language tag like `@en` or `@de-CH`, without the `@`.
+pub(crate) fn langtag(i: &str) -> IResult<&str, &str> {
+    preceded(
+        char('@'),
+        recognize(pair(alpha1, many0(pair(char('-'), alphanumeric1)))),
+    )(i)
+}
+
+#[test]
+fn test_iriref() {
+    assert_eq!(
+        (" abc", String::from("urn:Übung")),
+        iriref(r"<urn:\u00DCbung> abc").unwrap()
+    );
+    assert_eq!(
+        (" abc", String::from("urn:Übung")),
+        iriref(r"<urn:\U000000DCbung> abc").unwrap()
+    );
+}
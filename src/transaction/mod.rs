@@ -1,12 +1,19 @@
+use crate::diff::GraphDiff;
 use crate::{set, Graph, HashGraph, Node};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::ops::Deref;
-use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+use std::sync::{
+    Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError,
+};
+use std::time::{Duration, Instant};
 
 #[cfg(test)]
 mod tests;
 
 pub struct TransactionGraph<G> {
     graph: Arc<RwLock<IntTransactionGraph<G>>>,
+    history: Arc<Mutex<History>>,
 }
 
 struct IntTransactionGraph<G> {
@@ -20,10 +27,63 @@ impl<G> IntTransactionGraph<G> {
     }
 }
 
+/// The changes of recent commits, retained for [`IncrementalQuery`](struct.IncrementalQuery.html).
+///
+/// The changes of a commit are only recorded while there are incremental queries, and only kept
+/// until all of them have applied it.
+#[derive(Default)]
+struct History {
+    /// The changes of every commit, together with the revision it created.
+    diffs: VecDeque<(usize, GraphDiff)>,
+    /// The last revision seen by every incremental query, by the id of the query.
+    subscribers: HashMap<usize, usize>,
+    next_id: usize,
+}
+
+impl History {
+    /// Lock the history of a graph, even if it's poisoned.
+    ///
+    /// The history is only poisoned if the update function of an incremental query panicked, which
+    /// doesn't leave the recorded changes in an inconsistent state.
+    fn lock(history: &Mutex<History>) -> MutexGuard<'_, History> {
+        history.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn subscribe(&mut self, revision: usize) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.subscribers.insert(id, revision);
+        id
+    }
+
+    fn unsubscribe(&mut self, id: usize) {
+        self.subscribers.remove(&id);
+        self.prune();
+    }
+
+    /// Record the changes of a commit. This must only be called while there are subscribers.
+    fn record(&mut self, revision: usize, diff: GraphDiff) {
+        self.diffs.push_back((revision, diff));
+    }
+
+    /// Drop all changes that have been applied by every incremental query.
+    fn prune(&mut self) {
+        match self.subscribers.values().min() {
+            Some(&oldest) => {
+                while matches!(self.diffs.front(), Some((revision, _)) if *revision <= oldest) {
+                    self.diffs.pop_front();
+                }
+            }
+            None => self.diffs.clear(),
+        }
+    }
+}
+
 impl<G> Clone for TransactionGraph<G> {
     fn clone(&self) -> Self {
         Self {
             graph: self.graph.clone(),
+            history: self.history.clone(),
         }
     }
 }
@@ -35,54 +95,311 @@ impl<G: Graph + Default> Default for TransactionGraph<G> {
     }
 }
 
+/// The reason why a transaction couldn't be started.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransactionError {
+    /// Another transaction holds the lock, and starting this one would block.
+    WouldBlock,
+    /// A thread panicked while it held a mutable transaction.
+    ///
+    /// See [`TransactionGraph::clear_poison`](struct.TransactionGraph.html#method.clear_poison) on
+    /// how to recover from this.
+    Poisoned,
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionError::WouldBlock => f.write_str("another transaction is in progress"),
+            TransactionError::Poisoned => {
+                f.write_str("an active transaction panicked (graph is poisoned)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+impl<T> From<TryLockError<T>> for TransactionError {
+    fn from(error: TryLockError<T>) -> Self {
+        match error {
+            TryLockError::WouldBlock => TransactionError::WouldBlock,
+            TryLockError::Poisoned(_) => TransactionError::Poisoned,
+        }
+    }
+}
+
 impl<G: Graph> TransactionGraph<G> {
     pub fn new(graph: G) -> Self {
         Self {
             graph: Arc::new(RwLock::new(IntTransactionGraph::new(graph))),
+            history: Arc::new(Mutex::new(History::default())),
         }
     }
 
+    /// Return `true` if a thread panicked while it held a mutable transaction.
+    ///
+    /// While the graph is poisoned, the `try_*` methods return
+    /// [`TransactionError::Poisoned`](enum.TransactionError.html) and all other methods that start
+    /// a transaction panic.
+    pub fn is_poisoned(&self) -> bool {
+        self.graph.is_poisoned()
+    }
+
+    /// Clear the poisoned state of the graph, so that transactions can be started again.
+    ///
+    /// A mutable transaction only changes the graph when it's committed, so a thread that panics
+    /// while it holds one leaves the graph as it was before the transaction started. It's
+    /// therefore safe to continue using the graph after a panic, unless the panic happened within
+    /// the graph implementation while a commit was applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::transaction::{TransactionError, TransactionGraph};
+    /// use arrdf::{Graph, HashGraph, Node};
+    ///
+    /// let graph = TransactionGraph::new(HashGraph::new());
+    /// let other_graph = graph.clone();
+    /// std::thread::spawn(move || {
+    ///     let mut transaction = other_graph.mut_transaction();
+    ///     transaction.insert(Node::from("urn:a"), Node::from("urn:b"), Node::from("urn:c"));
+    ///     panic!("Something went wrong");
+    /// })
+    /// .join()
+    /// .unwrap_err();
+    ///
+    /// assert_eq!(Some(TransactionError::Poisoned), graph.try_transaction().err());
+    /// graph.clear_poison();
+    /// assert_eq!(0, graph.transaction().len());
+    /// ```
+    pub fn clear_poison(&self) {
+        self.graph.clear_poison();
+    }
+
     pub fn transaction(&self) -> Transaction<G> {
         Transaction::new(self.graph.read().unwrap())
     }
 
-    pub fn try_transaction(&self) -> Option<Transaction<G>> {
-        match self.graph.try_read() {
-            Ok(guard) => Some(Transaction::new(guard)),
-            Err(TryLockError::WouldBlock) => None,
-            #[cfg(not(tarpaulin_include))]
-            _ => panic!("An active transaction panicked (Graph is poisoned)"),
-        }
+    pub fn try_transaction(&self) -> Result<Transaction<G>, TransactionError> {
+        Ok(Transaction::new(self.graph.try_read()?))
     }
 
     pub fn mut_transaction(&self) -> MutTransaction<G> {
         self.graph
             .write()
-            .map(|guard| MutTransaction::new(guard))
+            .map(|guard| MutTransaction::new(guard, &self.history))
             .unwrap()
     }
 
-    pub fn try_mut_transaction(&self) -> Option<MutTransaction<G>> {
-        match self.graph.try_write() {
-            Ok(guard) => Some(MutTransaction::new(guard)),
-            Err(TryLockError::WouldBlock) => None,
-            #[cfg(not(tarpaulin_include))]
-            _ => panic!("An active transaction panicked (Graph is poisoned)"),
+    pub fn try_mut_transaction(&self) -> Result<MutTransaction<G>, TransactionError> {
+        Ok(MutTransaction::new(self.graph.try_write()?, &self.history))
+    }
+
+    /// Try to start a transaction, but give up after the timeout.
+    ///
+    /// `std::sync::RwLock` can't wait for a lock with a timeout, so this method repeatedly calls
+    /// [`try_transaction`](#method.try_transaction) and sleeps with an exponential backoff in
+    /// between. This is a best-effort approach: The lock isn't fair, and a transaction that becomes
+    /// available and is taken again between two attempts isn't noticed.
+    ///
+    /// If the timeout passes, [`TransactionError::WouldBlock`](enum.TransactionError.html) is
+    /// returned. A poisoned graph is reported immediately.
+    pub fn transaction_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Transaction<'_, G>, TransactionError> {
+        retry_until(timeout, || self.try_transaction())
+    }
+
+    /// Try to start a mutable transaction, but give up after the timeout.
+    ///
+    /// Just like [`transaction_timeout`](#method.transaction_timeout), this is a best-effort
+    /// approach based on [`try_mut_transaction`](#method.try_mut_transaction).
+    pub fn mut_transaction_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<MutTransaction<'_, G>, TransactionError> {
+        retry_until(timeout, || self.try_mut_transaction())
+    }
+
+    /// Run the closure in a mutable transaction and commit it if the closure succeeds.
+    ///
+    /// If the closure returns an error, the transaction is rolled back and the graph is left unchanged.
+    pub fn with_mut<F, R, E>(&self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut MutTransaction<G>) -> Result<R, E>,
+    {
+        let mut transaction = self.mut_transaction();
+        match f(&mut transaction) {
+            Ok(result) => {
+                transaction.commit();
+                Ok(result)
+            }
+            Err(error) => {
+                transaction.rollback();
+                Err(error)
+            }
         }
     }
 
+    pub fn revision(&self) -> usize {
+        self.graph.read().unwrap().revision
+    }
+
     pub fn cached_query<T, Q: FnMut(&G) -> T>(&self, query: Q) -> CachedQuery<T, G, Q> {
         let guard = self.graph.read().unwrap();
-        CachedQuery::new(self.clone(), guard, query)
+        CachedQuery::new(self.clone(), &guard, query)
+    }
+
+    pub fn cached_queries<T, Q: FnMut(&G) -> T>(
+        &self,
+        queries: Vec<Q>,
+    ) -> Vec<CachedQuery<T, G, Q>> {
+        let guard = self.graph.read().unwrap();
+        queries
+            .into_iter()
+            .map(|query| CachedQuery::new(self.clone(), &guard, query))
+            .collect()
+    }
+
+    /// Create a query whose result is updated from the changes of every commit.
+    ///
+    /// `init` computes the initial result from the current state of the graph. When the query is
+    /// [updated](struct.IncrementalQuery.html#method.update), `update` is called with the result
+    /// and the triples that were added and removed by every commit since the last update, in the
+    /// order of the commits. This is much cheaper than re-running an expensive query if only a few
+    /// triples change.
+    ///
+    /// The changes of a commit are retained by the graph until all incremental queries have been
+    /// updated, so a query that is rarely updated keeps the changes of all commits in memory.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::transaction::TransactionGraph;
+    /// use arrdf::{Graph, HashGraph, Node};
+    ///
+    /// let graph = TransactionGraph::new(HashGraph::new());
+    /// let mut count = graph.incremental_query(
+    ///     |graph| graph.len(),
+    ///     |count, added, removed| *count = *count + added.len() - removed.len(),
+    /// );
+    /// assert_eq!(0, *count);
+    ///
+    /// let mut transaction = graph.mut_transaction();
+    /// transaction.insert(Node::from("urn:a"), Node::from("urn:b"), Node::from("urn:c"));
+    /// transaction.commit();
+    ///
+    /// count.update();
+    /// assert_eq!(1, *count);
+    /// ```
+    pub fn incremental_query<T, I, U>(&self, init: I, update: U) -> IncrementalQuery<T, G, U>
+    where
+        I: FnOnce(&G) -> T,
+        U: FnMut(&mut T, &HashGraph, &HashGraph),
+    {
+        let guard = self.graph.read().unwrap();
+        let id = History::lock(&self.history).subscribe(guard.revision);
+        IncrementalQuery {
+            graph: self.clone(),
+            id,
+            result: init(&guard.graph),
+            update,
+            current_revision: guard.revision,
+        }
+    }
+
+    pub fn auto_cached_query<T, Q: FnMut(&G) -> T>(&self, query: Q) -> AutoCachedQuery<T, G, Q> {
+        AutoCachedQuery::new(self.cached_query(query))
+    }
+
+    pub fn try_cached_query<T, Q: FnMut(&G) -> T>(
+        &self,
+        query: Q,
+    ) -> Result<CachedQuery<T, G, Q>, TransactionError> {
+        let guard = self.graph.try_read()?;
+        Ok(CachedQuery::new(self.clone(), &guard, query))
     }
+}
+
+#[cfg(feature = "snapshot")]
+impl TransactionGraph<HashGraph> {
+    /// Serialize the graph, its revision and its [maximal literal length](../struct.HashGraph.html#method.max_literal_len)
+    /// to bytes, which can be turned back into a graph with [`restore`](#method.restore).
+    ///
+    /// The snapshot is taken within a transaction to get a consistent view of the graph, which
+    /// blocks all writers until the graph is serialized. Since blank nodes don't have names, they
+    /// are numbered in the snapshot as described in the [serde support](../index.html#serialization), and
+    /// the restored graph is isomorphic to the snapshotted one, but doesn't share its blank nodes.
+    ///
+    /// This method is only available with the `snapshot` feature.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use arrdf::transaction::TransactionGraph;
+    /// use arrdf::{iso, Graph, HashGraph, Node};
+    ///
+    /// let graph = TransactionGraph::new(HashGraph::new());
+    /// let mut transaction = graph.mut_transaction();
+    /// transaction.insert(Node::blank(), Node::from("urn:knows"), Node::from("urn:alice"));
+    /// transaction.commit();
+    ///
+    /// let restored = TransactionGraph::restore(&graph.snapshot()).unwrap();
+    /// assert_eq!(graph.revision(), restored.revision());
+    /// assert!(iso::is_isomorphic(&*graph.transaction(), &*restored.transaction()));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the graph is poisoned.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let transaction = self.transaction();
+        let graph = &transaction.guard.graph;
+        bincode::serialize(&(transaction.guard.revision, graph.max_literal_len(), graph))
+            .expect("Serializing a graph into memory never fails")
+    }
+
+    /// Restore a graph, its revision and its maximal literal length from a [snapshot](#method.snapshot).
+    ///
+    /// The restored graph doesn't have any history, so it starts without queries, and its blank
+    /// nodes are new ones. Returns an error if the bytes aren't a valid snapshot.
+    ///
+    /// This method is only available with the `snapshot` feature.
+    pub fn restore(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        let (revision, max_literal_len, mut graph): (usize, Option<usize>, HashGraph) =
+            bincode::deserialize(bytes)?;
+        graph.set_max_literal_len(max_literal_len);
+        let graph = Self::new(graph);
+        graph.graph.write().unwrap().revision = revision;
+        Ok(graph)
+    }
+}
 
-    pub fn try_cached_query<T, Q: FnMut(&G) -> T>(&self, query: Q) -> Option<CachedQuery<T, G, Q>> {
-        match self.graph.try_read() {
-            Ok(guard) => Some(CachedQuery::new(self.clone(), guard, query)),
-            Err(TryLockError::WouldBlock) => None,
-            #[cfg(not(tarpaulin_include))]
-            _ => panic!("An active transaction panicked (Graph is poisoned)"),
+/// Call `attempt` until it doesn't block anymore, sleeping with an exponential backoff in between.
+///
+/// Returns `TransactionError::WouldBlock` if the timeout has passed without success.
+fn retry_until<T, F>(timeout: Duration, mut attempt: F) -> Result<T, TransactionError>
+where
+    F: FnMut() -> Result<T, TransactionError>,
+{
+    const MIN_BACKOFF: Duration = Duration::from_micros(10);
+    const MAX_BACKOFF: Duration = Duration::from_millis(10);
+
+    let deadline = Instant::now() + timeout;
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        match attempt() {
+            Err(TransactionError::WouldBlock) => (),
+            result => return result,
         }
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(TransactionError::WouldBlock);
+        }
+        std::thread::sleep(backoff.min(deadline - now));
+        backoff = (backoff * 2).min(MAX_BACKOFF);
     }
 }
 
@@ -106,14 +423,19 @@ impl<'a, G> Deref for Transaction<'a, G> {
 
 pub struct MutTransaction<'a, G> {
     guard: RwLockWriteGuard<'a, IntTransactionGraph<G>>,
+    history: &'a Mutex<History>,
     added_triples: HashGraph,
     removed_triples: HashGraph,
 }
 
 impl<'a, G: Graph> MutTransaction<'a, G> {
-    fn new(guard: RwLockWriteGuard<'a, IntTransactionGraph<G>>) -> Self {
+    fn new(
+        guard: RwLockWriteGuard<'a, IntTransactionGraph<G>>,
+        history: &'a Mutex<History>,
+    ) -> Self {
         Self {
             guard,
+            history,
             added_triples: HashGraph::new(),
             removed_triples: HashGraph::new(),
         }
@@ -124,17 +446,120 @@ impl<'a, G: Graph> MutTransaction<'a, G> {
             && set::is_disjoint(&self.guard.graph, &self.added_triples)
     }
 
-    pub fn commit(mut self) {
+    /// Return the triples that will be added to the graph when the transaction is committed.
+    pub fn added(&self) -> &HashGraph {
+        &self.added_triples
+    }
+
+    /// Return the triples that will be removed from the graph when the transaction is committed.
+    pub fn removed(&self) -> &HashGraph {
+        &self.removed_triples
+    }
+
+    /// Return a read-only view of the graph as it would be after committing the transaction.
+    ///
+    /// The view is the transaction itself, so creating it is free and queries are answered from the
+    /// base graph and the staged changes. This makes it possible to pass the pending state to
+    /// consumers like the [`set`](../set/index.html) functions without allowing them to modify it.
+    pub fn as_graph(&self) -> &(impl Graph + 'a) {
+        self
+    }
+
+    /// Capture the changes of the transaction so far.
+    ///
+    /// Passing the savepoint to [`restore`](#method.restore) undoes all changes made after it was
+    /// created, while the changes made before it remain staged.
+    pub fn savepoint(&self) -> Savepoint {
+        Savepoint {
+            added_triples: self.added_triples.clone(),
+            removed_triples: self.removed_triples.clone(),
+        }
+    }
+
+    /// Reset the changes of the transaction to the state of a savepoint.
+    ///
+    /// A savepoint may also be restored in a later transaction of the same graph, which stages the
+    /// captured changes again. Changes that have already been committed in the meantime are
+    /// dropped, since they don't change the graph anymore.
+    pub fn restore(&mut self, savepoint: Savepoint) {
+        let graph = &self.guard.graph;
+        self.added_triples = savepoint.added_triples;
+        self.added_triples
+            .retain(|s, p, o| !graph.contains(s, p, o));
+        self.removed_triples = savepoint.removed_triples;
+        self.removed_triples
+            .retain(|s, p, o| graph.contains(s, p, o));
+
         if cfg!(test) {
             assert!(self.is_valid());
         }
+    }
+
+    fn stats(&self) -> TransactionStats {
+        TransactionStats {
+            added: self.added_triples.len(),
+            removed: self.removed_triples.len(),
+            revision: self.guard.revision,
+        }
+    }
 
+    pub fn commit(mut self) -> TransactionStats {
+        if cfg!(test) {
+            assert!(self.is_valid());
+        }
+
+        let mut stats = self.stats();
+        let mut history = History::lock(self.history);
         self.guard.graph.remove_all(self.removed_triples.iter());
-        self.guard.graph.extend(self.added_triples.into_iter());
         self.guard.revision += 1;
+        stats.revision = self.guard.revision;
+
+        // The changes only have to be kept if an incremental query is going to apply them.
+        if history.subscribers.is_empty() {
+            self.guard.graph.extend(self.added_triples);
+        } else {
+            self.guard.graph.clone_extend(self.added_triples.iter());
+            let diff = GraphDiff {
+                added: self.added_triples,
+                removed: self.removed_triples,
+            };
+            history.record(stats.revision, diff);
+        }
+        stats
+    }
+
+    /// Discard all changes of the transaction.
+    ///
+    /// This has the same effect as dropping the transaction, but makes the intent explicit.
+    /// The returned statistics describe the discarded changes.
+    pub fn rollback(mut self) -> TransactionStats {
+        let stats = self.stats();
+        self.added_triples.clear();
+        self.removed_triples.clear();
+        stats
     }
 }
 
+/// The staged changes of a [`MutTransaction`](struct.MutTransaction.html) at some point in time.
+///
+/// Savepoints are created by [`MutTransaction::savepoint`](struct.MutTransaction.html#method.savepoint)
+/// and restored by [`MutTransaction::restore`](struct.MutTransaction.html#method.restore).
+#[derive(Clone, Debug)]
+pub struct Savepoint {
+    added_triples: HashGraph,
+    removed_triples: HashGraph,
+}
+
+/// The number of triples that were added to and removed from a graph by a transaction.
+///
+/// `revision` is the revision of the graph after the transaction, which is only incremented by commits.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TransactionStats {
+    pub added: usize,
+    pub removed: usize,
+    pub revision: usize,
+}
+
 impl<'a, G: Graph> Graph for MutTransaction<'a, G> {
     fn len(&self) -> usize {
         self.guard.graph.len() + self.added_triples.len() - self.removed_triples.len()
@@ -203,6 +628,35 @@ impl<'a, G: Graph> Graph for MutTransaction<'a, G> {
             assert!(self.is_valid());
         }
     }
+
+    fn relationships<'b>(
+        &'b self,
+        subject: &'b Node,
+    ) -> Box<dyn 'b + Iterator<Item = (&'b Node, &'b Node, &'b Node)>> {
+        let removed_triples = &self.removed_triples;
+        Box::new(
+            self.guard
+                .graph
+                .relationships(subject)
+                .filter(move |(s, p, o)| !removed_triples.contains(s, p, o))
+                .chain(self.added_triples.relationships(subject)),
+        )
+    }
+
+    fn objects<'b>(
+        &'b self,
+        subject: &'b Node,
+        predicate: &'b Node,
+    ) -> Box<dyn 'b + Iterator<Item = (&'b Node, &'b Node, &'b Node)>> {
+        let removed_triples = &self.removed_triples;
+        Box::new(
+            self.guard
+                .graph
+                .objects(subject, predicate)
+                .filter(move |(s, p, o)| !removed_triples.contains(s, p, o))
+                .chain(self.added_triples.objects(subject, predicate)),
+        )
+    }
 }
 
 pub struct CachedQuery<T, G, Q> {
@@ -213,16 +667,12 @@ pub struct CachedQuery<T, G, Q> {
 }
 
 impl<T, G, Q: FnMut(&G) -> T> CachedQuery<T, G, Q> {
-    fn new(
-        graph: TransactionGraph<G>,
-        guard: RwLockReadGuard<IntTransactionGraph<G>>,
-        mut query: Q,
-    ) -> Self {
+    fn new(graph: TransactionGraph<G>, inner: &IntTransactionGraph<G>, mut query: Q) -> Self {
         Self {
             graph,
-            result: query(&guard.graph),
+            result: query(&inner.graph),
             query,
-            current_revision: guard.revision,
+            current_revision: inner.revision,
         }
     }
 }
@@ -245,3 +695,97 @@ impl<T, G, Q> Deref for CachedQuery<T, G, Q> {
         &self.result
     }
 }
+
+/// A query whose result is updated from the changes of every commit.
+///
+/// Incremental queries are created by
+/// [`TransactionGraph::incremental_query`](struct.TransactionGraph.html#method.incremental_query).
+/// Just like a [`CachedQuery`](struct.CachedQuery.html), the result is only updated when
+/// [`update`](#method.update) is called.
+pub struct IncrementalQuery<T, G, U> {
+    graph: TransactionGraph<G>,
+    id: usize,
+    result: T,
+    update: U,
+    current_revision: usize,
+}
+
+impl<T, G: Graph, U: FnMut(&mut T, &HashGraph, &HashGraph)> IncrementalQuery<T, G, U> {
+    /// Apply the changes of all commits since the last update to the result.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the graph is poisoned, just like
+    /// [`TransactionGraph::transaction`](struct.TransactionGraph.html#method.transaction).
+    pub fn update(&mut self) {
+        let guard = self.graph.graph.read().unwrap();
+        if guard.revision > self.current_revision {
+            let mut history = History::lock(&self.graph.history);
+            for (revision, diff) in history.diffs.iter() {
+                if *revision > self.current_revision {
+                    (self.update)(&mut self.result, &diff.added, &diff.removed);
+                }
+            }
+            self.current_revision = guard.revision;
+            history.subscribers.insert(self.id, guard.revision);
+            history.prune();
+        }
+    }
+}
+
+impl<T, G, U> Deref for IncrementalQuery<T, G, U> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.result
+    }
+}
+
+impl<T, G, U> Drop for IncrementalQuery<T, G, U> {
+    fn drop(&mut self) {
+        History::lock(&self.graph.history).unsubscribe(self.id);
+    }
+}
+
+/// A cached query that is always up to date.
+///
+/// In contrast to [`CachedQuery`](struct.CachedQuery.html), which has to be updated manually, the
+/// result returned by [`get`](#method.get) always reflects the latest committed revision of the graph.
+/// This comes at a cost: Every access locks an internal mutex and acquires a read lock of the graph
+/// to check its revision, which blocks while a mutable transaction is in progress. Therefore, you
+/// must not access the query while the same thread holds a mutable transaction of the graph. If
+/// the result is read often, a `CachedQuery` that is updated at well-defined points is cheaper.
+///
+/// Since the result may be replaced by every access, it can't be borrowed directly from the query.
+/// Instead, `get` returns a guard that dereferences to the result and keeps the query locked.
+pub struct AutoCachedQuery<T, G, Q> {
+    query: Mutex<CachedQuery<T, G, Q>>,
+}
+
+impl<T, G: Graph, Q: FnMut(&G) -> T> AutoCachedQuery<T, G, Q> {
+    fn new(query: CachedQuery<T, G, Q>) -> Self {
+        Self {
+            query: Mutex::new(query),
+        }
+    }
+
+    /// Re-run the query if the graph has changed and return the current result.
+    pub fn get(&self) -> AutoCachedQueryGuard<'_, T, G, Q> {
+        let mut query = self.query.lock().unwrap();
+        query.update();
+        AutoCachedQueryGuard { query }
+    }
+}
+
+/// A guard that dereferences to the current result of an [`AutoCachedQuery`](struct.AutoCachedQuery.html).
+pub struct AutoCachedQueryGuard<'a, T, G, Q> {
+    query: MutexGuard<'a, CachedQuery<T, G, Q>>,
+}
+
+impl<'a, T, G, Q> Deref for AutoCachedQueryGuard<'a, T, G, Q> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.query.result
+    }
+}
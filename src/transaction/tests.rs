@@ -45,7 +45,10 @@ fn try_query() {
 
     let _transaction = graph.mut_transaction();
 
-    assert!(graph.try_transaction().is_none());
+    assert_eq!(
+        Some(TransactionError::WouldBlock),
+        graph.try_transaction().err()
+    );
 }
 
 #[test]
@@ -62,7 +65,7 @@ fn cached_query() {
     transaction.clone_insert(node_a, predicate_a, node_a);
 
     // Assert that we can't run a query since a mutable transaction is in progress.
-    assert!(graph.try_cached_query(|g| g.len()).is_none());
+    assert!(graph.try_cached_query(|g| g.len()).is_err());
 
     transaction.commit();
 
@@ -82,14 +85,621 @@ fn mut_transaction() {
     validator.validate();
 }
 
+#[test]
+fn retain() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let node_c = &validator.node_c;
+    let predicate_a = &validator.predicate_a;
+    let predicate_b = &validator.predicate_b;
+
+    // Retain only triples with a blank object, including a pending one.
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_c);
+    transaction.retain(|_, _, object| object.is_blank());
+    assert_eq!(2, transaction.len());
+    assert!(transaction.contains(node_b, predicate_b, node_c));
+    assert!(transaction.contains(node_a, predicate_a, node_c));
+    assert!(!transaction.contains(node_a, predicate_a, node_b));
+    transaction.commit();
+
+    let transaction = graph.transaction();
+    assert_eq!(2, transaction.len());
+    assert!(transaction.contains(node_b, predicate_b, node_c));
+    assert!(transaction.contains(node_a, predicate_a, node_c));
+}
+
 #[test]
 fn try_mut_transaction() {
     let graph = TransactionGraph::new(HashGraph::new());
 
     // Assert that we can start a mutable transaction now.
-    assert!(graph.try_mut_transaction().is_some());
+    assert!(graph.try_mut_transaction().is_ok());
 
     // Start an immutable transaction and assert that no mutable transaction can be started.
     let _transaction = graph.transaction();
-    assert!(graph.try_mut_transaction().is_none());
+    assert!(graph.try_mut_transaction().is_err());
+}
+
+#[test]
+fn transaction_timeouts() {
+    use std::time::{Duration, Instant};
+
+    let graph = TransactionGraph::new(HashGraph::new());
+    let timeout = Duration::from_millis(20);
+    assert!(graph.transaction_timeout(timeout).is_ok());
+    assert!(graph.mut_transaction_timeout(timeout).is_ok());
+
+    let transaction = graph.transaction();
+    let start = Instant::now();
+    assert!(graph.mut_transaction_timeout(timeout).is_err());
+    assert!(start.elapsed() >= timeout);
+    assert!(graph.transaction_timeout(timeout).is_ok());
+
+    // The lock is acquired as soon as another thread releases it.
+    let other_graph = graph.clone();
+    let handle = std::thread::spawn(move || {
+        other_graph
+            .mut_transaction_timeout(Duration::from_secs(10))
+            .is_ok()
+    });
+    std::thread::sleep(Duration::from_millis(5));
+    drop(transaction);
+    assert!(handle.join().unwrap());
+
+    let _transaction = graph.mut_transaction();
+    assert!(graph.transaction_timeout(timeout).is_err());
+    assert!(graph
+        .mut_transaction_timeout(Duration::from_secs(0))
+        .is_err());
+}
+
+#[test]
+fn poisoned_graph() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = validator.node_a.clone();
+    let node_b = validator.node_b.clone();
+    let predicate_a = validator.predicate_a.clone();
+
+    let other_graph = graph.clone();
+    let result = std::thread::spawn(move || {
+        let mut transaction = other_graph.mut_transaction();
+        transaction.remove(&node_a, &predicate_a, &node_b);
+        transaction.clear();
+        panic!("Aborting the transaction");
+    })
+    .join();
+    assert!(result.is_err());
+
+    assert!(graph.is_poisoned());
+    let poisoned = Some(TransactionError::Poisoned);
+    assert_eq!(poisoned, graph.try_transaction().err());
+    assert_eq!(poisoned, graph.try_mut_transaction().err());
+    assert_eq!(poisoned, graph.try_cached_query(|g| g.len()).err());
+    assert_eq!(
+        poisoned,
+        graph
+            .transaction_timeout(std::time::Duration::from_secs(10))
+            .err()
+    );
+
+    // The uncommitted changes of the panicking transaction are gone.
+    graph.clear_poison();
+    assert!(!graph.is_poisoned());
+    assert_eq!(3, graph.try_transaction().unwrap().len());
+    assert_eq!(0, graph.revision());
+}
+
+#[test]
+fn incremental_query() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let count_predicate_a = |added: &HashGraph, removed: &HashGraph| {
+        let count = |graph: &HashGraph| graph.iter().filter(|(_, p, _)| *p == predicate_a).count();
+        (count(added), count(removed))
+    };
+    let mut updates = 0;
+    let mut query = graph.incremental_query(
+        |g| g.iter().filter(|(_, p, _)| *p == predicate_a).count(),
+        |count, added, removed| {
+            let (added, removed) = count_predicate_a(added, removed);
+            *count = *count + added - removed;
+            updates += 1;
+        },
+    );
+    assert_eq!(1, *query);
+
+    // Rolled back transactions aren't recorded.
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_b, predicate_a, node_a);
+    transaction.rollback();
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.clone_insert(node_b, predicate_a, node_a);
+    transaction.commit();
+    let mut transaction = graph.mut_transaction();
+    transaction.remove(node_a, predicate_a, node_b);
+    transaction.commit();
+    assert_eq!(2, graph.history.lock().unwrap().diffs.len());
+
+    query.update();
+    assert_eq!(2, *query);
+    assert!(graph.history.lock().unwrap().diffs.is_empty());
+
+    // A second query keeps the changes until both queries have seen them.
+    let mut other_query = graph.incremental_query(
+        |g| g.len(),
+        |len, added, removed| *len = *len + added.len() - removed.len(),
+    );
+    assert_eq!(4, *other_query);
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_b, predicate_a, node_b);
+    transaction.commit();
+
+    other_query.update();
+    assert_eq!(5, *other_query);
+    assert_eq!(1, graph.history.lock().unwrap().diffs.len());
+    query.update();
+    assert_eq!(3, *query);
+    assert!(graph.history.lock().unwrap().diffs.is_empty());
+
+    // Updating without new commits doesn't call the closure.
+    query.update();
+    drop(query);
+    assert_eq!(3, updates);
+
+    drop(other_query);
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_b);
+    transaction.commit();
+    assert!(graph.history.lock().unwrap().diffs.is_empty());
+    assert!(graph.history.lock().unwrap().subscribers.is_empty());
+}
+
+#[test]
+fn poisoned_history() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let predicate_a = &validator.predicate_a;
+
+    // An update function that panics while it holds the history poisons it.
+    let other_graph = graph.clone();
+    let result = std::thread::spawn(move || {
+        let mut query = other_graph.incremental_query(|_| (), |_, _, _| panic!("Failing update"));
+        let mut transaction = other_graph.mut_transaction();
+        transaction.clear();
+        transaction.commit();
+        query.update();
+    })
+    .join();
+    assert!(result.is_err());
+    assert!(graph.history.is_poisoned());
+
+    // Commits and incremental queries still work, and the graph isn't poisoned.
+    let mut query = graph.incremental_query(|g| g.len(), |len, added, _| *len += added.len());
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.commit();
+    assert!(!graph.is_poisoned());
+    query.update();
+    assert_eq!(1, *query);
+
+    // Updating a query of a poisoned graph panics instead of keeping a stale result.
+    let other_graph = graph.clone();
+    let _ = std::thread::spawn(move || {
+        let _transaction = other_graph.mut_transaction();
+        panic!("Aborting the transaction");
+    })
+    .join();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| query.update()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn cached_queries() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let predicate_a = &validator.predicate_a;
+
+    type Query<'a> = Box<dyn 'a + FnMut(&HashGraph) -> usize>;
+    let queries: Vec<Query> = vec![
+        Box::new(|g| g.len()),
+        Box::new(|g| g.relationships(node_a).count()),
+        Box::new(|g| g.iter().filter(|(_, p, _)| *p == predicate_a).count()),
+    ];
+    let mut queries = graph.cached_queries(queries);
+    assert_eq!(3, queries.len());
+    assert_eq!(3, *queries[0]);
+    assert_eq!(1, *queries[1]);
+    assert_eq!(1, *queries[2]);
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.commit();
+
+    for query in queries.iter_mut() {
+        query.update();
+    }
+    assert_eq!(4, *queries[0]);
+    assert_eq!(2, *queries[1]);
+    assert_eq!(2, *queries[2]);
+}
+
+#[test]
+fn cancelling_changes() {
+    let validator = Validator::new(HashGraph::new());
+    let original = validator.graph.clone();
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let mut transaction = graph.mut_transaction();
+
+    // Inserting and then removing a triple that isn't in the base graph.
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    assert_eq!(4, transaction.len());
+    assert!(transaction.contains(node_a, predicate_a, node_a));
+    transaction.remove(node_a, predicate_a, node_a);
+    assert_eq!(3, transaction.len());
+    assert!(!transaction.contains(node_a, predicate_a, node_a));
+
+    // Removing and then inserting a triple of the base graph.
+    transaction.remove(node_a, predicate_a, node_b);
+    assert_eq!(2, transaction.len());
+    assert!(!transaction.contains(node_a, predicate_a, node_b));
+    transaction.clone_insert(node_a, predicate_a, node_b);
+    assert_eq!(3, transaction.len());
+    assert!(transaction.contains(node_a, predicate_a, node_b));
+
+    let committed: HashGraph = transaction.iter().collect();
+    assert_eq!(original, committed);
+    transaction.commit();
+    assert_eq!(original, *graph.transaction());
+}
+
+#[test]
+fn redundant_changes() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let mut transaction = graph.mut_transaction();
+
+    // Removing a triple that isn't in the base graph and then inserting it.
+    transaction.remove(node_a, predicate_a, node_a);
+    assert_eq!(3, transaction.len());
+    assert!(!transaction.contains(node_a, predicate_a, node_a));
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    assert_eq!(4, transaction.len());
+    assert!(transaction.contains(node_a, predicate_a, node_a));
+
+    // Inserting a triple of the base graph and then removing it.
+    transaction.clone_insert(node_a, predicate_a, node_b);
+    assert_eq!(4, transaction.len());
+    assert!(transaction.contains(node_a, predicate_a, node_b));
+    transaction.remove(node_a, predicate_a, node_b);
+    assert_eq!(3, transaction.len());
+    assert!(!transaction.contains(node_a, predicate_a, node_b));
+
+    transaction.commit();
+
+    let transaction = graph.transaction();
+    assert_eq!(3, transaction.len());
+    assert!(transaction.contains(node_a, predicate_a, node_a));
+    assert!(!transaction.contains(node_a, predicate_a, node_b));
+}
+
+#[test]
+fn lookups() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let node_c = &validator.node_c;
+    let predicate_a = &validator.predicate_a;
+    let predicate_b = &validator.predicate_b;
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_b, node_c);
+    transaction.clone_insert(node_a, predicate_a, node_c);
+    transaction.remove(node_a, predicate_a, node_b);
+
+    let relationships: HashGraph = transaction.relationships(node_a).collect();
+    assert_eq!(2, relationships.len());
+    assert!(relationships.contains(node_a, predicate_b, node_c));
+    assert!(relationships.contains(node_a, predicate_a, node_c));
+
+    let objects: Vec<&Node> = transaction
+        .objects(node_a, predicate_a)
+        .map(|(_, _, o)| o)
+        .collect();
+    assert_eq!(vec![node_c], objects);
+    assert_eq!(0, transaction.objects(node_b, predicate_a).count());
+    assert_eq!(1, transaction.relationships(node_b).count());
+}
+
+#[test]
+fn insert_existing_triple() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_b);
+    transaction.clone_insert(node_a, predicate_a, node_b);
+    assert_eq!(3, transaction.len());
+    assert_eq!(3, transaction.iter().count());
+    assert!(transaction.added().is_empty());
+    assert_eq!(0, transaction.commit().added);
+    assert_eq!(3, graph.transaction().len());
+}
+
+#[test]
+fn commit_stats() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.clone_insert(node_b, predicate_a, node_b);
+    transaction.remove(node_a, predicate_a, node_b);
+    assert_eq!(
+        TransactionStats {
+            added: 2,
+            removed: 1,
+            revision: 1,
+        },
+        transaction.commit()
+    );
+    assert_eq!(4, graph.transaction().len());
+    assert_eq!(1, graph.revision());
+
+    assert_eq!(2, graph.mut_transaction().commit().revision);
+    assert_eq!(2, graph.revision());
+}
+
+#[test]
+fn rollback() {
+    let validator = Validator::new(HashGraph::new());
+    let original = validator.graph.clone();
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.remove(node_a, predicate_a, node_b);
+    assert_eq!(
+        TransactionStats {
+            added: 1,
+            removed: 1,
+            revision: 0,
+        },
+        transaction.rollback()
+    );
+    assert_eq!(0, graph.revision());
+
+    assert_eq!(original, *graph.transaction());
+}
+
+#[test]
+fn pending_changes() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let mut transaction = graph.mut_transaction();
+    assert!(transaction.added().is_empty());
+    assert!(transaction.removed().is_empty());
+
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.remove(node_a, predicate_a, node_b);
+    assert_eq!(1, transaction.added().len());
+    assert!(transaction.added().contains(node_a, predicate_a, node_a));
+    assert_eq!(1, transaction.removed().len());
+    assert!(transaction.removed().contains(node_a, predicate_a, node_b));
+
+    // Cancelled changes aren't staged anymore.
+    transaction.remove(node_a, predicate_a, node_a);
+    transaction.clone_insert(node_a, predicate_a, node_b);
+    assert!(transaction.added().is_empty());
+    assert!(transaction.removed().is_empty());
+}
+
+#[test]
+fn pending_view() {
+    let validator = Validator::new(HashGraph::new());
+    let baseline = validator.graph.clone();
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.remove(node_a, predicate_a, node_b);
+
+    let view = transaction.as_graph();
+    assert_eq!(3, view.len());
+    let added: Vec<(&Node, &Node, &Node)> = set::difference(view, &baseline).collect();
+    assert_eq!(vec![(node_a, predicate_a, node_a)], added);
+    let removed: Vec<(&Node, &Node, &Node)> = set::difference(&baseline, view).collect();
+    assert_eq!(vec![(node_a, predicate_a, node_b)], removed);
+    assert_eq!(4, set::union(view, &baseline).count());
+}
+
+#[test]
+fn with_mut() {
+    let validator = Validator::new(HashGraph::new());
+    let original = validator.graph.clone();
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let predicate_a = &validator.predicate_a;
+
+    let result: Result<usize, &str> = graph.with_mut(|transaction| {
+        transaction.clone_insert(node_a, predicate_a, node_a);
+        transaction.remove(node_a, predicate_a, node_b);
+        Err("Something went wrong")
+    });
+    assert_eq!(Err("Something went wrong"), result);
+    assert_eq!(original, *graph.transaction());
+    assert_eq!(0, graph.revision());
+
+    let result: Result<usize, &str> = graph.with_mut(|transaction| {
+        transaction.clone_insert(node_a, predicate_a, node_a);
+        transaction.remove(node_a, predicate_a, node_b);
+        Ok(transaction.len())
+    });
+    assert_eq!(Ok(3), result);
+    assert_eq!(1, graph.revision());
+
+    let transaction = graph.transaction();
+    assert!(transaction.contains(node_a, predicate_a, node_a));
+    assert!(!transaction.contains(node_a, predicate_a, node_b));
+}
+
+#[test]
+fn auto_cached_query() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let predicate_a = &validator.predicate_a;
+
+    let query = graph.auto_cached_query(|g| g.len());
+    assert_eq!(3, *query.get());
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    transaction.commit();
+
+    // No manual update required.
+    assert_eq!(4, *query.get());
+
+    graph.mut_transaction().rollback();
+    assert_eq!(4, *query.get());
+}
+
+#[test]
+fn savepoints() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let node_a = &validator.node_a;
+    let node_b = &validator.node_b;
+    let node_c = &validator.node_c;
+    let predicate_a = &validator.predicate_a;
+    let predicate_b = &validator.predicate_b;
+
+    let mut transaction = graph.mut_transaction();
+    transaction.clone_insert(node_a, predicate_a, node_a);
+    let savepoint = transaction.savepoint();
+
+    transaction.clone_insert(node_b, predicate_a, node_b);
+    transaction.remove(node_a, predicate_a, node_b);
+    transaction.remove(node_b, predicate_b, node_c);
+    assert_eq!(3, transaction.len());
+
+    transaction.restore(savepoint.clone());
+    assert_eq!(4, transaction.len());
+    assert!(transaction.contains(node_a, predicate_a, node_a));
+    assert!(!transaction.contains(node_b, predicate_a, node_b));
+    assert!(transaction.contains(node_a, predicate_a, node_b));
+
+    // A savepoint can be restored more than once.
+    transaction.remove(node_a, predicate_a, node_b);
+    let second_savepoint = transaction.savepoint();
+    transaction.restore(savepoint.clone());
+    assert!(transaction.contains(node_a, predicate_a, node_b));
+    assert_eq!(
+        TransactionStats {
+            added: 1,
+            removed: 0,
+            revision: 1
+        },
+        transaction.commit()
+    );
+    assert_eq!(4, graph.transaction().len());
+
+    // In a later transaction, changes that were committed in the meantime are dropped.
+    let mut transaction = graph.mut_transaction();
+    transaction.restore(second_savepoint);
+    assert!(transaction.added().is_empty());
+    assert_eq!(1, transaction.removed().len());
+    assert!(!transaction.contains(node_a, predicate_a, node_b));
+    transaction.commit();
+    assert_eq!(3, graph.transaction().len());
+    assert!(graph.transaction().contains(node_a, predicate_a, node_a));
+}
+
+#[test]
+#[cfg(feature = "snapshot")]
+fn snapshot() {
+    let validator = Validator::new(HashGraph::new());
+    let graph = TransactionGraph::new(validator.graph);
+    let mut transaction = graph.mut_transaction();
+    let label = Node::literal("chat").with_language("fr").build().unwrap();
+    let count = Node::literal("3")
+        .with_datatype(Node::from("http://www.w3.org/2001/XMLSchema#integer"))
+        .build()
+        .unwrap();
+    transaction.clone_insert(&validator.node_c, &validator.predicate_a, &label);
+    transaction.clone_insert(&validator.node_c, &validator.predicate_b, &count);
+    transaction.commit();
+
+    let restored = TransactionGraph::restore(&graph.snapshot()).unwrap();
+    assert_eq!(1, restored.revision());
+    assert_eq!(5, restored.transaction().len());
+    assert!(iso::is_isomorphic(
+        &*graph.transaction(),
+        &*restored.transaction()
+    ));
+    // The blank node is restored as a new one, but it's still the same node in all its triples.
+    let restored_transaction = restored.transaction();
+    let (blank, _, _) = restored_transaction
+        .iter()
+        .find(|(_, _, o)| **o == label)
+        .unwrap();
+    assert_ne!(&validator.node_c, blank);
+    assert!(restored_transaction.contains(blank, &validator.predicate_b, &count));
+    assert!(restored_transaction.contains(blank, &validator.predicate_c, &validator.node_a));
+    assert!(restored_transaction.contains(&validator.node_b, &validator.predicate_b, blank));
+
+    assert!(TransactionGraph::restore(&[1, 2, 3]).is_err());
+
+    // The maximal literal length of a graph survives the snapshot.
+    let mut limited = HashGraph::with_max_literal_len(5);
+    limited.clone_insert(&validator.node_a, &validator.predicate_a, &label);
+    let graph = TransactionGraph::new(limited);
+    let restored = TransactionGraph::restore(&graph.snapshot()).unwrap();
+    assert_eq!(Some(5), restored.transaction().max_literal_len());
+    assert_eq!(1, restored.transaction().len());
+    let mut transaction = restored.mut_transaction();
+    transaction.insert(
+        validator.node_a.clone(),
+        validator.predicate_b.clone(),
+        Node::from("Hello World"),
+    );
+    transaction.commit();
+    assert_eq!(1, restored.transaction().len());
 }
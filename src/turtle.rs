@@ -1,59 +1,648 @@
+//! A parser and serializer for the [Turtle](https://www.w3.org/TR/turtle/) serialization format.
+//!
+//! The parser currently supports a subset of Turtle: `@prefix` and `@base` directives, IRIs,
+//! prefixed names, blank node labels, string literals with optional datatypes or language tags, and
+//! the numeric and boolean literal shorthands. Relative IRIs are resolved against the last
+//! `@base`. Every statement is inserted into a [`HashGraph`](../struct.HashGraph.html).
+//!
+//! This module is only available with the `turtle` feature.
+use crate::blank_node_allocator::BlankNodes;
+use crate::node::{escape, resolve_reference};
+use crate::prefix_map::is_name_char;
+use crate::syntax::{blank_label, comment, iriref, langtag, string_literal, unparsed, Term};
+use crate::vocab::{rdf, xsd};
+use crate::{BlankNodeAllocator, Graph, HashGraph, Node, NodeError, NodeKind, PrefixMap};
 use nom::branch::*;
-use nom::bytes::streaming::*;
-use nom::character::streaming::*;
+use nom::bytes::complete::*;
+use nom::character::complete::*;
 use nom::combinator::*;
 use nom::multi::*;
 use nom::sequence::*;
 use nom::*;
-use std::convert::TryFrom;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
 
-#[derive(Debug)]
-enum FromHexError {
-    ToCharError,
-    ToU32Error,
+pub use crate::ParseError;
+
+/// The prefixes and the base IRI declared so far in a document.
+#[derive(Default)]
+struct Context {
+    prefixes: PrefixMap,
+    base: Option<String>,
 }
 
-fn from_hex(input: &str) -> Result<char, FromHexError> {
-    char::try_from(u32::from_str_radix(input, 16).map_err(|_| FromHexError::ToU32Error)?)
-        .map_err(|_| FromHexError::ToCharError)
+impl Context {
+    /// Resolve an IRI reference against the base IRI, if one was declared.
+    fn resolve(&self, reference: &str) -> Result<String, NodeError> {
+        match self.base.as_ref() {
+            Some(base) => resolve_reference(reference, base).map(|iri| iri.as_str().to_owned()),
+            None => Ok(reference.to_owned()),
+        }
+    }
 }
 
-fn u16_char(i: &str) -> IResult<&str, char> {
-    let (i, (_, _, c)) = tuple((
-        char('\\'),
-        char('u'),
-        map_res(take_while_m_n(4, 4, |c: char| c.is_digit(16)), from_hex),
-    ))(i)?;
-    Ok((i, c))
+/// Parse a Turtle document and collect all triples in a graph.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{Node, Graph};
+///
+/// let graph = arrdf::turtle::parse(r#"
+///     @prefix ex: <http://example.org/> .
+///     ex:alice ex:age 42 .
+/// "#).unwrap();
+///
+/// assert!(graph.contains(
+///     &Node::from("http://example.org/alice"),
+///     &Node::from("http://example.org/age"),
+///     &Node::typed_literal("42", "http://www.w3.org/2001/XMLSchema#integer"),
+/// ));
+/// ```
+pub fn parse(input: &str) -> Result<HashGraph, ParseError> {
+    let mut graph = HashGraph::new();
+    graph.extend(parse_to_triples(input)?);
+    Ok(graph)
+}
+
+/// Parse a Turtle document and return all triples in the order they're written.
+///
+/// In contrast to [`parse`](fn.parse.html), duplicate triples aren't removed, which is useful to
+/// check exactly what the parser emits.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::Node;
+///
+/// let triples = arrdf::turtle::parse_to_triples(r#"
+///     <http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+///     <http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+/// "#).unwrap();
+///
+/// assert_eq!(2, triples.len());
+/// assert_eq!(triples[0], triples[1]);
+/// ```
+pub fn parse_to_triples(input: &str) -> Result<Vec<(Node, Node, Node)>, ParseError> {
+    let mut triples = Vec::new();
+    let mut context = Context::default();
+    let mut blanks = BlankNodes::default();
+    let error = |error, context: &Context| {
+        let rest = unparsed(error, input);
+        ParseError::at(input, rest, expectation(rest, context))
+    };
+
+    let (mut i, _) = whitespace(input).map_err(|e| error(e, &context))?;
+    while !i.is_empty() {
+        if let Ok((rest, (prefix, iri))) = prefix_directive(i) {
+            let namespace = context
+                .resolve(&iri)
+                .map_err(|e| ParseError::at(input, i, e.to_string()))?;
+            context.prefixes.insert(prefix, &namespace);
+            i = rest;
+        } else if let Ok((rest, iri)) = base_directive(i) {
+            let base = context
+                .resolve(&iri)
+                .ok()
+                .filter(|base| Node::from(base.as_str()).is_iri())
+                .ok_or_else(|| ParseError::at(input, i, NodeError::InvalidBase(iri).to_string()))?;
+            context.base = Some(base);
+            i = rest;
+        } else {
+            let (rest, statement_triples) =
+                self::triples(i, &context).map_err(|e| error(e, &context))?;
+            triples.extend(
+                statement_triples
+                    .into_iter()
+                    .map(|(s, p, o)| (blanks.resolve(s), p, blanks.resolve(o))),
+            );
+            i = rest;
+        }
+        i = whitespace(i).map_err(|e| error(e, &context))?.0;
+    }
+
+    Ok(triples)
 }
 
-fn u32_char(i: &str) -> IResult<&str, char> {
-    let (i, (_, _, c)) = tuple((
-        char('\\'),
-        char('U'),
-        map_res(take_while_m_n(8, 8, |c: char| c.is_digit(16)), from_hex),
+/// Describe what was expected at the start of `rest` if a statement couldn't be parsed.
+fn expectation(rest: &str, context: &Context) -> String {
+    let prefixed_name = separated_pair(name, char(':'), name)(rest);
+    if rest.is_empty() {
+        "expected '.' to end the statement".to_owned()
+    } else if object(rest, context).is_ok() {
+        "expected '.', ';' or ',' before the next term".to_owned()
+    } else if rest.starts_with('<') {
+        "expected a valid IRI enclosed in '<' and '>'".to_owned()
+    } else if rest.starts_with('"') {
+        "expected a string literal enclosed in '\"'".to_owned()
+    } else if rest.starts_with('@') {
+        "expected a prefix directive like '@prefix ex: <http://example.org/> .' or a base \
+         directive like '@base <http://example.org/> .'"
+            .to_owned()
+    } else if let Some((_, (prefix, _))) = prefixed_name
+        .ok()
+        .filter(|(_, (prefix, _))| context.prefixes.get(prefix).is_none())
+    {
+        format!("undefined prefix '{}:'", prefix)
+    } else {
+        "expected an IRI, a prefixed name or a literal".to_owned()
+    }
+}
+
+/// Write a graph as a Turtle document.
+///
+/// All `prefixes`, given as pairs of a prefix name and a namespace IRI, are declared at the start of
+/// the document, and IRIs in these namespaces are shortened to prefixed names if possible. Like
+/// [`PrefixMap::abbreviate`](../struct.PrefixMap.html#method.abbreviate), the longest matching
+/// namespace is used. The triples are grouped by subject, with `;` separating the predicates of a
+/// subject and `,` separating the objects of a predicate, and `rdf:type` is written as `a`. Subjects, predicates
+/// and objects are written in the order of [`Node`](../struct.Node.html)'s `Ord` implementation,
+/// which is stable for IRIs and literals. Blank nodes are labeled `_:b0`, `_:b1`, and so on.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::graph;
+///
+/// let graph = graph! {
+///     "http://example.org/alice" -> "http://www.w3.org/1999/02/22-rdf-syntax-ns#type" -> "http://example.org/Person";
+///     "http://example.org/alice" -> "http://example.org/knows" -> "http://example.org/bob";
+///     "http://example.org/alice" -> "http://example.org/knows" -> "http://example.org/carol";
+/// };
+///
+/// let mut document: Vec<u8> = Vec::new();
+/// arrdf::turtle::write_turtle(&graph, &[("ex", "http://example.org/")], &mut document).unwrap();
+/// assert_eq!(
+///     "@prefix ex: <http://example.org/> .\n\
+///      \n\
+///      ex:alice a ex:Person ;\n    \
+///      ex:knows ex:bob, ex:carol .\n",
+///     String::from_utf8(document).unwrap()
+/// );
+/// ```
+pub fn write_turtle<G: Graph, W: Write>(
+    graph: &G,
+    prefixes: &[(&str, &str)],
+    out: &mut W,
+) -> io::Result<()> {
+    for (prefix, namespace) in prefixes {
+        writeln!(out, "@prefix {}: <{}> .", prefix, namespace)?;
+    }
+
+    let mut subjects: BTreeMap<&Node, BTreeMap<&Node, BTreeSet<&Node>>> = BTreeMap::new();
+    for (subject, predicate, object) in graph.iter() {
+        subjects
+            .entry(subject)
+            .or_default()
+            .entry(predicate)
+            .or_default()
+            .insert(object);
+    }
+
+    let rdf_type = rdf::type_();
+    let prefix_map: PrefixMap = prefixes.iter().copied().collect();
+    let mut labels = BlankNodeAllocator::new();
+    for (index, (subject, predicates)) in subjects.into_iter().enumerate() {
+        if index > 0 || !prefixes.is_empty() {
+            writeln!(out)?;
+        }
+        write_term(out, subject, &prefix_map, &mut labels)?;
+
+        let mut predicates: Vec<(&Node, BTreeSet<&Node>)> = predicates.into_iter().collect();
+        predicates.sort_by_key(|(predicate, _)| *predicate != &rdf_type);
+        for (index, (predicate, objects)) in predicates.into_iter().enumerate() {
+            write!(out, "{}", if index > 0 { " ;\n    " } else { " " })?;
+            if predicate == &rdf_type {
+                write!(out, "a")?;
+            } else {
+                write_term(out, predicate, &prefix_map, &mut labels)?;
+            }
+            for (index, object) in objects.into_iter().enumerate() {
+                write!(out, "{}", if index > 0 { ", " } else { " " })?;
+                write_term(out, object, &prefix_map, &mut labels)?;
+            }
+        }
+        writeln!(out, " .")?;
+    }
+    Ok(())
+}
+
+fn write_term<W: Write>(
+    out: &mut W,
+    node: &Node,
+    prefixes: &PrefixMap,
+    labels: &mut BlankNodeAllocator,
+) -> io::Result<()> {
+    match node.kind() {
+        NodeKind::Iri => write_iri(out, node.as_str(), prefixes),
+        NodeKind::Blank => write!(out, "_:b{}", labels.label_of_blank(node)),
+        NodeKind::Literal => {
+            write!(out, "\"{}\"", escape(node.lexical_form()))?;
+            match (node.datatype(), node.language()) {
+                (Some(datatype), _) => {
+                    write!(out, "^^")?;
+                    write_iri(out, datatype, prefixes)
+                }
+                (None, Some(language)) => write!(out, "@{}", language),
+                (None, None) => Ok(()),
+            }
+        }
+    }
+}
+
+/// Write an IRI as a prefixed name if one of the prefixes matches, or in full otherwise.
+fn write_iri<W: Write>(out: &mut W, iri: &str, prefixes: &PrefixMap) -> io::Result<()> {
+    match prefixes.abbreviate_iri(iri) {
+        Some(prefixed_name) => write!(out, "{}", prefixed_name),
+        None => write!(out, "<{}>", iri),
+    }
+}
+
+fn whitespace(i: &str) -> IResult<&str, ()> {
+    map(many0(alt((multispace1, comment))), |_| ())(i)
+}
+
+fn name(i: &str) -> IResult<&str, &str> {
+    take_while(is_name_char)(i)
+}
+
+fn prefix_directive(i: &str) -> IResult<&str, (&str, String)> {
+    let (i, (_, _, prefix, _, _, iri, _, _)) = tuple((
+        tag("@prefix"),
+        multispace1,
+        name,
+        char(':'),
+        whitespace,
+        iriref,
+        whitespace,
+        char('.'),
     ))(i)?;
-    Ok((i, c))
+    Ok((i, (prefix, iri)))
+}
+
+fn base_directive(i: &str) -> IResult<&str, String> {
+    delimited(
+        pair(tag("@base"), multispace1),
+        iriref,
+        pair(whitespace, char('.')),
+    )(i)
 }
 
-fn iriref(i: &str) -> IResult<&str, String> {
-    let (i, iri) = delimited(
-        char('<'),
-        many0(alt((none_of(r#"<>"{}|^`|\"#), u16_char, u32_char))),
-        char('>'),
+fn iri<'a>(i: &'a str, context: &Context) -> IResult<&'a str, Node> {
+    let prefixed_name = map_opt(separated_pair(name, char(':'), name), |(prefix, local)| {
+        context
+            .prefixes
+            .get(prefix)
+            .map(|namespace| Node::from(format!("{}{}", namespace, local).as_str()))
+    });
+    let iriref = map_res(iriref, |iri| {
+        context.resolve(&iri).map(|iri| Node::from(iri.as_str()))
+    });
+    alt((iriref, prefixed_name))(i)
+}
+
+fn rdf_literal<'a>(i: &'a str, context: &Context) -> IResult<&'a str, Node> {
+    let (i, lexical_form) = string_literal(i)?;
+    if let Ok((i, language)) = langtag(i) {
+        return Ok((i, Node::lang_literal(&lexical_form, language)));
+    }
+    let (i, datatype) = opt(preceded(tag("^^"), |i| iri(i, context)))(i)?;
+    let node = match datatype {
+        Some(datatype) => Node::typed_literal(&lexical_form, datatype.as_str()),
+        None => Node::plain_literal(&lexical_form),
+    };
+    Ok((i, node))
+}
+
+fn numeric_literal(i: &str) -> IResult<&str, Node> {
+    let exponent = |i| recognize(tuple((one_of("eE"), opt(one_of("+-")), digit1)))(i);
+    let double = recognize(pair(
+        opt(one_of("+-")),
+        alt((
+            recognize(tuple((digit1, char('.'), digit0, exponent))),
+            recognize(tuple((char('.'), digit1, exponent))),
+            recognize(pair(digit1, exponent)),
+        )),
+    ));
+    let decimal = recognize(tuple((opt(one_of("+-")), digit0, char('.'), digit1)));
+    let integer = recognize(pair(opt(one_of("+-")), digit1));
+
+    alt((
+        map(double, |literal| {
+            Node::typed_literal(literal, &xsd::double())
+        }),
+        map(decimal, |literal| {
+            Node::typed_literal(literal, &xsd::decimal())
+        }),
+        map(integer, |literal| {
+            Node::typed_literal(literal, &xsd::integer())
+        }),
+    ))(i)
+}
+
+fn boolean_literal(i: &str) -> IResult<&str, Node> {
+    map(alt((tag("true"), tag("false"))), |literal| {
+        Node::typed_literal(literal, &xsd::boolean())
+    })(i)
+}
+
+fn subject<'a>(i: &'a str, context: &Context) -> IResult<&'a str, Term<'a>> {
+    alt((
+        map(|i| iri(i, context), Term::Node),
+        map(blank_label, Term::Blank),
+    ))(i)
+}
+
+fn object<'a>(i: &'a str, context: &Context) -> IResult<&'a str, Term<'a>> {
+    alt((
+        |i| subject(i, context),
+        map(|i| rdf_literal(i, context), Term::Node),
+        map(numeric_literal, Term::Node),
+        map(boolean_literal, Term::Node),
+    ))(i)
+}
+
+fn verb<'a>(i: &'a str, context: &Context) -> IResult<&'a str, Node> {
+    alt((|i| iri(i, context), value(rdf::type_(), char('a'))))(i)
+}
+
+/// The triples of a statement whose blank node labels haven't been resolved yet.
+type Triples<'a> = Vec<(Term<'a>, Node, Term<'a>)>;
+
+fn triples<'a>(i: &'a str, context: &Context) -> IResult<&'a str, Triples<'a>> {
+    let (i, subject) = terminated(|i| self::subject(i, context), whitespace)(i)?;
+
+    let object_list = separated_nonempty_list(tuple((whitespace, char(','), whitespace)), |i| {
+        object(i, context)
+    });
+    let predicate_object_list = separated_nonempty_list(
+        tuple((whitespace, char(';'), whitespace)),
+        separated_pair(|i| verb(i, context), whitespace, object_list),
+    );
+    let (i, predicate_objects) = terminated(
+        predicate_object_list,
+        tuple((
+            whitespace,
+            opt(terminated(char(';'), whitespace)),
+            char('.'),
+        )),
     )(i)?;
 
-    Ok((i, iri.into_iter().collect()))
+    let triples = predicate_objects
+        .into_iter()
+        .flat_map(|(predicate, objects)| {
+            let subject = subject.clone();
+            objects
+                .into_iter()
+                .map(move |object| (subject.clone(), predicate.clone(), object))
+        })
+        .collect();
+    Ok((i, triples))
+}
+
+#[test]
+fn test_numeric_literal() {
+    assert_eq!(
+        (" .", Node::typed_literal("42", &xsd::integer())),
+        numeric_literal("42 .").unwrap()
+    );
+    assert_eq!(
+        (".", Node::typed_literal("-42", &xsd::integer())),
+        numeric_literal("-42.").unwrap()
+    );
+    assert_eq!(
+        (" .", Node::typed_literal("3.14", &xsd::decimal())),
+        numeric_literal("3.14 .").unwrap()
+    );
+    assert_eq!(
+        (" .", Node::typed_literal("1.0e6", &xsd::double())),
+        numeric_literal("1.0e6 .").unwrap()
+    );
+    assert_eq!(
+        (" .", Node::typed_literal("4E-2", &xsd::double())),
+        numeric_literal("4E-2 .").unwrap()
+    );
+    assert_eq!(
+        (" .", Node::typed_literal("true", &xsd::boolean())),
+        boolean_literal("true .").unwrap()
+    );
+}
+
+#[test]
+fn test_literal_shorthands() {
+    let graph = parse(
+        r#"
+        @prefix ex: <http://example.org/> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+        # Shorthands and their quoted equivalents describe the same nodes.
+        ex:alice ex:age 42, "42"^^xsd:integer, "43"^^<http://www.w3.org/2001/XMLSchema#integer> ;
+            ex:height 1.75, "1.80"^^xsd:decimal ;
+            ex:mass 6.5e1, "6.5e1"^^xsd:double ;
+            ex:admin true, "false"^^xsd:boolean ;
+            ex:name "Alice", "" .
+        "#,
+    )
+    .unwrap();
+
+    let alice = Node::from("http://example.org/alice");
+    let age = Node::from("http://example.org/age");
+    let height = Node::from("http://example.org/height");
+    let mass = Node::from("http://example.org/mass");
+    let admin = Node::from("http://example.org/admin");
+    let name = Node::from("http://example.org/name");
+
+    assert_eq!(9, graph.len());
+    assert!(graph.contains(&alice, &age, &Node::typed_literal("42", &xsd::integer())));
+    assert!(graph.contains(&alice, &age, &Node::typed_literal("43", &xsd::integer())));
+    assert!(graph.contains(
+        &alice,
+        &height,
+        &Node::typed_literal("1.75", &xsd::decimal())
+    ));
+    assert!(graph.contains(
+        &alice,
+        &height,
+        &Node::typed_literal("1.80", &xsd::decimal())
+    ));
+    assert!(graph.contains(&alice, &mass, &Node::typed_literal("6.5e1", &xsd::double())));
+    assert!(graph.contains(
+        &alice,
+        &admin,
+        &Node::typed_literal("true", &xsd::boolean())
+    ));
+    assert!(graph.contains(
+        &alice,
+        &admin,
+        &Node::typed_literal("false", &xsd::boolean())
+    ));
+    assert!(graph.contains(&alice, &name, &Node::from("Alice")));
+    assert!(graph.contains(&alice, &name, &Node::typed_literal("", &xsd::string())));
 }
 
 #[test]
-fn test_iriref() {
+fn test_parse_to_triples() {
+    let triples = parse_to_triples(
+        r#"
+        @prefix ex: <http://example.org/> .
+        ex:alice ex:knows ex:bob, ex:carol, ex:bob ;
+            a ex:Person .
+        "#,
+    )
+    .unwrap();
+
+    let alice = Node::from("http://example.org/alice");
+    let knows = Node::from("http://example.org/knows");
+    let bob = Node::from("http://example.org/bob");
+    let carol = Node::from("http://example.org/carol");
+    let person = Node::from("http://example.org/Person");
+    assert_eq!(
+        vec![
+            (alice.clone(), knows.clone(), bob.clone()),
+            (alice.clone(), knows.clone(), carol),
+            (alice.clone(), knows, bob),
+            (alice, rdf::type_(), person),
+        ],
+        triples
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = parse(
+        "@prefix ex: <http://example.org/> .\n\
+         ex:alice ex:knows ex:bob ;\n    \
+         foo:knows ex:carol .",
+    )
+    .unwrap_err();
+    assert_eq!((3, 5), (error.line, error.column));
+    assert_eq!("undefined prefix 'foo:'", error.message);
+
+    let error = parse("<urn:alice> <urn:knows> <urn:bob> <urn:carol> .").unwrap_err();
+    assert_eq!((1, 35), (error.line, error.column));
     assert_eq!(
-        (" abc", String::from("urn:Übung")),
-        iriref(r"<urn:\u00DCbung> abc").unwrap()
+        "expected '.', ';' or ',' before the next term",
+        error.message
     );
+
+    let error = parse("<urn:alice> <urn:knows> <urn:b{ob> .").unwrap_err();
+    assert_eq!((1, 25), (error.line, error.column));
+    assert_eq!(
+        "expected a valid IRI enclosed in '<' and '>'",
+        error.message
+    );
+
+    let error = parse("<urn:alice> <urn:name> \"Alice .").unwrap_err();
+    assert_eq!(24, error.column);
+    assert_eq!("expected a string literal enclosed in '\"'", error.message);
+
+    let error = parse("@prefix ex <http://example.org/> .").unwrap_err();
+    assert_eq!(1, error.column);
+    assert!(error.message.starts_with("expected a prefix directive"));
+
+    let error = parse("<urn:alice> <urn:knows> ; .").unwrap_err();
+    assert_eq!(25, error.column);
     assert_eq!(
-        (" abc", String::from("urn:Übung")),
-        iriref(r"<urn:\U000000DCbung> abc").unwrap()
+        "expected an IRI, a prefixed name or a literal",
+        error.message
     );
 }
+
+#[test]
+fn test_blank_node_labels() {
+    let graph = parse(
+        r#"
+        _:someone <urn:knows> _:other, <urn:alice> .
+        _:other <urn:knows> _:someone .
+        "#,
+    )
+    .unwrap();
+
+    let knows = Node::from("urn:knows");
+    let alice = Node::from("urn:alice");
+    assert_eq!(3, graph.len());
+    let someone = graph.iter().find(|(_, _, o)| *o == &alice).unwrap().0;
+    let other = graph
+        .objects(someone, &knows)
+        .map(|(_, _, o)| o)
+        .find(|o| o.is_blank())
+        .unwrap();
+    assert!(someone.is_blank());
+    assert_ne!(someone, other);
+    assert!(graph.contains(other, &knows, someone));
+}
+
+#[test]
+fn test_base_directive() {
+    let triples = parse_to_triples(
+        "@base <http://example.org/dir/doc> .\n\
+         <#alice> <knows> <../bob> .\n\
+         @prefix ex: <./vocab#> .\n\
+         <> ex:author <.> .\n\
+         @base <sub/> .\n\
+         <carol> <knows> <http://other.example/dave> .",
+    )
+    .unwrap();
+    let node = |iri: &str| Node::from(iri);
+    assert_eq!(
+        vec![
+            (
+                node("http://example.org/dir/doc#alice"),
+                node("http://example.org/dir/knows"),
+                node("http://example.org/bob"),
+            ),
+            (
+                node("http://example.org/dir/doc"),
+                node("http://example.org/dir/vocab#author"),
+                node("http://example.org/dir/"),
+            ),
+            (
+                node("http://example.org/dir/sub/carol"),
+                node("http://example.org/dir/sub/knows"),
+                node("http://other.example/dave"),
+            ),
+        ],
+        triples
+    );
+
+    let error = parse("<urn:a> <urn:b> <urn:c> .\n@base <relative> .").unwrap_err();
+    assert_eq!((2, 1), (error.line, error.column));
+    assert_eq!("'relative' is not a valid base IRI", error.message);
+}
+
+#[test]
+fn test_turtle_roundtrip() {
+    let blank = Node::blank();
+    let graph = crate::graph! {
+        "http://example.org/alice" -> "http://www.w3.org/1999/02/22-rdf-syntax-ns#type" -> "http://example.org/Person";
+        "http://example.org/alice" -> "http://example.org/knows" -> (blank);
+        "http://example.org/alice" -> "http://example.org/age" -> (Node::typed_literal("42", &xsd::integer()));
+        "http://example.org/alice" -> "http://example.org/homepage" -> "http://other.example.org/~alice";
+        (blank) -> "http://example.org/name" -> "Bob \"the\nbuilder\"";
+        (blank) -> "http://example.org/knows" -> "http://example.org/alice";
+        (blank) -> "http://example.org/greeting" -> (Node::literal("Bonjour").with_language("fr").build().unwrap());
+    };
+    let prefixes = [("ex", "http://example.org/"), ("xsd", xsd::NAMESPACE)];
+
+    let mut document: Vec<u8> = Vec::new();
+    write_turtle(&graph, &prefixes, &mut document).unwrap();
+    let document = String::from_utf8(document).unwrap();
+    assert!(document.contains("ex:alice a ex:Person ;"));
+    assert!(document.contains("\"42\"^^xsd:integer"));
+    assert!(document.contains("\"Bonjour\"@fr"));
+    assert!(document.contains("<http://other.example.org/~alice>"));
+
+    let parsed = parse(&document).unwrap();
+    assert!(crate::iso::is_isomorphic(&graph, &parsed));
+
+    let mut document: Vec<u8> = Vec::new();
+    write_turtle(&graph, &[], &mut document).unwrap();
+    assert!(crate::iso::is_isomorphic(
+        &graph,
+        &parse(std::str::from_utf8(&document).unwrap()).unwrap()
+    ));
+
+    let mut document: Vec<u8> = Vec::new();
+    write_turtle(&HashGraph::new(), &[], &mut document).unwrap();
+    assert!(document.is_empty());
+}
@@ -0,0 +1,58 @@
+//! Small helpers for working with iterators over triples.
+use crate::Node;
+use std::collections::HashSet;
+
+/// Yield every distinct triple of the iterator only once.
+///
+/// The triples that were already yielded are kept in a `HashSet`, so the memory usage grows with
+/// the number of distinct triples. This is useful to remove duplicates from chains of
+/// [set operations](../set/index.html) over more than two graphs.
+///
+/// ## Examples
+///
+/// ```
+/// use arrdf::{graph, set, util};
+///
+/// let a = graph! { "urn:alice" -> "urn:knows" -> "urn:bob"; };
+/// let b = graph! { "urn:alice" -> "urn:knows" -> "urn:bob"; };
+/// let c = graph! { "urn:bob" -> "urn:knows" -> "urn:carol"; };
+///
+/// let all = set::union(&a, &c).chain(set::union(&b, &c));
+/// assert_eq!(2, util::dedup_triples(all).count());
+/// ```
+pub fn dedup_triples<'a, I>(iter: I) -> impl Iterator<Item = (&'a Node, &'a Node, &'a Node)>
+where
+    I: IntoIterator<Item = (&'a Node, &'a Node, &'a Node)>,
+{
+    let mut seen = HashSet::new();
+    iter.into_iter().filter(move |triple| seen.insert(*triple))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn dedup_triples() {
+        let validator = Validator::new(HashGraph::new());
+        let graph = &validator.graph;
+        let repeated: Vec<(&Node, &Node, &Node)> = graph
+            .iter()
+            .chain(graph.iter())
+            .chain(graph.iter())
+            .collect();
+        assert_eq!(9, repeated.len());
+
+        let triples: Vec<(&Node, &Node, &Node)> = util::dedup_triples(repeated).collect();
+        assert_eq!(3, triples.len());
+        for triple in graph.iter() {
+            assert!(triples.contains(&triple));
+        }
+
+        // The first occurrence of a triple is kept, in order.
+        let first = graph.iter().next().unwrap();
+        let triples: Vec<(&Node, &Node, &Node)> =
+            util::dedup_triples(vec![first, first, first]).collect();
+        assert_eq!(vec![first], triples);
+    }
+}
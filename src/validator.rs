@@ -98,6 +98,38 @@ impl<G: Graph> Validator<G> {
         assert!(!self
             .graph
             .contains(&self.node_a, &self.predicate_b, &self.node_b));
+
+        assert!(self
+            .graph
+            .contains_subject_predicate(&self.node_a, &self.predicate_a));
+        assert!(!self
+            .graph
+            .contains_subject_predicate(&self.node_a, &self.predicate_b));
+    }
+
+    fn is_ground(&mut self) {
+        assert!(!self.graph.is_ground());
+
+        // Remove the triples with the blank node.
+        self.graph
+            .remove(&self.node_b, &self.predicate_b, &self.node_c);
+        assert!(!self.graph.is_ground());
+        self.graph
+            .remove(&self.node_c, &self.predicate_c, &self.node_a);
+        assert!(self.graph.is_ground());
+    }
+
+    fn triples_sorted(&mut self) {
+        let sorted = self.graph.triples_sorted();
+        assert_eq!(3, sorted.len());
+        assert_eq!(
+            vec![
+                (&self.node_a, &self.predicate_a, &self.node_b),
+                (&self.node_b, &self.predicate_b, &self.node_c),
+                (&self.node_c, &self.predicate_c, &self.node_a),
+            ],
+            sorted
+        );
     }
 
     fn remove(&mut self) {
@@ -110,12 +142,51 @@ impl<G: Graph> Validator<G> {
         assert!(iter.contains(&(&self.node_b, &self.predicate_b, &self.node_c)));
     }
 
+    fn tuples(&mut self) {
+        self.graph
+            .remove_triple((&self.node_c, &self.predicate_c, &self.node_a));
+        assert_eq!(2, self.graph.len());
+        assert!(!self
+            .graph
+            .contains(&self.node_c, &self.predicate_c, &self.node_a));
+
+        self.graph.insert_triple((
+            self.node_c.clone(),
+            self.predicate_a.clone(),
+            self.node_a.clone(),
+        ));
+        assert_eq!(3, self.graph.len());
+        assert!(self
+            .graph
+            .contains(&self.node_c, &self.predicate_a, &self.node_a));
+    }
+
     fn retain(&mut self) {
         self.graph.retain(|_, _, object| object.is_blank());
 
         let iter: Vec<(&Node, &Node, &Node)> = self.graph.iter().collect();
         assert_eq!(1, iter.len());
         assert!(iter.contains(&(&self.node_b, &self.predicate_b, &self.node_c)));
+
+        assert!(!self
+            .graph
+            .contains_subject_predicate(&self.node_a, &self.predicate_a));
+    }
+
+    fn merge(&mut self) {
+        let mut other = crate::HashGraph::new();
+        other.clone_insert(&self.node_a, &self.predicate_a, &self.node_b);
+        other.clone_insert(&self.node_a, &self.predicate_a, &self.node_c);
+        self.graph.merge(&other);
+
+        assert_eq!(4, self.graph.len());
+        assert!(self
+            .graph
+            .contains(&self.node_a, &self.predicate_a, &self.node_b));
+        assert!(self
+            .graph
+            .contains(&self.node_a, &self.predicate_a, &self.node_c));
+        assert_eq!(2, other.len());
     }
 
     fn extend(&mut self) {
@@ -165,6 +236,10 @@ impl<G: Graph> Validator<G> {
         self.graph.clone_insert(node_a, predicate_a, node_a);
         self.graph.clone_insert(node_a, predicate_a, node_a);
 
+        assert_eq!(4, self.graph.len());
+        assert!(!self
+            .graph
+            .insert_if_absent(node_a.clone(), predicate_a.clone(), node_a.clone()));
         assert_eq!(4, self.graph.len());
         assert!(self.graph.contains(node_a, predicate_a, node_b));
         assert!(self.graph.contains(node_b, predicate_b, node_c));
@@ -174,6 +249,12 @@ impl<G: Graph> Validator<G> {
         self.graph.remove(node_a, predicate_a, node_a);
         self.graph.remove(node_a, predicate_a, node_a);
 
+        assert_eq!(3, self.graph.len());
+        assert!(self
+            .graph
+            .insert_if_absent(node_a.clone(), predicate_a.clone(), node_a.clone()));
+        assert_eq!(4, self.graph.len());
+        self.graph.remove(node_a, predicate_a, node_a);
         assert_eq!(3, self.graph.len());
         assert!(!self.graph.contains(node_a, predicate_a, node_a));
         assert!(self.graph.contains(node_a, predicate_a, node_b));
@@ -239,12 +320,20 @@ impl<G: Graph> Validator<G> {
         self.restore_graph();
         self.contains();
         self.restore_graph();
+        self.is_ground();
+        self.restore_graph();
+        self.triples_sorted();
+        self.restore_graph();
         self.remove();
         self.restore_graph();
+        self.tuples();
+        self.restore_graph();
         self.retain();
         self.restore_graph();
         self.extend();
         self.restore_graph();
+        self.merge();
+        self.restore_graph();
         self.duplicate_actions();
         self.restore_graph();
         self.relationships();
@@ -0,0 +1,134 @@
+//! Nodes for well-known vocabulary terms.
+//!
+//! Every term is provided as a function that returns a clone of a lazily created node, which makes
+//! re-using the terms cheap and avoids typos in hand-written IRIs:
+//!
+//! ```
+//! use arrdf::{Node, vocab::rdf};
+//!
+//! assert_eq!(Node::from("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"), rdf::type_());
+//! ```
+use crate::Node;
+
+macro_rules! terms {
+    ($namespace:literal; $($name:ident => $local:literal,)*) => {
+        /// The namespace IRI of the vocabulary.
+        pub const NAMESPACE: &str = $namespace;
+
+        $(
+            #[doc = concat!("`", $namespace, $local, "`")]
+            pub fn $name() -> Node {
+                thread_local! {
+                    static NODE: Node = Node::from(concat!($namespace, $local));
+                }
+                NODE.with(Node::clone)
+            }
+        )*
+    };
+}
+
+/// The RDF vocabulary.
+pub mod rdf {
+    use super::*;
+
+    terms! {
+        "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+        type_ => "type",
+        property => "Property",
+        statement => "Statement",
+        subject => "subject",
+        predicate => "predicate",
+        object => "object",
+        list => "List",
+        first => "first",
+        rest => "rest",
+        nil => "nil",
+        lang_string => "langString",
+    }
+}
+
+/// The RDF Schema vocabulary.
+pub mod rdfs {
+    use super::*;
+
+    terms! {
+        "http://www.w3.org/2000/01/rdf-schema#";
+        resource => "Resource",
+        class => "Class",
+        literal => "Literal",
+        datatype => "Datatype",
+        sub_class_of => "subClassOf",
+        sub_property_of => "subPropertyOf",
+        domain => "domain",
+        range => "range",
+        label => "label",
+        comment => "comment",
+        member => "member",
+        see_also => "seeAlso",
+        is_defined_by => "isDefinedBy",
+    }
+}
+
+/// The XML Schema datatypes.
+pub mod xsd {
+    use super::*;
+
+    terms! {
+        "http://www.w3.org/2001/XMLSchema#";
+        string => "string",
+        boolean => "boolean",
+        decimal => "decimal",
+        integer => "integer",
+        double => "double",
+        float => "float",
+        long => "long",
+        int => "int",
+        non_negative_integer => "nonNegativeInteger",
+        date => "date",
+        date_time => "dateTime",
+    }
+}
+
+/// The Vocabulary of Interlinked Datasets (VoID).
+pub mod void {
+    use super::*;
+
+    terms! {
+        "http://rdfs.org/ns/void#";
+        dataset => "Dataset",
+        triples => "triples",
+        entities => "entities",
+        classes => "classes",
+        properties => "properties",
+        distinct_subjects => "distinctSubjects",
+        distinct_objects => "distinctObjects",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms() {
+        assert_eq!(
+            Node::from("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+            rdf::type_()
+        );
+        assert_eq!(
+            Node::from("http://www.w3.org/2000/01/rdf-schema#subClassOf"),
+            rdfs::sub_class_of()
+        );
+        assert_eq!(
+            Node::from("http://www.w3.org/2001/XMLSchema#integer"),
+            xsd::integer()
+        );
+        assert!(xsd::integer().starts_with(xsd::NAMESPACE));
+
+        // Terms are shared, so retrieving them again doesn't allocate a new string.
+        assert!(std::sync::Arc::ptr_eq(
+            rdf::nil().internal(),
+            rdf::nil().internal()
+        ));
+    }
+}